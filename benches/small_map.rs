@@ -0,0 +1,70 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use cliargs::small_map::SmallMap;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::collections::HashMap;
+
+const SIZES: [usize; 5] = [8, 16, 24, 32, 64];
+
+fn option_names(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("option-name-{}", i)).collect()
+}
+
+fn bench_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert");
+    for n in SIZES {
+        let names = option_names(n);
+        group.bench_with_input(BenchmarkId::new("SmallMap", n), &names, |b, names| {
+            b.iter(|| {
+                let mut map: SmallMap<&str, Vec<&str>> = SmallMap::new();
+                for name in names.iter() {
+                    map.get_or_insert_default(name).push("a");
+                }
+                black_box(map)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("HashMap", n), &names, |b, names| {
+            b.iter(|| {
+                let mut map: HashMap<&str, Vec<&str>> = HashMap::new();
+                for name in names.iter() {
+                    map.entry(name).or_default().push("a");
+                }
+                black_box(map)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+    for n in SIZES {
+        let names = option_names(n);
+        let mut small: SmallMap<&str, Vec<&str>> = SmallMap::new();
+        let mut hashed: HashMap<&str, Vec<&str>> = HashMap::new();
+        for name in names.iter() {
+            small.insert(name, vec!["a"]);
+            hashed.insert(name, vec!["a"]);
+        }
+        group.bench_with_input(BenchmarkId::new("SmallMap", n), &names, |b, names| {
+            b.iter(|| {
+                for name in names.iter() {
+                    black_box(small.get(name.as_str()));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("HashMap", n), &names, |b, names| {
+            b.iter(|| {
+                for name in names.iter() {
+                    black_box(hashed.get(name.as_str()));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert, bench_lookup);
+criterion_main!(benches);
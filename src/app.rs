@@ -0,0 +1,1851 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::completion::{
+    candidates_or_directive, first_cmd_arg, print_candidates, split_line, ValueHint,
+};
+use crate::errors::{ErrorMessage, InvalidArgs, InvalidOption};
+use crate::exit;
+use crate::parse::classify_args;
+use crate::similar::{similar_names, suggest_opt, with_suggestion};
+use crate::Cmd;
+use crate::Constraints;
+use crate::OptCfg;
+use crate::OptCfgParam::{desc, names, store_key};
+use crate::ParseOptions;
+use crate::TokenKind;
+
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+
+const HELP_KEY: &str = "help";
+const VERSION_KEY: &str = "version";
+const HELP_CMD: &str = "help";
+
+type PostParseHook = fn(cmd: &Cmd) -> Result<(), InvalidOption>;
+
+/// Builds an application with its options and sub commands, and runs it with
+/// command line arguments.
+///
+/// An `App` wraps `Cmd` and `OptCfg`s, and adds the options `--help`/`-h` and
+/// `--version`/`-V` (only when a version is set) unless their names are already
+/// used by the specified option configurations.
+///
+/// ```
+/// use cliargs::app::{App, AppOutcome};
+/// use cliargs::OptCfg;
+/// use cliargs::OptCfgParam::{names, desc};
+///
+/// let app = App::new("tool")
+///     .version("1.0.0")
+///     .about("This is a tool.")
+///     .opt(OptCfg::with(&[names(&["verbose", "v"]), desc("Prints verbose logs.")]))
+///     .sub(App::new("sync").about("Synchronizes files."));
+///
+/// let args = ["tool", "-v", "sync", "dir"].map(|s| s.into());
+/// match app.run(args) {
+///     Ok(AppOutcome::Parsed(cmds)) => {
+///         assert_eq!(cmds[0].name(), "tool");
+///         assert_eq!(cmds[0].has_opt("verbose"), true);
+///         assert_eq!(cmds[1].name(), "sync");
+///         assert_eq!(cmds[1].args(), &["dir"]);
+///     }
+///     Ok(_) => { /* help or version was printed. */ }
+///     Err(err) => panic!("{}", err),
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct App {
+    name: String,
+    version: String,
+    about: String,
+    opt_cfgs: Vec<OptCfg>,
+    subs: Vec<App>,
+    allows_external_subs: bool,
+    parse_options: ParseOptions,
+    help_layout: HelpLayout,
+    examples: Vec<(String, String)>,
+    sections: Vec<(String, String)>,
+    header: String,
+    footer: String,
+    pre_parse_hooks: Vec<fn(args: &mut Vec<String>)>,
+    post_parse_hooks: Vec<PostParseHook>,
+    observer: Option<Observer>,
+}
+
+/// The trait for observers which are notified of the options and the sub
+/// commands used in command line arguments, for opt-in usage analytics.
+///
+/// An observer receives only names, and never receives option arguments or
+/// command arguments.
+///
+/// ```
+/// use cliargs::app::{App, UsageObserver};
+/// use cliargs::OptCfg;
+/// use cliargs::OptCfgParam::names;
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Default)]
+/// struct Log(Mutex<Vec<String>>);
+///
+/// impl UsageObserver for Log {
+///     fn observe(&self, path: &[&str], opt_keys: &[&str]) {
+///         self.0.lock().unwrap().push(format!("{} {:?}", path.join(" "), opt_keys));
+///     }
+/// }
+///
+/// let log = Arc::new(Log::default());
+///
+/// let app = App::new("tool")
+///     .opt(OptCfg::with(&[names(&["verbose", "v"])]))
+///     .sub(App::new("sync"))
+///     .observer(log.clone());
+///
+/// let args = ["tool", "-v", "sync", "secret.txt"].map(|s| s.into());
+/// app.run(args).unwrap();
+/// assert_eq!(*log.0.lock().unwrap(), ["tool [\"verbose\"]", "tool sync []"]);
+/// ```
+pub trait UsageObserver {
+    /// Is called for each command from the top command to the selected sub
+    /// command after `App#run` parses command line arguments successfully.
+    ///
+    /// `path` is the command path like `["tool", "sync"]`, and `opt_keys` are
+    /// the store keys of the options specified in the command line arguments
+    /// in the order of their first appearances.
+    /// The options which are set by default values or environment variables
+    /// are not included.
+    /// This method is not called when `--help` or `--version` is specified.
+    fn observe(&self, path: &[&str], opt_keys: &[&str]);
+}
+
+impl<T: UsageObserver + ?Sized> UsageObserver for &T {
+    fn observe(&self, path: &[&str], opt_keys: &[&str]) {
+        (**self).observe(path, opt_keys);
+    }
+}
+
+impl<T: UsageObserver + ?Sized> UsageObserver for Arc<T> {
+    fn observe(&self, path: &[&str], opt_keys: &[&str]) {
+        (**self).observe(path, opt_keys);
+    }
+}
+
+struct Observer(Box<dyn UsageObserver>);
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UsageObserver")
+    }
+}
+
+/// The error of running the command which is at the path of command names,
+/// with the option name suggested for it.
+struct RunError {
+    err: Box<InvalidArgs>,
+    path: Vec<String>,
+    suggestion: Option<String>,
+}
+
+impl RunError {
+    fn at<E: Into<InvalidArgs>>(path: &[String]) -> impl FnOnce(E) -> Self + '_ {
+        move |err| RunError {
+            err: Box::new(err.into()),
+            path: path.to_vec(),
+            suggestion: None,
+        }
+    }
+
+    fn of_parse(cmd: &Cmd, err: InvalidOption, opt_cfgs: &[OptCfg], path: &[String]) -> Self {
+        RunError {
+            suggestion: suggest_opt(cmd, &err, opt_cfgs),
+            err: Box::new(err.into()),
+            path: path.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct HelpLayout {
+    desc_width: usize,
+    name_width: usize,
+}
+
+/// The enum type for the results of `App#run`.
+#[derive(Debug)]
+pub enum AppOutcome<'a> {
+    /// Indicates that the help text was printed because `--help` was
+    /// specified.
+    HelpPrinted,
+
+    /// Indicates that the version was printed because `--version` was
+    /// specified.
+    VersionPrinted,
+
+    /// Indicates that completion candidates were printed because the hidden
+    /// sub command `__complete` was specified.
+    CompletionPrinted,
+
+    /// Holds the parsed `Cmd`s from the top command to the selected sub
+    /// command.
+    Parsed(Vec<Cmd<'a>>),
+
+    /// Indicates that an unknown sub command is resolved to an external
+    /// executable named like `app-foo` on `PATH`.
+    ExternalSubCmd {
+        /// The sub command name.
+        name: String,
+
+        /// The path of the external executable.
+        path: PathBuf,
+
+        /// The arguments after the sub command name.
+        args: Vec<String>,
+
+        /// The parsed `Cmd`s from the top command to the parent of the sub
+        /// command.
+        cmds: Vec<Cmd<'a>>,
+    },
+}
+
+impl App {
+    /// Creates an `App` instance with the specified command name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the version which is printed with `--version`.
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = version.to_string();
+        self
+    }
+
+    /// Sets the description which is printed in the help text.
+    pub fn about(mut self, about: &str) -> Self {
+        self.about = about.to_string();
+        self
+    }
+
+    /// Adds an option configuration.
+    pub fn opt(mut self, opt_cfg: OptCfg) -> Self {
+        self.opt_cfgs.push(opt_cfg);
+        self
+    }
+
+    /// Adds a sub command.
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub(mut self, sub: App) -> Self {
+        self.subs.push(sub);
+        self
+    }
+
+    /// Adds a hook which is called before parsing command line arguments.
+    ///
+    /// The hook receives the command line arguments including the command
+    /// path at the first element, and can rewrite them, for example, to expand
+    /// aliases.
+    /// Hooks are called in the order in which they were added.
+    pub fn pre_parse(mut self, hook: fn(args: &mut Vec<String>)) -> Self {
+        self.pre_parse_hooks.push(hook);
+        self
+    }
+
+    /// Adds a hook which is called after parsing command line arguments.
+    ///
+    /// The hook receives the parsed `Cmd` of this application, and can
+    /// validate it across options.
+    /// If a hook returns an error, `App#run` stops and returns it.
+    /// Hooks are not called when `--help` or `--version` is specified.
+    pub fn post_parse(mut self, hook: PostParseHook) -> Self {
+        self.post_parse_hooks.push(hook);
+        self
+    }
+
+    /// Sets an observer which is notified of the options and the sub commands
+    /// used in command line arguments.
+    ///
+    /// The observer is called only by the top application, for all commands
+    /// from the top command to the selected sub command.
+    pub fn observer(mut self, observer: impl UsageObserver + 'static) -> Self {
+        self.observer = Some(Observer(Box::new(observer)));
+        self
+    }
+
+    /// Enables to resolve an unknown sub command `foo` to an external
+    /// executable `app-foo` on `PATH`, like cargo or git.
+    ///
+    /// If the executable is found, `App#run` returns
+    /// `AppOutcome::ExternalSubCmd`, which can be executed with
+    /// `run_external_sub_cmd`.
+    pub fn external_subs(mut self, allows: bool) -> Self {
+        self.allows_external_subs = allows;
+        self
+    }
+
+    /// Sets the switches of parsing behaviors for this command.
+    ///
+    /// The switches are not inherited by sub commands, so the same switches
+    /// need to be set to each sub command if needed.
+    pub fn parse_options(mut self, options: ParseOptions) -> Self {
+        self.parse_options = options;
+        self
+    }
+
+    /// Sets the maximum width of option and sub command descriptions in the
+    /// help text.
+    ///
+    /// A longer description is wrapped at spaces onto continuation lines
+    /// aligned under the description column.
+    /// The width `0`, which is the default, means no wrapping.
+    pub fn desc_width(mut self, width: usize) -> Self {
+        self.help_layout.desc_width = width;
+        self
+    }
+
+    /// Sets the maximum width of the name column of options and sub commands
+    /// in the help text.
+    ///
+    /// The description of a row of which the names are longer than this
+    /// width starts on the next line, like the help of GNU tools.
+    /// The width `0`, which is the default, means no limit.
+    pub fn name_width(mut self, width: usize) -> Self {
+        self.help_layout.name_width = width;
+        self
+    }
+
+    /// Adds an example which is shown in the `Examples:` section of the help
+    /// text, with a command line and its description.
+    pub fn example(mut self, cmd: &str, description: &str) -> Self {
+        self.examples
+            .push((cmd.to_string(), description.to_string()));
+        self
+    }
+
+    /// Adds a section which is shown after the options and the sub commands
+    /// in the help text, like `Notes:`.
+    ///
+    /// The body is indented and wrapped like descriptions.
+    pub fn section(mut self, title: &str, body: &str) -> Self {
+        self.sections.push((title.to_string(), body.to_string()));
+        self
+    }
+
+    /// Sets the text which is shown before the usage line in the help text,
+    /// like a banner.
+    pub fn header(mut self, text: &str) -> Self {
+        self.header = text.to_string();
+        self
+    }
+
+    /// Sets the text which is shown at the end of the help text, like
+    /// `See 'app help <command>' for more information.`.
+    pub fn footer(mut self, text: &str) -> Self {
+        self.footer = text.to_string();
+        self
+    }
+
+    /// Returns the command name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the help text of this application.
+    pub fn help_text(&self) -> String {
+        self.help_text_with_path(&self.name)
+    }
+
+    /// Returns the extended help text of this application, which is the help
+    /// text followed by the option tables of all sub commands, like
+    /// `docker --help` or `ip help`.
+    ///
+    /// Each option table has a heading of the command path and the
+    /// description of the sub command, and the sub commands are listed in
+    /// depth-first order.
+    ///
+    /// ```
+    /// use cliargs::app::App;
+    /// use cliargs::OptCfg;
+    /// use cliargs::OptCfgParam::{names, has_arg, desc};
+    ///
+    /// let app = App::new("tool")
+    ///     .sub(App::new("sync")
+    ///         .about("Synchronizes files.")
+    ///         .opt(OptCfg::with(&[names(&["depth"]), has_arg(true), desc("Limits the depth.")])))
+    ///     .sub(App::new("list"));
+    ///
+    /// assert_eq!(app.extended_help_text(), "\
+    /// Usage: tool [OPTIONS] [COMMAND]
+    ///
+    /// Options:
+    ///   --help, -h  Prints help.
+    ///
+    /// Commands:
+    ///   sync  Synchronizes files.
+    ///   list
+    ///   help  Prints help of a command.
+    ///
+    /// tool sync - Synchronizes files.
+    ///   --depth <VALUE>  Limits the depth.
+    ///
+    /// tool list
+    /// ");
+    /// ```
+    pub fn extended_help_text(&self) -> String {
+        let mut text = self.help_text();
+        for sub in self.subs.iter() {
+            sub.write_sub_cmd_tables(&mut text, &self.name, self.help_layout);
+        }
+        text
+    }
+
+    fn write_sub_cmd_tables(&self, text: &mut String, parent_path: &str, layout: HelpLayout) {
+        let path = format!("{} {}", parent_path, self.name);
+        let heading = if self.about.is_empty() {
+            path.clone()
+        } else {
+            format!("{} - {}", path, self.about)
+        };
+        let rows = opt_rows(&self.opt_cfgs);
+        if rows.is_empty() {
+            text.push('\n');
+            text.push_str(&heading);
+            text.push('\n');
+        } else {
+            write_rows(text, &heading, &rows, layout);
+        }
+        for sub in self.subs.iter() {
+            sub.write_sub_cmd_tables(text, &path, layout);
+        }
+    }
+
+    /// Labels each of the specified command line arguments with its kind as
+    /// `App#run` sees it, including sub command names.
+    ///
+    /// The first element of the returned vector is `TokenKind::Command`.
+    /// Like `Cmd#classify_with`, this method does not fail even if the command
+    /// line arguments are invalid.
+    /// The hooks added by `App#pre_parse` are not applied.
+    ///
+    /// ```
+    /// use cliargs::app::App;
+    /// use cliargs::{OptCfg, TokenKind};
+    /// use cliargs::OptCfgParam::names;
+    ///
+    /// let app = App::new("tool")
+    ///     .opt(OptCfg::with(&[names(&["verbose", "v"])]))
+    ///     .sub(App::new("sync"));
+    ///
+    /// assert_eq!(app.classify(&["tool", "-v", "sync", "dir"]), [
+    ///     TokenKind::Command,
+    ///     TokenKind::Option { names: vec!["v"] },
+    ///     TokenKind::SubCmd,
+    ///     TokenKind::Positional,
+    /// ]);
+    /// ```
+    pub fn classify<'a>(&self, args: &[&'a str]) -> Vec<TokenKind<'a>> {
+        if args.is_empty() {
+            return Vec::new();
+        }
+        let mut kinds = vec![TokenKind::Command];
+        self.classify_after_name(&args[1..], &mut kinds);
+        kinds
+    }
+
+    fn classify_after_name<'a>(&self, args: &[&'a str], kinds: &mut Vec<TokenKind<'a>>) {
+        let opt_cfgs = self.opt_cfgs_with_builtins();
+        let has_subs = !self.subs.is_empty() || self.allows_external_subs;
+        let options = &self.parse_options;
+        let (arg_kinds, first_arg) = classify_args(args, &opt_cfgs, options, has_subs);
+
+        match first_arg {
+            Some(i) if args[i] == HELP_CMD && self.owns_help_cmd() => {
+                kinds.extend(arg_kinds.into_iter().take(i));
+                kinds.push(TokenKind::SubCmd);
+                kinds.resize(kinds.len() + args.len() - i - 1, TokenKind::Positional);
+            }
+            Some(i) => match self.subs.iter().find(|sub| sub.name == args[i]) {
+                Some(sub) => {
+                    kinds.extend(arg_kinds.into_iter().take(i));
+                    kinds.push(TokenKind::SubCmd);
+                    sub.classify_after_name(&args[i + 1..], kinds);
+                }
+                None => kinds.extend(classify_args(args, &opt_cfgs, options, false).0),
+            },
+            None => kinds.extend(arg_kinds),
+        }
+    }
+
+    /// Parses the specified command line arguments and dispatches them to the
+    /// sub commands.
+    ///
+    /// If `--help` or `--version` is specified, this method prints the help
+    /// text or the version to stdout, and returns `AppOutcome::HelpPrinted` or
+    /// `AppOutcome::VersionPrinted`.
+    /// Otherwise, this method returns the parsed `Cmd`s from the top command
+    /// to the selected sub command.
+    /// If this application has sub commands, the first command argument is
+    /// regarded as a sub command name, and an unknown name causes
+    /// `InvalidArgs::SubCmdIsUnknown` with the similar sub command names.
+    ///
+    /// If this application has sub commands and none of them is named
+    /// `help`, the built-in sub command `help` is provided, which prints the
+    /// help text of the command specified by the following names, like
+    /// `app help remote add`, and returns `AppOutcome::HelpPrinted`.
+    ///
+    /// If the hidden sub command `__complete` is specified by the scripts of
+    /// `completion::script`, this method prints the completion candidates of
+    /// the options and the sub commands of the command at the cursor, and
+    /// returns `AppOutcome::CompletionPrinted`.
+    pub fn run<'a>(
+        &self,
+        osargs: impl IntoIterator<Item = OsString>,
+    ) -> Result<AppOutcome<'a>, InvalidArgs> {
+        self.run_with_path(osargs).map_err(|e| *e.err)
+    }
+
+    fn run_with_path<'a>(
+        &self,
+        osargs: impl IntoIterator<Item = OsString>,
+    ) -> Result<AppOutcome<'a>, RunError> {
+        let path = std::slice::from_ref(&self.name);
+        let cmd = Cmd::with_os_strings(osargs).map_err(RunError::at(path))?;
+        if print_candidates(self.completion_request(&cmd)) {
+            return Ok(AppOutcome::CompletionPrinted);
+        }
+        let outcome = self.run_cmd(cmd, path)?;
+        if let Some(Observer(observer)) = &self.observer {
+            match &outcome {
+                AppOutcome::Parsed(cmds) => notify_usage(observer.as_ref(), cmds, None),
+                AppOutcome::ExternalSubCmd { name, cmds, .. } => {
+                    notify_usage(observer.as_ref(), cmds, Some(name))
+                }
+                _ => {}
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Parses the specified command line arguments like `App#run`, but
+    /// exits the process instead of returning except for the parsed `Cmd`s.
+    ///
+    /// If `--help` or `--version` is specified, this method prints the help
+    /// text or the version to stdout, and exits with the status 0.
+    /// If the command line arguments are invalid, this method prints an error
+    /// message and a usage hint of the failing (sub) command, like
+    /// `tool remote add: error: ...`, to stderr, and exits with the status 2.
+    /// If the error is of an unconfigured option, the message suggests the
+    /// option of which the name is the most similar.
+    /// If an external sub command is resolved, this method executes it and
+    /// exits with its status.
+    pub fn run_or_exit<'a>(&self, osargs: impl IntoIterator<Item = OsString>) -> Vec<Cmd<'a>> {
+        match self.run_with_path(osargs) {
+            Ok(AppOutcome::Parsed(cmds)) => cmds,
+            Ok(AppOutcome::ExternalSubCmd { path, args, .. }) => {
+                match run_external_sub_cmd(&path, &args) {
+                    Ok(status) => process::exit(status.code().unwrap_or(1)),
+                    Err(err) => {
+                        eprintln!("{}: {}: {}", self.name, path.display(), err);
+                        process::exit(2);
+                    }
+                }
+            }
+            Ok(_) => process::exit(exit::OK),
+            Err(err) => {
+                eprint!("{}", self.run_error_message(&err));
+                process::exit(exit::USAGE);
+            }
+        }
+    }
+
+    fn run_error_message(&self, err: &RunError) -> String {
+        let app = err.path[1..].iter().fold(self, |app, name| {
+            app.subs.iter().find(|sub| sub.name == *name).unwrap_or(app)
+        });
+        let msg = with_suggestion(&err.err, err.suggestion.as_deref());
+        sub_cmd_error_message(&err.path, &app.usage(&err.path.join(" ")), &msg)
+    }
+
+    fn run_cmd<'a>(&self, mut cmd: Cmd<'a>, path: &[String]) -> Result<AppOutcome<'a>, RunError> {
+        if !self.pre_parse_hooks.is_empty() {
+            let mut args: Vec<String> = cmd._arg_refs.iter().map(|s| s.to_string()).collect();
+            for hook in self.pre_parse_hooks.iter() {
+                hook(&mut args);
+            }
+            let arg_offset = cmd.arg_offset;
+            cmd = Cmd::with_strings(args);
+            cmd.arg_offset = arg_offset;
+        }
+
+        cmd.path = path.to_vec();
+        cmd.parse_options = self.parse_options.clone();
+
+        let n_args = cmd._arg_refs.len();
+        let opt_cfgs = self.opt_cfgs_with_builtins();
+
+        // Without internal sub commands, the first command argument is a sub
+        // command only if it is resolved to an external executable, so it is
+        // resolved before parsing not to parse the arguments twice.
+        let external = if self.subs.is_empty() && self.allows_external_subs {
+            classify_args(&cmd._arg_refs[1..], &opt_cfgs, &self.parse_options, true)
+                .1
+                .and_then(|i| self.find_external_sub_cmd(cmd._arg_refs[i + 1], path))
+        } else {
+            None
+        };
+
+        let has_subs = !self.subs.is_empty() || external.is_some();
+        let idx = match cmd.parse_with_until_1st_arg(&opt_cfgs, has_subs) {
+            Ok(idx) => idx,
+            Err(err) => return Err(RunError::of_parse(&cmd, err, &opt_cfgs, path)),
+        };
+
+        if cmd.has_opt(HELP_KEY) && self.owns_builtin(HELP_KEY) {
+            print!("{}", self.help_text_with_path(&path.join(" ")));
+            return Ok(AppOutcome::HelpPrinted);
+        }
+        if cmd.has_opt(VERSION_KEY) && self.owns_builtin(VERSION_KEY) {
+            println!("{} {}", self.name, self.version);
+            return Ok(AppOutcome::VersionPrinted);
+        }
+
+        let i = match idx {
+            Some(i) => i,
+            None => {
+                self.call_post_parse_hooks(&cmd)
+                    .map_err(RunError::at(path))?;
+                return Ok(AppOutcome::Parsed(vec![cmd]));
+            }
+        };
+
+        if cmd._arg_refs[i] == HELP_CMD && self.owns_help_cmd() {
+            let mut app = self;
+            let mut help_path = path.to_vec();
+            for name in cmd._arg_refs[i + 1..n_args].iter() {
+                match app.subs.iter().find(|sub| sub.name == *name) {
+                    Some(sub) => {
+                        app = sub;
+                        help_path.push(sub.name.clone());
+                    }
+                    None => {
+                        let suggestions =
+                            similar_names(name, app.subs.iter().map(|sub| sub.name()));
+                        return Err(RunError::at(&help_path)(InvalidArgs::SubCmdIsUnknown {
+                            name: name.to_string(),
+                            suggestions: suggestions.iter().map(|s| s.to_string()).collect(),
+                        }));
+                    }
+                }
+            }
+            print!("{}", app.help_text_with_path(&help_path.join(" ")));
+            return Ok(AppOutcome::HelpPrinted);
+        }
+
+        match self.subs.iter().find(|sub| sub.name == cmd._arg_refs[i]) {
+            Some(sub) => {
+                self.call_post_parse_hooks(&cmd)
+                    .map_err(RunError::at(path))?;
+                let mut sub_cmd =
+                    Cmd::with_strings(cmd._arg_refs[i..n_args].iter().map(|s| s.to_string()));
+                sub_cmd.arg_offset = cmd.arg_offset + i;
+                let mut sub_path = path.to_vec();
+                sub_path.push(sub.name.clone());
+                match sub.run_cmd(sub_cmd, &sub_path)? {
+                    AppOutcome::Parsed(mut cmds) => {
+                        cmds.insert(0, cmd);
+                        Ok(AppOutcome::Parsed(cmds))
+                    }
+                    AppOutcome::ExternalSubCmd {
+                        name,
+                        path,
+                        args,
+                        mut cmds,
+                    } => {
+                        cmds.insert(0, cmd);
+                        Ok(AppOutcome::ExternalSubCmd {
+                            name,
+                            path,
+                            args,
+                            cmds,
+                        })
+                    }
+                    outcome => Ok(outcome),
+                }
+            }
+            None => {
+                let name = cmd._arg_refs[i];
+                let exe = match external {
+                    Some(exe) => Some(exe),
+                    None if self.allows_external_subs => self.find_external_sub_cmd(name, path),
+                    None => None,
+                };
+                if let Some(exe) = exe {
+                    self.call_post_parse_hooks(&cmd)
+                        .map_err(RunError::at(path))?;
+                    let args = cmd._arg_refs[i + 1..n_args]
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect();
+                    return Ok(AppOutcome::ExternalSubCmd {
+                        name: name.to_string(),
+                        path: exe,
+                        args,
+                        cmds: vec![cmd],
+                    });
+                }
+
+                let suggestions = similar_names(name, self.subs.iter().map(|sub| sub.name()));
+                Err(RunError::at(path)(InvalidArgs::SubCmdIsUnknown {
+                    name: name.to_string(),
+                    suggestions: suggestions.iter().map(|s| s.to_string()).collect(),
+                }))
+            }
+        }
+    }
+
+    fn find_external_sub_cmd(&self, name: &str, path: &[String]) -> Option<PathBuf> {
+        let file_name = format!("{}-{}{}", path.join("-"), name, env::consts::EXE_SUFFIX);
+        env::var_os("PATH").and_then(|p| find_in_path(&file_name, &p))
+    }
+
+    fn completion_request(&self, cmd: &Cmd) -> Option<Vec<String>> {
+        let (line, point) = cmd.completion_line()?;
+        let (words, current) = split_line(line, point);
+
+        let mut app = self;
+        let mut words = &words[..];
+        let mut opt_cfgs = app.opt_cfgs_with_builtins();
+        while let Some(i) = first_cmd_arg(&opt_cfgs, words) {
+            match app.subs.iter().find(|sub| sub.name == words[i]) {
+                Some(sub) => {
+                    app = sub;
+                    words = &words[i + 1..];
+                    opt_cfgs = app.opt_cfgs_with_builtins();
+                }
+                None => break,
+            }
+        }
+
+        let mut sub_cmds: Vec<&str> = app.subs.iter().map(|sub| sub.name()).collect();
+        if app.owns_help_cmd() {
+            sub_cmds.push(HELP_CMD);
+        }
+        Some(candidates_or_directive(
+            &opt_cfgs,
+            &Constraints::new(),
+            &sub_cmds,
+            words,
+            current,
+        ))
+    }
+
+    fn call_post_parse_hooks(&self, cmd: &Cmd) -> Result<(), InvalidOption> {
+        for hook in self.post_parse_hooks.iter() {
+            hook(cmd)?;
+        }
+        Ok(())
+    }
+
+    fn owns_help_cmd(&self) -> bool {
+        !self.subs.is_empty() && !self.subs.iter().any(|sub| sub.name == HELP_CMD)
+    }
+
+    fn owns_builtin(&self, key: &str) -> bool {
+        !self
+            .opt_cfgs
+            .iter()
+            .any(|cfg| store_key_of(cfg) == Some(key))
+    }
+
+    fn is_name_used(&self, name: &str) -> bool {
+        self.opt_cfgs.iter().any(|cfg| {
+            if cfg.names.is_empty() {
+                store_key_of(cfg) == Some(name)
+            } else {
+                cfg.names.iter().any(|n| n == name)
+            }
+        })
+    }
+
+    fn opt_cfgs_with_builtins(&self) -> Vec<OptCfg> {
+        let mut opt_cfgs = self.opt_cfgs.clone();
+
+        let mut add = |key: &str, cands: &[&str], description: &str| {
+            if !self.owns_builtin(key) {
+                return;
+            }
+            let cands: Vec<&str> = cands
+                .iter()
+                .filter(|n| !self.is_name_used(n))
+                .copied()
+                .collect();
+            if !cands.is_empty() {
+                opt_cfgs.push(OptCfg::with(&[
+                    store_key(key),
+                    names(&cands),
+                    desc(description),
+                ]));
+            }
+        };
+
+        add(HELP_KEY, &["help", "h"], "Prints help.");
+        if !self.version.is_empty() {
+            add(VERSION_KEY, &["version", "V"], "Prints version.");
+        }
+
+        opt_cfgs
+    }
+
+    /// Returns the compact help text which consists of only the usage line
+    /// and a hint to see the full help, intended to accompany an error
+    /// message.
+    pub fn usage_only(&self) -> String {
+        usage_only_text(&self.usage(&self.name), &self.name)
+    }
+
+    /// Prints the help text of this application to stderr.
+    pub fn print_help_to_stderr(&self) {
+        eprint!("{}", self.help_text());
+    }
+
+    fn usage(&self, path: &str) -> String {
+        if self.subs.is_empty() {
+            format!("{} [OPTIONS]", path)
+        } else {
+            format!("{} [OPTIONS] [COMMAND]", path)
+        }
+    }
+
+    fn help_text_with_path(&self, path: &str) -> String {
+        let usage = self.usage(path);
+        let mut sub_rows: Vec<(String, &str)> = self
+            .subs
+            .iter()
+            .map(|sub| (sub.name.clone(), sub.about.as_str()))
+            .collect();
+        if self.owns_help_cmd() {
+            sub_rows.push((HELP_CMD.to_string(), "Prints help of a command."));
+        }
+        let mut text = String::new();
+        if !self.header.is_empty() {
+            text.push_str(&self.header);
+            text.push_str("\n\n");
+        }
+
+        text.push_str(&help_text(
+            &usage,
+            &self.about,
+            &self.opt_cfgs_with_builtins(),
+            &sub_rows,
+            self.help_layout,
+        ));
+
+        if !self.examples.is_empty() {
+            text.push_str("\nExamples:\n");
+            for (cmd, description) in self.examples.iter() {
+                text.push_str(&format!("  {}\n", cmd));
+                for line in wrap_text(description, self.help_layout.desc_width) {
+                    text.push_str(&format!("      {}\n", line));
+                }
+            }
+        }
+
+        for (title, body) in self.sections.iter() {
+            text.push_str(&format!("\n{}:\n", title));
+            for line in wrap_text(body, self.help_layout.desc_width) {
+                if line.is_empty() {
+                    text.push('\n');
+                } else {
+                    text.push_str(&format!("  {}\n", line));
+                }
+            }
+        }
+
+        if !self.footer.is_empty() {
+            text.push('\n');
+            text.push_str(&self.footer);
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+pub(crate) fn help_text(
+    usage: &str,
+    about: &str,
+    opt_cfgs: &[OptCfg],
+    sub_rows: &[(String, &str)],
+    layout: HelpLayout,
+) -> String {
+    let mut text = format!("Usage: {}\n", usage);
+
+    if !about.is_empty() {
+        text.push('\n');
+        text.push_str(about);
+        text.push('\n');
+    }
+
+    let opt_rows = opt_rows(opt_cfgs);
+    write_rows(&mut text, "Options:", &opt_rows, layout);
+    write_rows(&mut text, "Commands:", sub_rows, layout);
+
+    text
+}
+
+fn opt_rows(opt_cfgs: &[OptCfg]) -> Vec<(String, &str)> {
+    opt_cfgs
+        .iter()
+        .filter_map(|cfg| {
+            let opt_names: Vec<&str> = if cfg.names.is_empty() {
+                store_key_of(cfg).into_iter().collect()
+            } else {
+                cfg.names
+                    .iter()
+                    .filter(|n| !cfg.hidden_names.contains(n))
+                    .map(|s| s.as_str())
+                    .collect()
+            };
+            let mut head = opt_names
+                .iter()
+                .filter(|n| !n.is_empty() && **n != "*")
+                .map(|n| {
+                    if n.chars().count() == 1 {
+                        format!("-{}", n)
+                    } else {
+                        format!("--{}", n)
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            if head.is_empty() {
+                return None;
+            }
+            if !cfg.arg_in_help.is_empty() {
+                head.push(' ');
+                head.push_str(&cfg.arg_in_help);
+            } else if cfg.has_arg && cfg.default_missing_value.is_some() {
+                head.push_str("[=");
+                head.push_str(&arg_placeholder(cfg));
+                head.push(']');
+            } else if cfg.has_arg {
+                head.push(' ');
+                head.push_str(&arg_placeholder(cfg));
+            }
+            Some((head, cfg.desc.as_str()))
+        })
+        .collect()
+}
+
+fn arg_placeholder(cfg: &OptCfg) -> String {
+    let mut placeholder = if cfg.is_numeric {
+        "<NUM>".to_string()
+    } else if cfg.complete == ValueHint::FilePath {
+        "<FILE>".to_string()
+    } else if cfg.complete == ValueHint::DirPath {
+        "<DIR>".to_string()
+    } else if cfg.is_path || (cfg.complete == ValueHint::Unknown && is_path_like_key(cfg)) {
+        "<PATH>".to_string()
+    } else {
+        "<VALUE>".to_string()
+    };
+    if cfg.is_array {
+        placeholder.push_str("...");
+    }
+    placeholder
+}
+
+fn is_path_like_key(cfg: &OptCfg) -> bool {
+    let key = store_key_of(cfg).unwrap_or("").to_ascii_lowercase();
+    ["path", "file", "dir"].iter().any(|s| key.contains(s))
+}
+
+pub(crate) fn usage_only_text(usage: &str, cmd_name: &str) -> String {
+    format!(
+        "Usage: {}\nTry '{} --help' for more information.\n",
+        usage, cmd_name
+    )
+}
+
+pub(crate) fn error_message(cmd_name: &str, usage: &str, err: &dyn fmt::Display) -> String {
+    sub_cmd_error_message(&[cmd_name.to_string()], usage, err)
+}
+
+fn sub_cmd_error_message(path: &[String], usage: &str, err: &dyn fmt::Display) -> String {
+    let msg = path[1..]
+        .iter()
+        .fold(ErrorMessage::new(err).app_name(&path[0]), |msg, name| {
+            msg.sub_cmd(name)
+        });
+    format!("{}\n\n{}", msg, usage_only_text(usage, &path.join(" ")))
+}
+
+/// Executes the external sub command at the specified path with the
+/// specified arguments, and waits for it to finish.
+pub fn run_external_sub_cmd(path: &Path, args: &[String]) -> io::Result<process::ExitStatus> {
+    process::Command::new(path).args(args).status()
+}
+
+fn find_in_path(file_name: &str, path_var: &OsStr) -> Option<PathBuf> {
+    env::split_paths(path_var)
+        .map(|dir| dir.join(file_name))
+        .find(|file| file.is_file())
+}
+
+fn notify_usage(observer: &dyn UsageObserver, cmds: &[Cmd], external: Option<&str>) {
+    for cmd in cmds {
+        let mut keys: Vec<(&str, usize)> = cmd
+            .opt_indices
+            .iter()
+            .filter_map(|(key, indices)| indices.first().map(|i| (*key, *i)))
+            .collect();
+        keys.sort_by_key(|(_, i)| *i);
+        let keys: Vec<&str> = keys.into_iter().map(|(key, _)| key).collect();
+        observer.observe(&cmd.path(), &keys);
+    }
+    if let (Some(name), Some(cmd)) = (external, cmds.last()) {
+        let mut path = cmd.path();
+        path.push(name);
+        observer.observe(&path, &[]);
+    }
+}
+
+fn store_key_of(cfg: &OptCfg) -> Option<&str> {
+    if !cfg.store_key.is_empty() {
+        Some(&cfg.store_key)
+    } else {
+        cfg.names.first().map(|s| s.as_str())
+    }
+}
+
+fn write_rows(text: &mut String, title: &str, rows: &[(String, &str)], layout: HelpLayout) {
+    if rows.is_empty() {
+        return;
+    }
+    let fits = |head: &str| layout.name_width == 0 || head.chars().count() <= layout.name_width;
+    let width = rows
+        .iter()
+        .filter(|(head, _)| fits(head))
+        .map(|(head, _)| head.chars().count())
+        .max();
+    let width = width.unwrap_or(layout.name_width);
+
+    text.push('\n');
+    text.push_str(title);
+    text.push('\n');
+    for (head, body) in rows {
+        let lines = wrap_text(body, layout.desc_width);
+        if lines.is_empty() {
+            text.push_str(&format!("  {}\n", head));
+            continue;
+        }
+        let mut lines = lines.iter();
+        if fits(head) {
+            let first = lines.next().unwrap();
+            text.push_str(&format!("  {:<width$}  {}\n", head, first, width = width));
+        } else {
+            text.push_str(&format!("  {}\n", head));
+        }
+        for line in lines {
+            text.push_str(&format!("  {:<width$}  {}\n", "", line, width = width));
+        }
+    }
+}
+
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for para in text.lines() {
+        if width == 0 {
+            lines.push(para.to_string());
+            continue;
+        }
+        let mut line = String::new();
+        for word in para.split_whitespace() {
+            let len = line.chars().count();
+            if len > 0 && len + 1 + word.chars().count() > width {
+                lines.push(line);
+                line = String::new();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests_of_app {
+    use super::*;
+    use crate::OptCfgParam::{
+        arg_in_help, complete, default_missing_value, has_arg, hidden_names, is_array, is_numeric,
+        is_path,
+    };
+
+    fn os_args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    fn sample_app() -> App {
+        App::new("tool")
+            .version("1.2.3")
+            .about("This is a sample tool.")
+            .opt(OptCfg::with(&[
+                names(&["verbose", "v"]),
+                desc("Prints verbose logs."),
+            ]))
+            .sub(
+                App::new("sync")
+                    .about("Synchronizes files.")
+                    .opt(OptCfg::with(&[
+                        names(&["depth", "d"]),
+                        has_arg(true),
+                        arg_in_help("<num>"),
+                        desc("Limits the depth."),
+                    ])),
+            )
+            .sub(App::new("list"))
+    }
+
+    #[test]
+    fn should_parse_top_command_without_sub_command() {
+        let app = App::new("tool").opt(OptCfg::with(&[names(&["foo", "f"])]));
+        match app.run(os_args(&["/path/to/tool", "-f", "a", "b"])) {
+            Ok(AppOutcome::Parsed(cmds)) => {
+                assert_eq!(cmds.len(), 1);
+                assert_eq!(cmds[0].name(), "tool");
+                assert!(cmds[0].has_opt("foo"));
+                assert_eq!(cmds[0].args(), &["a", "b"]);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_dispatch_to_sub_command() {
+        let app = sample_app();
+        match app.run(os_args(&["tool", "-v", "sync", "-d", "2", "dir"])) {
+            Ok(AppOutcome::Parsed(cmds)) => {
+                assert_eq!(cmds.len(), 2);
+                assert_eq!(cmds[0].name(), "tool");
+                assert_eq!(cmds[0].path(), ["tool"]);
+                assert!(cmds[0].has_opt("verbose"));
+                assert_eq!(cmds[0].args(), &[] as &[&str]);
+                assert_eq!(cmds[1].name(), "sync");
+                assert_eq!(cmds[1].path(), ["tool", "sync"]);
+                assert_eq!(cmds[0].arg_offset(), 0);
+                assert_eq!(cmds[1].arg_offset(), 2);
+                assert_eq!(cmds[1].opt_indices("depth"), &[1]);
+                assert_eq!(cmds[1].opt_arg("depth"), Some("2"));
+                assert_eq!(cmds[1].args(), &["dir"]);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_fail_if_sub_command_is_unknown() {
+        let app = sample_app();
+        match app.run(os_args(&["tool", "-v", "snyc"])) {
+            Err(InvalidArgs::SubCmdIsUnknown { name, suggestions }) => {
+                assert_eq!(name, "snyc");
+                assert_eq!(suggestions, vec!["sync"]);
+            }
+            _ => panic!(),
+        }
+        match app.run(os_args(&["tool", "foo", "sync"])) {
+            Err(InvalidArgs::SubCmdIsUnknown { name, suggestions }) => {
+                assert_eq!(name, "foo");
+                assert_eq!(suggestions, Vec::<String>::new());
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_fail_if_option_is_invalid_in_sub_command() {
+        let app = sample_app();
+        match app.run(os_args(&["tool", "list", "-d", "2"])) {
+            Err(InvalidArgs::OptionIsInvalid(InvalidOption::UnconfiguredOption { option })) => {
+                assert_eq!(option, "d");
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_print_help_and_version() {
+        let app = sample_app();
+        match app.run(os_args(&["tool", "--help"])) {
+            Ok(AppOutcome::HelpPrinted) => {}
+            _ => panic!(),
+        }
+        match app.run(os_args(&["tool", "sync", "-h"])) {
+            Ok(AppOutcome::HelpPrinted) => {}
+            _ => panic!(),
+        }
+        match app.run(os_args(&["tool", "-V"])) {
+            Ok(AppOutcome::VersionPrinted) => {}
+            _ => panic!(),
+        }
+        match app.run(os_args(&["tool", "sync", "-V"])) {
+            Err(InvalidArgs::OptionIsInvalid(InvalidOption::UnconfiguredOption { option })) => {
+                assert_eq!(option, "V");
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_not_override_user_defined_names() {
+        let app = App::new("tool").opt(OptCfg::with(&[names(&["host", "h"]), has_arg(true)]));
+        match app.run(os_args(&["tool", "-h", "localhost"])) {
+            Ok(AppOutcome::Parsed(cmds)) => {
+                assert_eq!(cmds[0].opt_arg("host"), Some("localhost"));
+            }
+            _ => panic!(),
+        }
+        match app.run(os_args(&["tool", "--help"])) {
+            Ok(AppOutcome::HelpPrinted) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_make_help_text() {
+        let app = sample_app();
+        assert_eq!(
+            app.help_text(),
+            "Usage: tool [OPTIONS] [COMMAND]
+
+This is a sample tool.
+
+Options:
+  --verbose, -v  Prints verbose logs.
+  --help, -h     Prints help.
+  --version, -V  Prints version.
+
+Commands:
+  sync  Synchronizes files.
+  list
+  help  Prints help of a command.
+"
+        );
+        assert_eq!(
+            app.subs[0].help_text_with_path("tool sync"),
+            "Usage: tool sync [OPTIONS]
+
+Synchronizes files.
+
+Options:
+  --depth, -d <num>  Limits the depth.
+  --help, -h         Prints help.
+"
+        );
+    }
+
+    #[test]
+    fn should_make_error_message() {
+        let err = InvalidOption::UnconfiguredOption {
+            option: "foo".to_string(),
+        };
+        assert_eq!(
+            error_message("tool", "tool [OPTIONS]", &err),
+            "tool: error: The option is not specified in configurations (option: \"foo\")
+
+Usage: tool [OPTIONS]
+Try 'tool --help' for more information.
+"
+        );
+    }
+
+    #[test]
+    fn should_make_error_message_of_nested_sub_command() {
+        let app = sample_app().sub(
+            App::new("remote")
+                .sub(App::new("add").opt(OptCfg::with(&[names(&["force", "f"])])))
+                .sub(App::new("remove")),
+        );
+
+        match app.run_with_path(os_args(&["tool", "remote", "add", "--forc"])) {
+            Ok(_) => panic!(),
+            Err(err) => {
+                assert_eq!(err.path, ["tool", "remote", "add"]);
+                assert_eq!(
+                    app.run_error_message(&err),
+                    "tool remote add: error: The option is not specified in configurations (option: \"forc\"), did you mean \"--force\"?
+
+Usage: tool remote add [OPTIONS]
+Try 'tool remote add --help' for more information.
+"
+                );
+            }
+        }
+
+        match app.run_with_path(os_args(&["tool", "remote", "ad"])) {
+            Ok(_) => panic!(),
+            Err(err) => {
+                assert_eq!(err.path, ["tool", "remote"]);
+                assert_eq!(
+                    app.run_error_message(&err),
+                    "tool remote: error: The sub command is unknown (sub command: \"ad\"), did you mean \"add\"?
+
+Usage: tool remote [OPTIONS] [COMMAND]
+Try 'tool remote --help' for more information.
+"
+                );
+            }
+        }
+
+        match app.run_with_path(os_args(&["tool", "-x"])) {
+            Ok(_) => panic!(),
+            Err(err) => assert_eq!(err.path, ["tool"]),
+        }
+    }
+
+    #[test]
+    fn should_make_usage_only_text() {
+        assert_eq!(
+            sample_app().usage_only(),
+            "Usage: tool [OPTIONS] [COMMAND]
+Try 'tool --help' for more information.
+"
+        );
+    }
+
+    #[test]
+    fn should_call_pre_parse_hooks() {
+        fn expand_alias(args: &mut Vec<String>) {
+            if args.len() > 1 && args[1] == "ls" {
+                args.splice(1..2, ["list".to_string(), "x".to_string()]);
+            }
+        }
+        fn add_verbose(args: &mut Vec<String>) {
+            args.insert(1, "-v".to_string());
+        }
+
+        let app = sample_app().pre_parse(expand_alias).pre_parse(add_verbose);
+        match app.run(os_args(&["tool", "ls"])) {
+            Ok(AppOutcome::Parsed(cmds)) => {
+                assert_eq!(cmds.len(), 2);
+                assert!(cmds[0].has_opt("verbose"));
+                assert_eq!(cmds[1].name(), "list");
+                assert_eq!(cmds[1].args(), &["x"]);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_call_post_parse_hooks() {
+        fn needs_an_arg(cmd: &Cmd) -> Result<(), InvalidOption> {
+            if cmd.has_opt("verbose") && cmd.args().is_empty() {
+                return Err(InvalidOption::OptionArgIsInvalid {
+                    store_key: "verbose".to_string(),
+                    option: "verbose".to_string(),
+                    opt_arg: "".to_string(),
+                    details: "needs a command argument".to_string(),
+                });
+            }
+            Ok(())
+        }
+
+        let app = App::new("tool")
+            .opt(OptCfg::with(&[names(&["verbose", "v"])]))
+            .post_parse(needs_an_arg);
+
+        match app.run(os_args(&["tool", "-v", "a"])) {
+            Ok(AppOutcome::Parsed(cmds)) => assert_eq!(cmds[0].args(), &["a"]),
+            _ => panic!(),
+        }
+        match app.run(os_args(&["tool", "-v"])) {
+            Err(InvalidArgs::OptionIsInvalid(InvalidOption::OptionArgIsInvalid {
+                details,
+                ..
+            })) => {
+                assert_eq!(details, "needs a command argument");
+            }
+            _ => panic!(),
+        }
+        match app.run(os_args(&["tool", "-v", "-h"])) {
+            Ok(AppOutcome::HelpPrinted) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_notify_observer_of_used_options_and_sub_commands() {
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct Log(Mutex<Vec<(Vec<String>, Vec<String>)>>);
+
+        impl UsageObserver for Log {
+            fn observe(&self, path: &[&str], opt_keys: &[&str]) {
+                let to_vec = |a: &[&str]| a.iter().map(|s| s.to_string()).collect();
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((to_vec(path), to_vec(opt_keys)));
+            }
+        }
+
+        let log = Arc::new(Log::default());
+        let app = sample_app()
+            .opt(OptCfg::with(&[names(&["quiet", "q"])]))
+            .observer(log.clone());
+
+        match app.run(os_args(&[
+            "tool",
+            "-q",
+            "--verbose",
+            "-q",
+            "sync",
+            "-d",
+            "3",
+            "x",
+        ])) {
+            Ok(AppOutcome::Parsed(_)) => {}
+            _ => panic!(),
+        }
+        assert_eq!(
+            *log.0.lock().unwrap(),
+            [
+                (
+                    vec!["tool".to_string()],
+                    vec!["quiet".to_string(), "verbose".to_string()]
+                ),
+                (
+                    vec!["tool".to_string(), "sync".to_string()],
+                    vec!["depth".to_string()]
+                ),
+            ]
+        );
+
+        log.0.lock().unwrap().clear();
+        match app.run(os_args(&["tool", "-h"])) {
+            Ok(AppOutcome::HelpPrinted) => {}
+            _ => panic!(),
+        }
+        match app.run(os_args(&["tool", "--unknown"])) {
+            Err(_) => {}
+            _ => panic!(),
+        }
+        assert!(log.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_classify_args_with_sub_commands() {
+        let app = sample_app();
+        assert_eq!(
+            app.classify(&["tool", "-v", "sync", "-d", "3", "x", "list"]),
+            [
+                TokenKind::Command,
+                TokenKind::Option { names: vec!["v"] },
+                TokenKind::SubCmd,
+                TokenKind::Option { names: vec!["d"] },
+                TokenKind::OptArg { of: "d" },
+                TokenKind::Positional,
+                TokenKind::Positional,
+            ]
+        );
+        assert_eq!(
+            app.classify(&["tool", "--help", "unknown", "-v"]),
+            [
+                TokenKind::Command,
+                TokenKind::Option {
+                    names: vec!["help"]
+                },
+                TokenKind::Positional,
+                TokenKind::Option { names: vec!["v"] },
+            ]
+        );
+        assert_eq!(app.classify(&[]), []);
+    }
+
+    #[test]
+    fn should_provide_help_sub_command() {
+        let app = sample_app().sub(App::new("remote").sub(App::new("add")));
+        assert!(app.owns_help_cmd());
+        assert!(!app.subs[0].owns_help_cmd());
+
+        for args in [
+            &["tool", "help"] as &[&str],
+            &["tool", "help", "sync"],
+            &["tool", "-v", "help", "remote", "add"],
+        ] {
+            match app.run(os_args(args)) {
+                Ok(AppOutcome::HelpPrinted) => {}
+                _ => panic!(),
+            }
+        }
+        match app.run(os_args(&["tool", "help", "remote", "ad"])) {
+            Err(InvalidArgs::SubCmdIsUnknown { name, suggestions }) => {
+                assert_eq!(name, "ad");
+                assert_eq!(suggestions, ["add"]);
+            }
+            _ => panic!(),
+        }
+        assert_eq!(
+            app.classify(&["tool", "help", "sync"]),
+            [TokenKind::Command, TokenKind::SubCmd, TokenKind::Positional]
+        );
+
+        let app = App::new("tool").sub(App::new("help").about("Custom help."));
+        assert!(!app.owns_help_cmd());
+        match app.run(os_args(&["tool", "help", "x"])) {
+            Ok(AppOutcome::Parsed(cmds)) => {
+                assert_eq!(cmds[1].name(), "help");
+                assert_eq!(cmds[1].args(), &["x"]);
+            }
+            _ => panic!(),
+        }
+        assert!(!app.help_text().contains("Prints help of a command."));
+    }
+
+    #[test]
+    fn should_complete_sub_commands_and_their_options() {
+        let app = sample_app().sub(
+            App::new("remote")
+                .opt(OptCfg::with(&[names(&["name"]), has_arg(true)]))
+                .sub(App::new("add"))
+                .sub(App::new("remove")),
+        );
+        let request = |line: &str| {
+            let cmd = Cmd::with_strings(["tool", "__complete", line].map(String::from));
+            app.completion_request(&cmd)
+        };
+
+        assert_eq!(
+            request("tool "),
+            Some(
+                ["sync", "list", "remote", "help"]
+                    .map(String::from)
+                    .to_vec()
+            )
+        );
+        assert_eq!(request("tool -v s"), Some(vec!["sync".to_string()]));
+        assert_eq!(
+            request("tool remote --name x re"),
+            Some(vec!["remove".to_string()])
+        );
+        assert_eq!(request("tool remote --n"), Some(vec!["--name".to_string()]));
+        assert_eq!(
+            request("tool remote add --"),
+            Some(vec!["--help".to_string()])
+        );
+        assert_eq!(request("tool unknown s"), Some(vec![]));
+        assert_eq!(request("tool -- s"), Some(vec![]));
+
+        let cmd = Cmd::with_strings(["tool", "sync"].map(String::from));
+        assert_eq!(app.completion_request(&cmd), None);
+
+        match app.run(os_args(&["tool", "__complete", "tool s", "6"])) {
+            Ok(AppOutcome::CompletionPrinted) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_make_extended_help_text() {
+        let app = sample_app().desc_width(20).sub(App::new("remote").sub(
+            App::new("add").about("Adds a remote.").opt(OptCfg::with(&[
+                names(&["fetch", "f"]),
+                desc("Fetches the remote after adding it."),
+            ])),
+        ));
+        assert_eq!(
+            app.extended_help_text(),
+            format!(
+                "{}{}",
+                app.help_text(),
+                "
+tool sync - Synchronizes files.
+  --depth, -d <num>  Limits the depth.
+
+tool list
+
+tool remote
+
+tool remote add - Adds a remote.
+  --fetch, -f  Fetches the remote
+               after adding it.
+"
+            )
+        );
+    }
+
+    #[test]
+    fn should_find_external_sub_cmd_in_path() {
+        let dir = env::temp_dir().join(format!("cliargs-app-test-{}", process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("tool-foo");
+        std::fs::write(&file, "").unwrap();
+
+        let path_var = env::join_paths([Path::new("/nonexistent"), &dir]).unwrap();
+        assert_eq!(find_in_path("tool-foo", &path_var), Some(file));
+        assert_eq!(find_in_path("tool-bar", &path_var), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_treat_unresolved_external_sub_cmd_as_command_arg() {
+        let app = App::new("cliargs-no-such-tool")
+            .opt(OptCfg::with(&[names(&["verbose", "v"])]))
+            .external_subs(true);
+        match app.run(os_args(&["tool", "foo", "-v"])) {
+            Ok(AppOutcome::Parsed(cmds)) => {
+                assert_eq!(cmds.len(), 1);
+                assert!(cmds[0].has_opt("verbose"));
+                assert_eq!(cmds[0].args(), &["foo"]);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_parse_options_once_for_unresolved_external_sub_cmd() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn count(_: &str, _: &str, _: &str) {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let app = App::new("cliargs-no-such-tool")
+            .opt(OptCfg::with(&[
+                names(&["verbose", "v"]),
+                crate::OptCfgParam::on_parse(count),
+            ]))
+            .external_subs(true);
+        match app.run(os_args(&["tool", "-v", "foo", "-v"])) {
+            Ok(AppOutcome::Parsed(cmds)) => {
+                assert_eq!(cmds.len(), 1);
+                assert_eq!(cmds[0].args(), &["foo"]);
+            }
+            _ => panic!(),
+        }
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn should_wrap_long_descriptions_with_hanging_indent() {
+        let app = App::new("tool")
+            .desc_width(20)
+            .opt(OptCfg::with(&[
+                names(&["foo", "f"]),
+                desc("This is a long description of the foo option."),
+            ]))
+            .opt(OptCfg::with(&[
+                names(&["bar"]),
+                desc("First paragraph.\nSecond one."),
+            ]));
+        assert_eq!(
+            app.help_text(),
+            "Usage: tool [OPTIONS]
+
+Options:
+  --foo, -f   This is a long
+              description of the
+              foo option.
+  --bar       First paragraph.
+              Second one.
+  --help, -h  Prints help.
+"
+        );
+    }
+
+    #[test]
+    fn should_wrap_text() {
+        assert_eq!(wrap_text("", 10), Vec::<String>::new());
+        assert_eq!(wrap_text("abc def", 0), vec!["abc def"]);
+        assert_eq!(wrap_text("abc def ghi", 7), vec!["abc def", "ghi"]);
+        assert_eq!(wrap_text("abcdefghij kl", 5), vec!["abcdefghij", "kl"]);
+    }
+
+    #[test]
+    fn should_put_description_on_next_line_if_names_are_too_long() {
+        let app = App::new("tool")
+            .name_width(12)
+            .opt(OptCfg::with(&[names(&["foo", "f"]), desc("Foo option.")]))
+            .opt(OptCfg::with(&[
+                names(&["bar-baz-qux", "b", "bbb"]),
+                has_arg(true),
+                arg_in_help("<text>"),
+                desc("Bar option.\nIt has many names."),
+            ]));
+        assert_eq!(
+            app.help_text(),
+            "Usage: tool [OPTIONS]
+
+Options:
+  --foo, -f   Foo option.
+  --bar-baz-qux, -b, --bbb <text>
+              Bar option.
+              It has many names.
+  --help, -h  Prints help.
+"
+        );
+    }
+
+    #[test]
+    fn should_show_examples_and_sections() {
+        let app = App::new("tool")
+            .desc_width(24)
+            .example("tool sync dir", "Synchronizes the files in the directory.")
+            .example("tool list", "")
+            .section("Notes", "The config file is read from ~/.toolrc.\n\nBye.");
+        assert_eq!(
+            app.help_text(),
+            "Usage: tool [OPTIONS]
+
+Options:
+  --help, -h  Prints help.
+
+Examples:
+  tool sync dir
+      Synchronizes the files
+      in the directory.
+  tool list
+
+Notes:
+  The config file is read
+  from ~/.toolrc.
+
+  Bye.
+"
+        );
+    }
+
+    #[test]
+    fn should_show_header_and_footer() {
+        let app = App::new("tool")
+            .header("tool 1.0 - a sample tool")
+            .footer("See 'tool help <command>' for more information.")
+            .sub(App::new("sync"));
+        assert_eq!(
+            app.help_text(),
+            "tool 1.0 - a sample tool
+
+Usage: tool [OPTIONS] [COMMAND]
+
+Options:
+  --help, -h  Prints help.
+
+Commands:
+  sync
+  help  Prints help of a command.
+
+See 'tool help <command>' for more information.
+"
+        );
+    }
+
+    #[test]
+    fn should_show_placeholders_of_options_without_arg_in_help() {
+        let app = App::new("tool")
+            .opt(OptCfg::with(&[
+                names(&["jobs", "j"]),
+                has_arg(true),
+                is_numeric(true),
+            ]))
+            .opt(OptCfg::with(&[names(&["config-file"]), has_arg(true)]))
+            .opt(OptCfg::with(&[
+                names(&["define", "D"]),
+                has_arg(true),
+                is_array(true),
+            ]))
+            .opt(OptCfg::with(&[
+                names(&["level"]),
+                has_arg(true),
+                arg_in_help("<lv>"),
+            ]))
+            .opt(OptCfg::with(&[
+                names(&["input"]),
+                has_arg(true),
+                is_path(true),
+            ]))
+            .opt(OptCfg::with(&[
+                names(&["log"]),
+                has_arg(true),
+                complete(ValueHint::FilePath),
+            ]))
+            .opt(OptCfg::with(&[
+                names(&["out-dir"]),
+                has_arg(true),
+                complete(ValueHint::DirPath),
+            ]))
+            .opt(OptCfg::with(&[
+                names(&["file-type"]),
+                has_arg(true),
+                complete(ValueHint::Choices(vec!["a".to_string()])),
+            ]));
+        assert_eq!(
+            app.help_text(),
+            "Usage: tool [OPTIONS]
+
+Options:
+  --jobs, -j <NUM>
+  --config-file <PATH>
+  --define, -D <VALUE>...
+  --level <lv>
+  --input <PATH>
+  --log <FILE>
+  --out-dir <DIR>
+  --file-type <VALUE>
+  --help, -h               Prints help.
+"
+        );
+    }
+
+    #[test]
+    fn should_show_optional_option_argument_in_help() {
+        let app = App::new("tool").opt(OptCfg::with(&[
+            names(&["color"]),
+            has_arg(true),
+            default_missing_value("auto"),
+            desc("Colors the output."),
+        ]));
+        assert_eq!(
+            app.help_text(),
+            "Usage: tool [OPTIONS]
+
+Options:
+  --color[=<VALUE>]  Colors the output.
+  --help, -h         Prints help.
+"
+        );
+    }
+
+    #[test]
+    fn should_not_show_hidden_names_in_help() {
+        let app = App::new("tool").opt(OptCfg::with(&[
+            names(&["color", "colour", "c"]),
+            hidden_names(&["colour"]),
+            desc("Colors the output."),
+        ]));
+        assert_eq!(
+            app.help_text(),
+            "Usage: tool [OPTIONS]
+
+Options:
+  --color, -c  Colors the output.
+  --help, -h   Prints help.
+"
+        );
+
+        let mut cmd = Cmd::with_strings(["tool", "--colour"].map(String::from));
+        cmd.parse_with(&app.opt_cfgs).unwrap();
+        assert!(cmd.has_opt("color"));
+    }
+}
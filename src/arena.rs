@@ -0,0 +1,94 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+/// The default capacity of a chunk in bytes.
+const CHUNK_SIZE: usize = 1024;
+
+/// Is a bump arena which stores the strings created while parsing, like store
+/// keys, default values, and values from environment variables, into a few
+/// chunks instead of allocating each of them.
+///
+/// A chunk is a [String] which never grows beyond its capacity, so the string
+/// slices in it are not moved until this arena is dropped.
+#[derive(Default)]
+pub(crate) struct Arena {
+    chunks: Vec<String>,
+}
+
+impl Arena {
+    /// Copies the specified string slice into this arena, and returns the
+    /// copied string slice.
+    ///
+    /// The returned string slice is valid while this arena is alive.
+    /// The caller must ensure that it is not used after this arena is
+    /// dropped, as with the string slices in `Cmd`.
+    pub(crate) fn alloc<'a>(&mut self, s: &str) -> &'a str {
+        if s.is_empty() {
+            return "";
+        }
+
+        let fits = self
+            .chunks
+            .last()
+            .is_some_and(|c| c.capacity() - c.len() >= s.len());
+        if !fits {
+            self.chunks
+                .push(String::with_capacity(CHUNK_SIZE.max(s.len())));
+        }
+
+        let chunk = self.chunks.last_mut().unwrap();
+        let start = chunk.len();
+        chunk.push_str(s);
+        let str: *const str = &chunk[start..];
+        unsafe { &*str }
+    }
+
+    #[cfg(test)]
+    fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+#[cfg(test)]
+mod tests_of_arena {
+    use super::*;
+
+    #[test]
+    fn should_allocate_strings_in_chunks() {
+        let mut arena = Arena::default();
+        assert_eq!(arena.chunk_count(), 0);
+
+        let foo = arena.alloc("foo");
+        let empty = arena.alloc("");
+        let bar = arena.alloc("bar");
+        assert_eq!(foo, "foo");
+        assert_eq!(empty, "");
+        assert_eq!(bar, "bar");
+        assert_eq!(arena.chunk_count(), 1);
+
+        let mut strs = Vec::new();
+        for i in 0..1000 {
+            strs.push(arena.alloc(&format!("value{i}")));
+        }
+        assert!(arena.chunk_count() < 20);
+        for (i, s) in strs.iter().enumerate() {
+            assert_eq!(*s, format!("value{i}"));
+        }
+        assert_eq!(foo, "foo");
+    }
+
+    #[test]
+    fn should_allocate_long_string_in_its_own_chunk() {
+        let mut arena = Arena::default();
+        arena.alloc("foo");
+
+        let long = "x".repeat(CHUNK_SIZE * 2);
+        let s = arena.alloc(&long);
+        assert_eq!(s, long);
+        assert_eq!(arena.chunk_count(), 2);
+
+        assert_eq!(arena.alloc("bar"), "bar");
+        assert_eq!(arena.chunk_count(), 3);
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::errors::InvalidOsArg;
+use crate::Cmd;
+use std::env;
+use std::ffi::{OsStr, OsString};
+
+/// The trait for sources of command line arguments.
+///
+/// A source provides command line arguments including the command path as
+/// its first element.
+/// This crate provides the implementations for the process arguments
+/// ([EnvArgs]) and for explicit slices, arrays and vectors of strings.
+///
+/// On WASI, [EnvArgs] reads the arguments passed by the host through
+/// `args_get`, since [std::env::args_os] is backed by it.
+/// In environments where there are no process arguments, like browsers or
+/// plugins, pass explicit arguments or implement this trait.
+///
+/// ```
+/// use cliargs::Cmd;
+///
+/// let mut cmd = Cmd::with_source(&["/path/to/app", "--foo", "bar"]).unwrap();
+/// cmd.parse().unwrap();
+/// assert_eq!(cmd.name(), "app");
+/// assert_eq!(cmd.has_opt("foo"), true);
+/// assert_eq!(cmd.args(), &["bar"]);
+/// ```
+pub trait ArgSource {
+    /// Returns the command line arguments including the command path.
+    fn os_args(&self) -> Vec<OsString>;
+}
+
+/// The argument source which reads the arguments of the current process with
+/// [std::env::args_os].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvArgs;
+
+impl ArgSource for EnvArgs {
+    fn os_args(&self) -> Vec<OsString> {
+        env::args_os().collect()
+    }
+}
+
+impl<S: AsRef<OsStr>> ArgSource for [S] {
+    fn os_args(&self) -> Vec<OsString> {
+        self.iter().map(|s| s.as_ref().to_os_string()).collect()
+    }
+}
+
+impl<S: AsRef<OsStr>, const N: usize> ArgSource for [S; N] {
+    fn os_args(&self) -> Vec<OsString> {
+        self[..].os_args()
+    }
+}
+
+impl<S: AsRef<OsStr>> ArgSource for Vec<S> {
+    fn os_args(&self) -> Vec<OsString> {
+        self[..].os_args()
+    }
+}
+
+impl<'a> Cmd<'a> {
+    /// Creates a `Cmd` instance with the command line arguments provided by
+    /// the specified source.
+    ///
+    /// Since the arguments can contain invalid unicode data, the return value
+    /// of this function is [Result] of `Cmd` or `errors::InvalidOsArg`.
+    pub fn with_source<S: ArgSource + ?Sized>(source: &S) -> Result<Cmd<'a>, InvalidOsArg> {
+        Self::with_os_strings(source.os_args())
+    }
+}
+
+#[cfg(test)]
+mod tests_of_arg_source {
+    use super::*;
+
+    struct FixedArgs;
+
+    impl ArgSource for FixedArgs {
+        fn os_args(&self) -> Vec<OsString> {
+            vec![OsString::from("plugin"), OsString::from("-v")]
+        }
+    }
+
+    #[test]
+    fn should_create_cmd_with_env_args() {
+        let cmd = Cmd::with_source(&EnvArgs).unwrap();
+        assert_eq!(cmd._arg_refs.len(), env::args_os().count());
+    }
+
+    #[test]
+    fn should_create_cmd_with_slices() {
+        let args = vec!["/path/to/app".to_string(), "--foo".to_string()];
+        let mut cmd = Cmd::with_source(&args).unwrap();
+        cmd.parse().unwrap();
+        assert_eq!(cmd.name(), "app");
+        assert!(cmd.has_opt("foo"));
+
+        let args: &[&str] = &["app", "bar"];
+        let mut cmd = Cmd::with_source(args).unwrap();
+        cmd.parse().unwrap();
+        assert_eq!(cmd.args(), &["bar"]);
+
+        let mut cmd = Cmd::with_source(&[OsString::from("app"), OsString::from("-b")]).unwrap();
+        cmd.parse().unwrap();
+        assert!(cmd.has_opt("b"));
+    }
+
+    #[test]
+    fn should_create_cmd_with_custom_source() {
+        let mut cmd = Cmd::with_source(&FixedArgs).unwrap();
+        cmd.parse().unwrap();
+        assert_eq!(cmd.name(), "plugin");
+        assert!(cmd.has_opt("v"));
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+
+/// Appends the arguments read from the files specified with the option of the
+/// specified name, like `--args-from list.txt`, to the command line
+/// arguments.
+///
+/// The file has one argument per line, and empty lines are ignored.
+/// If the option argument is `-`, the arguments are read from stdin, so that
+/// a very large list of arguments can be piped, like `find . | app --args-from -`.
+/// The option can be given with `=`, like `--args-from=-`, and can be given
+/// more than once.
+/// The options after `--` are not regarded as this option.
+///
+/// The read arguments are appended to the end of the command line arguments
+/// in the order of the options, so they are treated as command arguments if
+/// `--` is given in the command line arguments.
+/// Since the option itself is left in the command line arguments, it needs to
+/// be configured as an option which takes an argument when parsing with
+/// option configurations.
+///
+/// ```
+/// use cliargs::{read_args_from, Cmd, OptCfg};
+/// use cliargs::OptCfgParam::{names, has_arg};
+///
+/// let args = ["app", "--args-from=/dev/null", "a"].map(|s| s.into());
+/// let mut cmd = Cmd::with_os_strings(read_args_from(args, "args-from").unwrap()).unwrap();
+/// cmd.parse_with(&[OptCfg::with(&[names(&["args-from"]), has_arg(true)])]).unwrap();
+/// assert_eq!(cmd.args(), &["a"]);
+/// ```
+pub fn read_args_from(
+    args: impl IntoIterator<Item = OsString>,
+    name: &str,
+) -> io::Result<Vec<OsString>> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    let long_name = format!("--{}", name);
+    let long_name_eq = format!("--{}=", name);
+
+    let mut sources: Vec<String> = Vec::new();
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        let Some(arg) = arg.to_str() else {
+            continue;
+        };
+        if arg == "--" {
+            break;
+        }
+        if arg == long_name {
+            if let Some(source) = iter.next().and_then(|s| s.to_str()) {
+                sources.push(source.to_string());
+            }
+        } else if let Some(source) = arg.strip_prefix(&long_name_eq) {
+            sources.push(source.to_string());
+        }
+    }
+
+    for source in sources {
+        if source == "-" {
+            read_lines(io::stdin().lock(), &mut args)?;
+        } else {
+            read_lines(BufReader::new(fs::File::open(source)?), &mut args)?;
+        }
+    }
+    Ok(args)
+}
+
+fn read_lines(reader: impl BufRead, args: &mut Vec<OsString>) -> io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if !line.is_empty() {
+            args.push(OsString::from(line));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_of_read_args_from {
+    use super::*;
+    use std::env;
+
+    fn os_args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn should_append_args_read_from_files() {
+        let path = env::temp_dir().join("cliargs_test_3982.txt");
+        fs::write(&path, "-v\r\nfoo bar\n\n--\n-x\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let eq_opt = format!("--list={}", path);
+        let args = os_args(&["app", "--list", path, "a", &eq_opt]);
+        let result = read_args_from(args, "list").unwrap();
+        assert_eq!(
+            result,
+            os_args(&[
+                "app", "--list", path, "a", &eq_opt, "-v", "foo bar", "--", "-x", "-v", "foo bar",
+                "--", "-x",
+            ])
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn should_ignore_options_after_end_opt() {
+        let args = os_args(&["app", "a", "--", "--list=/no/such/file", "--list"]);
+        let result = read_args_from(args.clone(), "list").unwrap();
+        assert_eq!(result, args);
+    }
+
+    #[test]
+    fn should_fail_if_file_does_not_exist() {
+        let args = os_args(&["app", "--list", "/no/such/file"]);
+        match read_args_from(args, "list") {
+            Ok(_) => panic!(),
+            Err(err) => assert_eq!(err.kind(), io::ErrorKind::NotFound),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::quote;
+use crate::Cmd;
+
+impl<'a> Cmd<'a> {
+    /// Regenerates command line arguments equivalent to the parsed options
+    /// and command arguments.
+    ///
+    /// The first element is the command name, and options follow in the
+    /// order of their first occurrences in the original command line
+    /// arguments, then command arguments follow.
+    /// An option is written as `--name=arg` for each of its arguments, or as
+    /// `--name` (`-n` if the name is one character) if it has no argument.
+    /// `--` is inserted before command arguments if any of them starts with
+    /// `-` or `+`.
+    /// Options to which default values were applied are not included because
+    /// parsing with the same configurations applies them again.
+    ///
+    /// Options are written with their store keys, or with the first names of
+    /// the option configurations stored in this `Cmd` instance if exist.
+    ///
+    /// ```
+    /// use cliargs::Cmd;
+    ///
+    /// let mut cmd = Cmd::with_strings([
+    ///     "/path/to/app", "--foo=1", "file", "-a",
+    /// ].map(String::from));
+    /// cmd.parse().unwrap();
+    /// assert_eq!(cmd.to_argv(), ["app", "--foo=1", "-a", "file"]);
+    /// ```
+    pub fn to_argv(&self) -> Vec<String> {
+        let mut keys: Vec<&str> = self
+            .opts
+            .keys()
+            .filter(|k| !self.defaulted.contains(k))
+            .copied()
+            .collect();
+        keys.sort_by_key(|k| (self.opt_indices(k).first().copied(), *k));
+
+        let mut argv = vec![self.name.to_string()];
+
+        for key in keys {
+            let name = self.option_name_of(key);
+            let prefix = if name.chars().count() == 1 { "-" } else { "--" };
+            let vals = &self.opts[key];
+
+            if vals.is_empty() {
+                let count = self.opt_occurrence_count(key).max(1);
+                for _ in 0..count {
+                    argv.push(format!("{}{}", prefix, name));
+                }
+                if self.is_plus_opt(key) {
+                    argv.push(format!("+{}", name));
+                }
+            } else {
+                for val in vals {
+                    if prefix == "-" {
+                        argv.push(format!("-{}", name));
+                        argv.push(val.to_string());
+                    } else {
+                        argv.push(format!("--{}={}", name, val));
+                    }
+                }
+            }
+        }
+
+        if self.args.iter().any(|a| a.starts_with(['-', '+'])) {
+            argv.push("--".to_string());
+        }
+        for arg in self.args.iter() {
+            argv.push(arg.to_string());
+        }
+
+        argv
+    }
+
+    /// Regenerates a command line string equivalent to the parsed options and
+    /// command arguments, of which each element is quoted for POSIX shells
+    /// if needed.
+    ///
+    /// See `to_argv` about how the elements are generated, and `quote::sh`
+    /// about how they are quoted.
+    pub fn to_command_line(&self) -> String {
+        self.to_argv()
+            .iter()
+            .map(|s| quote::sh(s))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn option_name_of<'k>(&'k self, store_key: &'k str) -> &'k str {
+        for cfg in self.cfgs.iter() {
+            let key = if cfg.store_key.is_empty() {
+                cfg.names.first().map(|s| s.as_str())
+            } else {
+                Some(cfg.store_key.as_str())
+            };
+            if key == Some(store_key) {
+                if let Some(name) = cfg.names.first() {
+                    return name;
+                }
+            }
+        }
+        store_key
+    }
+}
+
+#[cfg(test)]
+mod tests_of_to_argv {
+    use crate::Cmd;
+    use crate::OptCfg;
+    use crate::OptCfgParam::{accepts_plus, defaults, has_arg, is_array, names, store_key};
+
+    #[test]
+    fn should_regenerate_argv_without_options() {
+        let mut cmd = Cmd::with_strings(["/path/to/app".to_string()]);
+        cmd.parse().unwrap();
+        assert_eq!(cmd.to_argv(), ["app"]);
+        assert_eq!(cmd.to_command_line(), "app");
+    }
+
+    #[test]
+    fn should_regenerate_argv_in_order_of_occurrences() {
+        let mut cmd = Cmd::with_strings(
+            [
+                "app",
+                "--foo-bar",
+                "-vv",
+                "--baz=1",
+                "arg1",
+                "--baz",
+                "2",
+                "-q",
+                "a b",
+            ]
+            .map(String::from),
+        );
+        cmd.parse().unwrap();
+        assert_eq!(
+            cmd.to_argv(),
+            [
+                "app",
+                "--foo-bar",
+                "-v",
+                "-v",
+                "--baz=1",
+                "-q",
+                "arg1",
+                "2",
+                "a b"
+            ]
+        );
+        assert_eq!(
+            cmd.to_command_line(),
+            "app --foo-bar -v -v --baz=1 -q arg1 2 'a b'"
+        );
+    }
+
+    #[test]
+    fn should_regenerate_argv_with_cfgs() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[store_key("fooBar"), names(&["foo-bar", "f"]), has_arg(true)]),
+            OptCfg::with(&[names(&["x"]), accepts_plus(true)]),
+            OptCfg::with(&[names(&["n"]), has_arg(true), is_array(true)]),
+            OptCfg::with(&[names(&["level"]), has_arg(true), defaults(&["1"])]),
+        ];
+        let mut cmd = Cmd::with_strings_and_cfgs(
+            ["app", "-n", "1", "+x", "-f", "it's", "--", "-arg"].map(String::from),
+            opt_cfgs,
+        );
+        cmd.parse().unwrap();
+        assert_eq!(
+            cmd.to_argv(),
+            ["app", "-n", "1", "-x", "+x", "--foo-bar=it's", "--", "-arg"]
+        );
+        assert_eq!(
+            cmd.to_command_line(),
+            r#"app -n 1 -x +x '--foo-bar=it'\''s' -- -arg"#
+        );
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::completion::ValueHint;
+use crate::{EmptyArgPolicy, OptCfg};
+use clap::{Arg, ArgAction};
+
+/// Converts the specified option configurations to `clap::Arg`s.
+///
+/// The store key of an option configuration is used as the id of the
+/// `clap::Arg`.
+/// The first long name and the first short name of `names` are used as the
+/// long and short names, and other names are used as aliases.
+/// The `arg_in_help` field is used as the value name after removing
+/// surrounding `<` and `>`.
+/// The `default_missing_value` field makes the option argument optional and
+/// requires `=` to specify it.
+///
+/// The option configuration of which the store key is `"*"` is skipped
+/// because `clap` has no equivalent for it.
+pub fn to_clap_args(opt_cfgs: &[OptCfg]) -> Vec<Arg> {
+    let mut args = Vec::with_capacity(opt_cfgs.len());
+
+    for cfg in opt_cfgs.iter() {
+        let store_key = if cfg.store_key.is_empty() {
+            match cfg.names.first() {
+                Some(name) => name.as_str(),
+                None => continue,
+            }
+        } else {
+            cfg.store_key.as_str()
+        };
+
+        if store_key == "*" {
+            continue;
+        }
+
+        let mut arg = Arg::new(store_key.to_string());
+
+        let names: Vec<&str> = if cfg.names.is_empty() {
+            vec![store_key]
+        } else {
+            cfg.names.iter().map(|s| s.as_str()).collect()
+        };
+
+        let mut has_long = false;
+        let mut has_short = false;
+        for name in names {
+            let is_hidden = cfg.hidden_names.iter().any(|n| n == name);
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) => {
+                    if has_short || is_hidden {
+                        arg = arg.short_alias(ch);
+                    } else {
+                        arg = arg.short(ch);
+                        has_short = true;
+                    }
+                }
+                (Some(_), Some(_)) => {
+                    if has_long || is_hidden {
+                        arg = arg.alias(name.to_string());
+                    } else {
+                        arg = arg.long(name.to_string());
+                        has_long = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if cfg.has_arg {
+            arg = arg.num_args(1);
+            if cfg.is_array {
+                arg = arg.action(ArgAction::Append);
+            } else {
+                arg = arg.action(ArgAction::Set);
+            }
+            if let Some(defaults) = &cfg.defaults {
+                arg = arg.default_values(defaults.clone());
+            }
+            if let Some(value) = &cfg.default_missing_value {
+                arg = arg
+                    .num_args(0..=1)
+                    .require_equals(true)
+                    .default_missing_value(value.clone());
+            }
+            let value_name = cfg
+                .arg_in_help
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>');
+            if !value_name.is_empty() {
+                arg = arg.value_name(value_name.to_string());
+            }
+        } else {
+            arg = arg.action(ArgAction::SetTrue);
+        }
+
+        if !cfg.desc.is_empty() {
+            arg = arg.help(cfg.desc.clone());
+        }
+
+        args.push(arg);
+    }
+
+    args
+}
+
+/// Converts the specified `clap::Arg`s to option configurations.
+///
+/// Positional arguments are skipped because option configurations are only
+/// for options.
+/// An argument of which action takes values is converted to an option
+/// configuration which has an argument, and the action `ArgAction::Append`
+/// makes it an array option.
+/// The first value name is used as `arg_in_help` with surrounding `<` and
+/// `>`.
+/// The default missing value is not converted because `clap::Arg` has no
+/// getter for it.
+pub fn from_clap_args<'a>(args: impl IntoIterator<Item = &'a Arg>) -> Vec<OptCfg> {
+    let mut opt_cfgs = Vec::new();
+
+    for arg in args {
+        if arg.is_positional() {
+            continue;
+        }
+
+        let mut names = Vec::new();
+        if let Some(long) = arg.get_long() {
+            names.push(long.to_string());
+        }
+        if let Some(short) = arg.get_short() {
+            names.push(short.to_string());
+        }
+        if let Some(aliases) = arg.get_all_aliases() {
+            for alias in aliases {
+                names.push(alias.to_string());
+            }
+        }
+        if let Some(aliases) = arg.get_all_short_aliases() {
+            for alias in aliases {
+                names.push(alias.to_string());
+            }
+        }
+
+        let has_arg = arg.get_action().takes_values();
+        let is_array = has_arg && matches!(arg.get_action(), ArgAction::Append);
+
+        let defaults = if has_arg && !arg.get_default_values().is_empty() {
+            Some(
+                arg.get_default_values()
+                    .iter()
+                    .map(|v| v.to_string_lossy().to_string())
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        let arg_in_help = match arg.get_value_names() {
+            Some(value_names) if has_arg && !value_names.is_empty() => {
+                format!("<{}>", value_names[0])
+            }
+            _ => String::new(),
+        };
+
+        opt_cfgs.push(OptCfg {
+            store_key: arg.get_id().to_string(),
+            names,
+            hidden_names: Vec::new(),
+            has_arg,
+            is_array,
+            unique: false,
+            is_numeric: false,
+            accepts_si_suffix: false,
+            is_path: false,
+            complete: ValueHint::Unknown,
+            trims_arg: false,
+            empty_arg: EmptyArgPolicy::Allow,
+            raw_arg: false,
+            accepts_plus: false,
+            defaults,
+            defaults_if: Vec::new(),
+            interpolates_defaults: false,
+            default_fn: None,
+            default_missing_value: None,
+            confirm: None,
+            desc: arg.get_help().map(|s| s.to_string()).unwrap_or_default(),
+            arg_in_help,
+            validator: |_, _, _| Ok(()),
+            on_parse: |_, _, _| {},
+        });
+    }
+
+    opt_cfgs
+}
+
+#[cfg(test)]
+mod tests_of_clap_interop {
+    use super::*;
+    use crate::OptCfgParam::*;
+
+    #[test]
+    fn should_convert_opt_cfgs_to_clap_args() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[
+                store_key("fooBar"),
+                names(&["foo-bar", "f", "foo", "F"]),
+                desc("foo-bar option"),
+            ]),
+            OptCfg::with(&[
+                names(&["baz"]),
+                has_arg(true),
+                defaults(&["1"]),
+                arg_in_help("<num>"),
+            ]),
+            OptCfg::with(&[names(&["qux"]), has_arg(true), is_array(true)]),
+            OptCfg::with(&[store_key("*")]),
+        ];
+
+        let args = to_clap_args(&opt_cfgs);
+        assert_eq!(args.len(), 3);
+
+        assert_eq!(args[0].get_id(), "fooBar");
+        assert_eq!(args[0].get_long(), Some("foo-bar"));
+        assert_eq!(args[0].get_short(), Some('f'));
+        assert_eq!(args[0].get_all_aliases(), Some(vec!["foo"]));
+        assert_eq!(args[0].get_all_short_aliases(), Some(vec!['F']));
+        assert_eq!(
+            args[0].get_help().map(|s| s.to_string()),
+            Some("foo-bar option".to_string())
+        );
+        assert!(matches!(args[0].get_action(), ArgAction::SetTrue));
+
+        assert_eq!(args[1].get_id(), "baz");
+        assert_eq!(args[1].get_long(), Some("baz"));
+        assert_eq!(args[1].get_short(), None);
+        assert_eq!(args[1].get_default_values(), &["1"]);
+        assert!(matches!(args[1].get_action(), ArgAction::Set));
+
+        assert_eq!(args[2].get_id(), "qux");
+        assert!(matches!(args[2].get_action(), ArgAction::Append));
+    }
+
+    #[test]
+    fn should_parse_with_clap_command_built_from_opt_cfgs() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo", "f"])]),
+            OptCfg::with(&[names(&["bar", "b"]), has_arg(true), defaults(&["1"])]),
+            OptCfg::with(&[names(&["baz"]), has_arg(true), is_array(true)]),
+        ];
+
+        let matches = clap::Command::new("app")
+            .args(to_clap_args(&opt_cfgs))
+            .try_get_matches_from(["app", "-f", "--baz", "x", "--baz=y"])
+            .unwrap();
+
+        assert!(matches.get_flag("foo"));
+        assert_eq!(matches.get_one::<String>("bar"), Some(&"1".to_string()));
+        assert_eq!(
+            matches
+                .get_many::<String>("baz")
+                .unwrap()
+                .map(|s| s.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["x", "y"]
+        );
+    }
+
+    #[test]
+    fn should_parse_optional_arg_with_clap_command_built_from_opt_cfgs() {
+        let opt_cfgs = vec![OptCfg::with(&[
+            names(&["color"]),
+            has_arg(true),
+            default_missing_value("auto"),
+        ])];
+
+        let matches = clap::Command::new("app")
+            .args(to_clap_args(&opt_cfgs))
+            .try_get_matches_from(["app", "--color"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<String>("color"),
+            Some(&"auto".to_string())
+        );
+
+        let matches = clap::Command::new("app")
+            .args(to_clap_args(&opt_cfgs))
+            .try_get_matches_from(["app", "--color=always"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<String>("color"),
+            Some(&"always".to_string())
+        );
+    }
+
+    #[test]
+    fn should_convert_clap_args_to_opt_cfgs() {
+        let args = vec![
+            Arg::new("fooBar")
+                .long("foo-bar")
+                .short('f')
+                .alias("foo")
+                .action(ArgAction::SetTrue)
+                .help("foo-bar option"),
+            Arg::new("baz")
+                .long("baz")
+                .action(ArgAction::Set)
+                .default_value("1")
+                .value_name("num"),
+            Arg::new("qux").long("qux").action(ArgAction::Append),
+            Arg::new("file"),
+        ];
+
+        let opt_cfgs = from_clap_args(&args);
+        assert_eq!(opt_cfgs.len(), 3);
+
+        assert_eq!(opt_cfgs[0].store_key, "fooBar");
+        assert_eq!(opt_cfgs[0].names, vec!["foo-bar", "f", "foo"]);
+        assert!(!opt_cfgs[0].has_arg);
+        assert!(!opt_cfgs[0].is_array);
+        assert_eq!(opt_cfgs[0].defaults, None);
+        assert_eq!(opt_cfgs[0].desc, "foo-bar option");
+        assert_eq!(opt_cfgs[0].arg_in_help, "");
+
+        assert_eq!(opt_cfgs[1].store_key, "baz");
+        assert_eq!(opt_cfgs[1].names, vec!["baz"]);
+        assert!(opt_cfgs[1].has_arg);
+        assert!(!opt_cfgs[1].is_array);
+        assert_eq!(opt_cfgs[1].defaults, Some(vec!["1".to_string()]));
+        assert_eq!(opt_cfgs[1].arg_in_help, "<num>");
+
+        assert_eq!(opt_cfgs[2].store_key, "qux");
+        assert!(opt_cfgs[2].has_arg);
+        assert!(opt_cfgs[2].is_array);
+    }
+}
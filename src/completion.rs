@@ -0,0 +1,942 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Cmd;
+use crate::Constraints;
+use crate::OptCfg;
+
+/// The hidden sub command name which requests completion candidates.
+pub const COMPLETE_CMD: &str = "__complete";
+
+/// Enumerates the shells for which this crate can generate completion
+/// scripts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    /// Bash.
+    Bash,
+
+    /// Zsh.
+    Zsh,
+
+    /// Fish.
+    Fish,
+
+    /// Elvish.
+    Elvish,
+
+    /// Nushell.
+    Nushell,
+}
+
+/// Enumerates the hints for completing option arguments, which are set to
+/// `OptCfg#complete`.
+///
+/// The hints except `Choices` and `Unknown` are passed to the shell as a
+/// directive line, like `:file`, by `Cmd#complete_if_requested`, and the
+/// scripts generated by `script` complete the option argument with the
+/// shell's own functions.
+/// The scripts for Elvish and Nushell ignore the directives.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValueHint {
+    /// Leaves the completion to the shell's default, which completes file
+    /// paths in most shells.
+    #[default]
+    Unknown,
+
+    /// Completes file paths.
+    FilePath,
+
+    /// Completes directory paths.
+    DirPath,
+
+    /// Completes host names.
+    Hostname,
+
+    /// Completes command names.
+    CommandName,
+
+    /// Completes the specified values.
+    Choices(Vec<String>),
+
+    /// Completes nothing.
+    None,
+}
+
+impl ValueHint {
+    /// Returns the directive line which is passed to the shell for this hint,
+    /// or `None` if this hint has no directive.
+    pub fn directive(&self) -> Option<&'static str> {
+        match self {
+            ValueHint::FilePath => Some(":file"),
+            ValueHint::DirPath => Some(":dir"),
+            ValueHint::Hostname => Some(":host"),
+            ValueHint::CommandName => Some(":command"),
+            ValueHint::None => Some(":none"),
+            ValueHint::Unknown | ValueHint::Choices(_) => None,
+        }
+    }
+}
+
+/// Computes completion candidates for the word at the cursor position in the
+/// specified command line.
+///
+/// `line` is a whole command line including the command name, and `point` is
+/// the cursor position counted in characters from the beginning of `line`.
+///
+/// If the word at the cursor starts with `-`, this function returns the
+/// option names in the option configurations which start with the word.
+/// The long option names are prefixed with `--` and the short option names
+/// are prefixed with `-`.
+/// If the word is an option argument of which `OptCfg#complete` is
+/// `ValueHint::Choices`, this function returns the choices which start with
+/// the word, prefixed with the option like `--foo=` if the word is so.
+/// If the word is another option argument or follows `--`, this function
+/// returns an empty vector, so that the shell can fall back to its default
+/// completion or to the completion of `value_hint`.
+///
+/// ```rust
+/// use cliargs::OptCfg;
+/// use cliargs::OptCfgParam::names;
+/// use cliargs::completion::complete;
+///
+/// let opt_cfgs = vec![
+///     OptCfg::with(&[names(&["foo-bar", "f"])]),
+///     OptCfg::with(&[names(&["foo-baz"])]),
+///     OptCfg::with(&[names(&["qux"])]),
+/// ];
+///
+/// assert_eq!(complete(&opt_cfgs, "app --foo", 9), vec!["--foo-bar", "--foo-baz"]);
+/// ```
+pub fn complete(opt_cfgs: &[OptCfg], line: &str, point: usize) -> Vec<String> {
+    complete_with_constraints(opt_cfgs, &Constraints::new(), line, point)
+}
+
+/// Computes completion candidates like `complete`, but excludes the options
+/// which conflict with the options already given in the command line by the
+/// specified constraints, like `--yaml` after `--json` for
+/// `exactly_one_of(&["json", "yaml"])`.
+///
+/// ```rust
+/// use cliargs::{Constraints, OptCfg};
+/// use cliargs::OptCfgParam::names;
+/// use cliargs::completion::complete_with_constraints;
+///
+/// let opt_cfgs = vec![
+///     OptCfg::with(&[names(&["json"])]),
+///     OptCfg::with(&[names(&["yaml"])]),
+///     OptCfg::with(&[names(&["verbose"])]),
+/// ];
+/// let constraints = Constraints::new().exactly_one_of(&["json", "yaml"]);
+///
+/// assert_eq!(
+///     complete_with_constraints(&opt_cfgs, &constraints, "app --json --", 13),
+///     vec!["--json", "--verbose"]
+/// );
+/// ```
+pub fn complete_with_constraints(
+    opt_cfgs: &[OptCfg],
+    constraints: &Constraints,
+    line: &str,
+    point: usize,
+) -> Vec<String> {
+    let (words, current) = split_line(line, point);
+    complete_words(opt_cfgs, constraints, &[], &words, current)
+}
+
+/// Computes completion candidates like `complete`, but also returns the
+/// specified sub command names which start with the word at the cursor, if
+/// the word does not start with `-` and no command argument precedes it.
+///
+/// ```rust
+/// use cliargs::OptCfg;
+/// use cliargs::OptCfgParam::{names, has_arg};
+/// use cliargs::completion::complete_with_sub_cmds;
+///
+/// let opt_cfgs = vec![OptCfg::with(&[names(&["config"]), has_arg(true)])];
+/// let sub_cmds = ["remote", "rebase", "status"];
+///
+/// assert_eq!(
+///     complete_with_sub_cmds(&opt_cfgs, &sub_cmds, "app --config x re", 17),
+///     vec!["remote", "rebase"]
+/// );
+/// assert_eq!(
+///     complete_with_sub_cmds(&opt_cfgs, &sub_cmds, "app status re", 13),
+///     Vec::<String>::new()
+/// );
+/// ```
+pub fn complete_with_sub_cmds(
+    opt_cfgs: &[OptCfg],
+    sub_cmds: &[&str],
+    line: &str,
+    point: usize,
+) -> Vec<String> {
+    let (words, current) = split_line(line, point);
+    complete_words(opt_cfgs, &Constraints::new(), sub_cmds, &words, current)
+}
+
+pub(crate) fn complete_words(
+    opt_cfgs: &[OptCfg],
+    constraints: &Constraints,
+    sub_cmds: &[&str],
+    words: &[&str],
+    current: &str,
+) -> Vec<String> {
+    if words.contains(&"--") {
+        return Vec::new();
+    }
+
+    if let Some((prefix, cfg, arg)) = find_cfg_of_arg(opt_cfgs, words, current) {
+        return match &cfg.complete {
+            ValueHint::Choices(choices) => choices
+                .iter()
+                .filter(|c| c.starts_with(arg))
+                .map(|c| format!("{}{}", prefix, c))
+                .collect(),
+            _ => Vec::new(),
+        };
+    }
+
+    if !current.starts_with('-') {
+        if first_cmd_arg(opt_cfgs, words).is_some() {
+            return Vec::new();
+        }
+        return sub_cmds
+            .iter()
+            .filter(|name| name.starts_with(current))
+            .map(|name| name.to_string())
+            .collect();
+    }
+
+    if current.contains('=') {
+        return Vec::new();
+    }
+
+    let given_keys = given_keys(opt_cfgs, words);
+    let conflicting_keys = constraints.conflicting_keys(&given_keys);
+
+    let mut candidates = Vec::new();
+    for cfg in opt_cfgs.iter() {
+        if conflicting_keys.contains(&store_key_of(cfg)) {
+            continue;
+        }
+        for name in cfg_names(cfg) {
+            if cfg.hidden_names.iter().any(|n| n == name) {
+                continue;
+            }
+            let candidate = if name.chars().count() == 1 {
+                format!("-{}", name)
+            } else {
+                format!("--{}", name)
+            };
+            if candidate.starts_with(current) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates
+}
+
+/// Returns the hint for completing the option argument at the cursor position
+/// in the specified command line, or `None` if the word at the cursor is not
+/// an option argument.
+///
+/// The arguments are same as `complete`.
+///
+/// ```rust
+/// use cliargs::OptCfg;
+/// use cliargs::OptCfgParam::{names, has_arg, complete};
+/// use cliargs::completion::{value_hint, ValueHint};
+///
+/// let opt_cfgs = vec![
+///     OptCfg::with(&[names(&["input"]), has_arg(true), complete(ValueHint::FilePath)]),
+///     OptCfg::with(&[names(&["count"]), has_arg(true), complete(ValueHint::None)]),
+/// ];
+///
+/// assert_eq!(value_hint(&opt_cfgs, "app --input ", 12), Some(&ValueHint::FilePath));
+/// assert_eq!(value_hint(&opt_cfgs, "app --count=", 12), Some(&ValueHint::None));
+/// assert_eq!(value_hint(&opt_cfgs, "app ", 4), None);
+/// ```
+pub fn value_hint<'b>(opt_cfgs: &'b [OptCfg], line: &str, point: usize) -> Option<&'b ValueHint> {
+    let (words, current) = split_line(line, point);
+    value_hint_of_words(opt_cfgs, &words, current)
+}
+
+fn value_hint_of_words<'b>(
+    opt_cfgs: &'b [OptCfg],
+    words: &[&str],
+    current: &str,
+) -> Option<&'b ValueHint> {
+    if words.contains(&"--") {
+        return None;
+    }
+
+    find_cfg_of_arg(opt_cfgs, words, current).map(|(_, cfg, _)| &cfg.complete)
+}
+
+/// Returns the completion candidates for the word at the cursor, or the
+/// directive of the option argument's value hint if there is no candidate.
+pub(crate) fn candidates_or_directive(
+    opt_cfgs: &[OptCfg],
+    constraints: &Constraints,
+    sub_cmds: &[&str],
+    words: &[&str],
+    current: &str,
+) -> Vec<String> {
+    let candidates = complete_words(opt_cfgs, constraints, sub_cmds, words, current);
+    if candidates.is_empty() {
+        if let Some(directive) =
+            value_hint_of_words(opt_cfgs, words, current).and_then(|h| h.directive())
+        {
+            return vec![directive.to_string()];
+        }
+    }
+    candidates
+}
+
+/// Returns the index of the first word which is neither an option nor an
+/// option argument, or `None` if there is no such word before `--`.
+pub(crate) fn first_cmd_arg(opt_cfgs: &[OptCfg], words: &[&str]) -> Option<usize> {
+    let mut takes_next = false;
+    for (i, word) in words.iter().enumerate() {
+        if takes_next {
+            takes_next = false;
+            continue;
+        }
+        if *word == "--" {
+            return None;
+        }
+        if !word.starts_with('-') || *word == "-" {
+            return Some(i);
+        }
+        takes_next = find_cfg_taking_next_arg(opt_cfgs, word).is_some_and(|cfg| cfg.has_arg);
+    }
+    None
+}
+
+/// Splits the specified command line up to the cursor position into the
+/// preceding words, excluding the command name, and the word at the cursor.
+pub(crate) fn split_line(line: &str, point: usize) -> (Vec<&str>, &str) {
+    let end = match line.char_indices().nth(point) {
+        Some((i, _)) => i,
+        None => line.len(),
+    };
+    let line = &line[0..end];
+
+    let mut words: Vec<&str> = line.split_whitespace().collect();
+    let current = if line.is_empty() || line.ends_with(char::is_whitespace) {
+        ""
+    } else {
+        words.pop().unwrap_or("")
+    };
+
+    // The first word is the command name.
+    if !words.is_empty() {
+        words.remove(0);
+    }
+
+    (words, current)
+}
+
+/// Finds the option configuration of which the argument is the word at the
+/// cursor, and returns the prefix of the word before the argument, the option
+/// configuration, and the argument.
+fn find_cfg_of_arg<'b, 'c>(
+    opt_cfgs: &'b [OptCfg],
+    words: &[&str],
+    current: &'c str,
+) -> Option<(&'c str, &'b OptCfg, &'c str)> {
+    if let Some(prev) = words.last() {
+        if let Some(cfg) = find_cfg_taking_next_arg(opt_cfgs, prev) {
+            if cfg.has_arg {
+                return Some(("", cfg, current));
+            }
+        }
+    }
+
+    if current.starts_with('-') {
+        let i = current.find('=')?;
+        let cfg = find_cfg_taking_next_arg(opt_cfgs, &current[..i])?;
+        if cfg.has_arg {
+            return Some((&current[..=i], cfg, &current[i + 1..]));
+        }
+    }
+
+    None
+}
+
+/// Returns a completion script for the specified shell.
+///
+/// The generated script calls the command with the hidden sub command
+/// `__complete`, the current command line, and the cursor position, and uses
+/// the output lines as completion candidates.
+/// The scripts for Elvish and Nushell pass the command line only up to the
+/// cursor, and omit the cursor position.
+/// The command needs to handle this request with `Cmd#complete_if_requested`
+/// before parsing command line arguments, or with `App#run`, which handles it
+/// by itself.
+pub fn script(shell: Shell, cmd_name: &str) -> String {
+    let fn_name = cmd_name.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+    match shell {
+        Shell::Bash => format!(
+            r#"_{fn_name}_complete() {{
+    local IFS=$'\n'
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    COMPREPLY=($({cmd_name} {COMPLETE_CMD} "${{COMP_LINE}}" "${{COMP_POINT}}"))
+    case "${{COMPREPLY[0]}}" in
+        :file) COMPREPLY=($(compgen -f -- "$cur")) ;;
+        :dir) COMPREPLY=($(compgen -d -- "$cur")) ;;
+        :host) COMPREPLY=($(compgen -A hostname -- "$cur")) ;;
+        :command) COMPREPLY=($(compgen -c -- "$cur")) ;;
+        :none) COMPREPLY=(); compopt +o default 2>/dev/null ;;
+    esac
+}}
+complete -o default -F _{fn_name}_complete {cmd_name}
+"#
+        ),
+        Shell::Zsh => format!(
+            r#"#compdef {cmd_name}
+_{fn_name}_complete() {{
+    local -a candidates
+    candidates=("${{(@f)$({cmd_name} {COMPLETE_CMD} "$BUFFER" "$CURSOR")}}")
+    case "${{candidates[1]}}" in
+        :file) _files ;;
+        :dir) _files -/ ;;
+        :host) _hosts ;;
+        :command) _command_names -e ;;
+        :none) ;;
+        "") _files ;;
+        *) compadd -a candidates ;;
+    esac
+}}
+compdef _{fn_name}_complete {cmd_name}
+"#
+        ),
+        Shell::Fish => format!(
+            r#"function __{fn_name}_complete
+    set -l line (commandline -cp)
+    set -l candidates ({cmd_name} {COMPLETE_CMD} "$line" (string length -- "$line"))
+    switch "$candidates[1]"
+        case :file ''
+            __fish_complete_path (commandline -ct)
+        case :dir
+            __fish_complete_directories (commandline -ct)
+        case :host
+            __fish_print_hostnames
+        case :command
+            __fish_complete_command
+        case :none
+        case '*'
+            printf '%s\n' $candidates
+    end
+end
+complete -c {cmd_name} -f -a '(__{fn_name}_complete)'
+"#
+        ),
+        Shell::Elvish => format!(
+            r#"use str
+set edit:completion:arg-completer[{cmd_name}] = {{|@words|
+    {cmd_name} {COMPLETE_CMD} (str:join ' ' $words) | from-lines | each {{|c|
+        if (not (str:has-prefix $c :)) {{ put $c }}
+    }}
+}}
+"#
+        ),
+        Shell::Nushell => format!(
+            r#"def "nu-complete {cmd_name}" [context: string] {{
+    ^{cmd_name} {COMPLETE_CMD} $context | lines | where {{|c| not ($c | str starts-with ':') }}
+}}
+
+export extern "{cmd_name}" [
+    ...args: string@"nu-complete {cmd_name}"
+]
+"#
+        ),
+    }
+}
+
+fn cfg_names(cfg: &OptCfg) -> Vec<&str> {
+    if cfg.names.is_empty() {
+        if cfg.store_key.is_empty() || cfg.store_key == "*" {
+            return Vec::new();
+        }
+        return vec![&cfg.store_key];
+    }
+    cfg.names.iter().map(|s| s.as_str()).collect()
+}
+
+fn store_key_of(cfg: &OptCfg) -> &str {
+    if cfg.store_key.is_empty() {
+        cfg.names.first().map_or("", |s| s.as_str())
+    } else {
+        &cfg.store_key
+    }
+}
+
+/// Returns the store keys of the options given in the specified words.
+fn given_keys<'b>(opt_cfgs: &'b [OptCfg], words: &[&str]) -> Vec<&'b str> {
+    let find_key = |name: &str| {
+        opt_cfgs
+            .iter()
+            .find(|cfg| cfg_names(cfg).contains(&name))
+            .map(store_key_of)
+    };
+
+    let mut keys = Vec::new();
+    let mut takes_next = false;
+    for word in words.iter() {
+        if takes_next {
+            takes_next = false;
+            continue;
+        }
+        if let Some(long) = word.strip_prefix("--") {
+            let name = long.split('=').next().unwrap_or("");
+            keys.extend(find_key(name));
+        } else if let Some(short) = word.strip_prefix('-') {
+            let names = short.split('=').next().unwrap_or("");
+            for (i, ch) in names.char_indices() {
+                keys.extend(find_key(&names[i..i + ch.len_utf8()]));
+            }
+        } else {
+            continue;
+        }
+        takes_next = find_cfg_taking_next_arg(opt_cfgs, word).is_some_and(|cfg| cfg.has_arg);
+    }
+    keys
+}
+
+fn find_cfg_taking_next_arg<'b>(opt_cfgs: &'b [OptCfg], word: &str) -> Option<&'b OptCfg> {
+    let name = if let Some(long) = word.strip_prefix("--") {
+        if long.contains('=') {
+            return None;
+        }
+        long
+    } else if let Some(short) = word.strip_prefix('-') {
+        if short.contains('=') {
+            return None;
+        }
+        match short.char_indices().last() {
+            Some((i, _)) => &short[i..],
+            None => return None,
+        }
+    } else {
+        return None;
+    };
+
+    opt_cfgs.iter().find(|cfg| cfg_names(cfg).contains(&name))
+}
+
+impl<'a> Cmd<'a> {
+    /// Prints completion candidates to stdout if this command is invoked with
+    /// the hidden sub command `__complete`.
+    ///
+    /// The hidden sub command takes a command line and a cursor position, like
+    /// `app __complete "app --fo" 8`, which are passed by the shell scripts
+    /// generated by `completion::script`.
+    /// If the word at the cursor is an option argument of which
+    /// `OptCfg#complete` has a directive, this method prints the directive,
+    /// like `:file`, instead of the candidates.
+    /// This method returns `true` if it printed the candidates, then the
+    /// application should exit without doing anything else.
+    ///
+    /// ```rust
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::names;
+    ///
+    /// let opt_cfgs = vec![OptCfg::with(&[names(&["foo"])])];
+    /// let cmd = Cmd::with_strings(vec![ /* ... */ ]);
+    ///
+    /// if cmd.complete_if_requested(&opt_cfgs) {
+    ///     return;
+    /// }
+    /// ```
+    pub fn complete_if_requested(&self, opt_cfgs: &[OptCfg]) -> bool {
+        self.complete_if_requested_with_constraints(opt_cfgs, &Constraints::new())
+    }
+
+    /// Prints completion candidates like `complete_if_requested`, but
+    /// excludes the options which conflict with the options already given by
+    /// the specified constraints, as `completion::complete_with_constraints`
+    /// does.
+    pub fn complete_if_requested_with_constraints(
+        &self,
+        opt_cfgs: &[OptCfg],
+        constraints: &Constraints,
+    ) -> bool {
+        print_candidates(self.completion_request(opt_cfgs, constraints, &[]))
+    }
+
+    /// Prints completion candidates like `complete_if_requested`, and also
+    /// the specified sub command names, as `completion::complete_with_sub_cmds`
+    /// does.
+    ///
+    /// ```rust
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::names;
+    ///
+    /// let opt_cfgs = vec![OptCfg::with(&[names(&["foo"])])];
+    /// let cmd = Cmd::with_strings(vec![ /* ... */ ]);
+    ///
+    /// if cmd.complete_if_requested_with_sub_cmds(&opt_cfgs, &["list", "add"]) {
+    ///     return;
+    /// }
+    /// ```
+    pub fn complete_if_requested_with_sub_cmds(
+        &self,
+        opt_cfgs: &[OptCfg],
+        sub_cmds: &[&str],
+    ) -> bool {
+        print_candidates(self.completion_request(opt_cfgs, &Constraints::new(), sub_cmds))
+    }
+
+    fn completion_request(
+        &self,
+        opt_cfgs: &[OptCfg],
+        constraints: &Constraints,
+        sub_cmds: &[&str],
+    ) -> Option<Vec<String>> {
+        let (line, point) = self.completion_line()?;
+        let (words, current) = split_line(line, point);
+        Some(candidates_or_directive(
+            opt_cfgs,
+            constraints,
+            sub_cmds,
+            &words,
+            current,
+        ))
+    }
+
+    /// Returns the command line and the cursor position passed to the hidden
+    /// sub command `__complete`, or `None` if it is not requested.
+    pub(crate) fn completion_line(&self) -> Option<(&str, usize)> {
+        if self._arg_refs.len() < 2 || self._arg_refs[1] != COMPLETE_CMD {
+            return None;
+        }
+
+        let line = self._arg_refs.get(2).copied().unwrap_or("");
+        let point = match self._arg_refs.get(3) {
+            Some(s) => s.parse::<usize>().unwrap_or(line.chars().count()),
+            None => line.chars().count(),
+        };
+        Some((line, point))
+    }
+}
+
+pub(crate) fn print_candidates(candidates: Option<Vec<String>>) -> bool {
+    match candidates {
+        Some(candidates) => {
+            for candidate in candidates {
+                println!("{}", candidate);
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests_of_completion {
+    use super::*;
+    use crate::OptCfgParam;
+    use crate::OptCfgParam::{has_arg, hidden_names, names, store_key};
+
+    fn opt_cfgs() -> Vec<OptCfg> {
+        vec![
+            OptCfg::with(&[names(&["foo-bar", "f"])]),
+            OptCfg::with(&[names(&["foo-baz"]), has_arg(true)]),
+            OptCfg::with(&[names(&["qux", "q"]), has_arg(true)]),
+            OptCfg::with(&[store_key("quux")]),
+            OptCfg::with(&[store_key("*")]),
+        ]
+    }
+
+    mod tests_of_complete {
+        use super::*;
+
+        #[test]
+        fn should_complete_long_options() {
+            let cfgs = opt_cfgs();
+            assert_eq!(
+                complete(&cfgs, "app --foo", 9),
+                vec!["--foo-bar", "--foo-baz"]
+            );
+            assert_eq!(complete(&cfgs, "app --q", 7), vec!["--qux", "--quux"]);
+            assert_eq!(complete(&cfgs, "app --x", 7), Vec::<String>::new());
+        }
+
+        #[test]
+        fn should_complete_all_options_for_single_hyphen() {
+            let cfgs = opt_cfgs();
+            assert_eq!(
+                complete(&cfgs, "app -", 5),
+                vec!["--foo-bar", "-f", "--foo-baz", "--qux", "-q", "--quux"]
+            );
+        }
+
+        #[test]
+        fn should_not_complete_non_option_words() {
+            let cfgs = opt_cfgs();
+            assert_eq!(complete(&cfgs, "app ", 4), Vec::<String>::new());
+            assert_eq!(complete(&cfgs, "app fo", 6), Vec::<String>::new());
+            assert_eq!(complete(&cfgs, "", 0), Vec::<String>::new());
+        }
+
+        #[test]
+        fn should_not_complete_option_arguments() {
+            let cfgs = opt_cfgs();
+            assert_eq!(complete(&cfgs, "app --qux -", 11), Vec::<String>::new());
+            assert_eq!(complete(&cfgs, "app -fq -", 9), Vec::<String>::new());
+            assert_eq!(complete(&cfgs, "app --qux=-", 11), Vec::<String>::new());
+            assert_eq!(complete(&cfgs, "app -f --q", 10), vec!["--qux", "--quux"]);
+        }
+
+        #[test]
+        fn should_not_complete_after_end_of_options() {
+            let cfgs = opt_cfgs();
+            assert_eq!(complete(&cfgs, "app -- --f", 10), Vec::<String>::new());
+        }
+
+        #[test]
+        fn should_use_only_the_part_before_the_point() {
+            let cfgs = opt_cfgs();
+            assert_eq!(complete(&cfgs, "app --q --foo", 7), vec!["--qux", "--quux"]);
+            assert_eq!(complete(&cfgs, "app --q", 100), vec!["--qux", "--quux"]);
+        }
+
+        #[test]
+        fn should_not_complete_hidden_names() {
+            let cfgs = vec![OptCfg::with(&[
+                names(&["color", "colour", "c"]),
+                hidden_names(&["colour"]),
+                has_arg(true),
+            ])];
+            assert_eq!(complete(&cfgs, "app --col", 9), vec!["--color"]);
+            assert_eq!(complete(&cfgs, "app -", 5), vec!["--color", "-c"]);
+            assert_eq!(complete(&cfgs, "app --colour -", 14), Vec::<String>::new());
+        }
+
+        #[test]
+        fn should_exclude_conflicting_options() {
+            let cfgs = vec![
+                OptCfg::with(&[names(&["json", "j"])]),
+                OptCfg::with(&[names(&["yaml", "y"])]),
+                OptCfg::with(&[store_key("out"), names(&["output", "o"]), has_arg(true)]),
+                OptCfg::with(&[names(&["stdout"])]),
+            ];
+            let constraints = Constraints::new()
+                .exactly_one_of(&["json", "yaml"])
+                .at_most_one_of(&["out", "stdout"]);
+
+            assert_eq!(
+                complete_with_constraints(&cfgs, &constraints, "app --", 6),
+                vec!["--json", "--yaml", "--output", "--stdout"]
+            );
+            assert_eq!(
+                complete_with_constraints(&cfgs, &constraints, "app --json --", 13),
+                vec!["--json", "--output", "--stdout"]
+            );
+            assert_eq!(
+                complete_with_constraints(&cfgs, &constraints, "app -yo x -", 11),
+                vec!["--yaml", "-y", "--output", "-o"]
+            );
+            assert_eq!(
+                complete_with_constraints(&cfgs, &constraints, "app --output=--json --", 22),
+                vec!["--json", "--yaml", "--output"]
+            );
+            assert_eq!(
+                complete_with_constraints(&cfgs, &constraints, "app -o --json --", 16),
+                vec!["--json", "--yaml", "--output"]
+            );
+        }
+
+        #[test]
+        fn should_complete_choices_of_option_argument() {
+            let cfgs = vec![OptCfg::with(&[
+                names(&["color", "c"]),
+                has_arg(true),
+                OptCfgParam::complete(ValueHint::Choices(vec![
+                    "always".to_string(),
+                    "auto".to_string(),
+                    "never".to_string(),
+                ])),
+            ])];
+            assert_eq!(complete(&cfgs, "app --color a", 13), vec!["always", "auto"]);
+            assert_eq!(
+                complete(&cfgs, "app -c ", 7),
+                vec!["always", "auto", "never"]
+            );
+            assert_eq!(complete(&cfgs, "app --color=n", 13), vec!["--color=never"]);
+            assert_eq!(complete(&cfgs, "app -c=au", 9), vec!["-c=auto"]);
+            assert_eq!(
+                complete(&cfgs, "app -- --color a", 16),
+                Vec::<String>::new()
+            );
+        }
+
+        #[test]
+        fn should_get_value_hint_of_option_argument() {
+            let cfgs = vec![
+                OptCfg::with(&[
+                    names(&["input", "i"]),
+                    has_arg(true),
+                    OptCfgParam::complete(ValueHint::FilePath),
+                ]),
+                OptCfg::with(&[names(&["count"]), has_arg(true)]),
+                OptCfg::with(&[
+                    names(&["verbose"]),
+                    OptCfgParam::complete(ValueHint::DirPath),
+                ]),
+            ];
+            assert_eq!(value_hint(&cfgs, "app -i ", 7), Some(&ValueHint::FilePath));
+            assert_eq!(
+                value_hint(&cfgs, "app --input=a", 13),
+                Some(&ValueHint::FilePath)
+            );
+            assert_eq!(
+                value_hint(&cfgs, "app --count 1", 13),
+                Some(&ValueHint::Unknown)
+            );
+            assert_eq!(value_hint(&cfgs, "app --verbose ", 14), None);
+            assert_eq!(value_hint(&cfgs, "app --input", 11), None);
+            assert_eq!(value_hint(&cfgs, "app -- -i ", 10), None);
+        }
+    }
+
+    mod tests_of_complete_if_requested {
+        use super::*;
+
+        #[test]
+        fn should_return_candidates_if_requested() {
+            let cmd = Cmd::with_strings([
+                "/path/to/app".to_string(),
+                "__complete".to_string(),
+                "app --foo".to_string(),
+                "9".to_string(),
+            ]);
+            assert_eq!(
+                cmd.completion_request(&opt_cfgs(), &Constraints::new(), &[]),
+                Some(vec!["--foo-bar".to_string(), "--foo-baz".to_string()])
+            );
+        }
+
+        #[test]
+        fn should_use_end_of_line_if_point_is_omitted() {
+            let cmd = Cmd::with_strings([
+                "/path/to/app".to_string(),
+                "__complete".to_string(),
+                "app --qu".to_string(),
+            ]);
+            assert_eq!(
+                cmd.completion_request(&opt_cfgs(), &Constraints::new(), &[]),
+                Some(vec!["--qux".to_string(), "--quux".to_string()])
+            );
+        }
+
+        #[test]
+        fn should_return_directive_of_value_hint() {
+            let cfgs = vec![
+                OptCfg::with(&[
+                    names(&["dir"]),
+                    has_arg(true),
+                    OptCfgParam::complete(ValueHint::DirPath),
+                ]),
+                OptCfg::with(&[
+                    names(&["count"]),
+                    has_arg(true),
+                    OptCfgParam::complete(ValueHint::None),
+                ]),
+                OptCfg::with(&[names(&["name"]), has_arg(true)]),
+            ];
+            let request =
+                |line: &str| {
+                    Cmd::with_strings(["app", "__complete", line].map(String::from))
+                        .completion_request(&cfgs, &Constraints::new(), &[])
+                };
+            assert_eq!(request("app --dir "), Some(vec![":dir".to_string()]));
+            assert_eq!(request("app --count "), Some(vec![":none".to_string()]));
+            assert_eq!(request("app --name "), Some(vec![]));
+            assert_eq!(request("app --d"), Some(vec!["--dir".to_string()]));
+        }
+
+        #[test]
+        fn should_return_sub_command_names() {
+            let request =
+                |line: &str| {
+                    Cmd::with_strings(["app", "__complete", line].map(String::from))
+                        .completion_request(&opt_cfgs(), &Constraints::new(), &["status", "sync"])
+                };
+            assert_eq!(
+                request("app "),
+                Some(vec!["status".to_string(), "sync".to_string()])
+            );
+            assert_eq!(request("app -f --qux x sy"), Some(vec!["sync".to_string()]));
+            assert_eq!(request("app --qux s"), Some(vec![]));
+            assert_eq!(request("app sync s"), Some(vec![]));
+            assert_eq!(request("app -- s"), Some(vec![]));
+        }
+
+        #[test]
+        fn should_return_none_if_not_requested() {
+            let cmd = Cmd::with_strings(["/path/to/app".to_string(), "--foo-bar".to_string()]);
+            assert_eq!(
+                cmd.completion_request(&opt_cfgs(), &Constraints::new(), &[]),
+                None
+            );
+            assert!(!cmd.complete_if_requested(&opt_cfgs()));
+
+            let cmd = Cmd::with_strings([]);
+            assert_eq!(
+                cmd.completion_request(&opt_cfgs(), &Constraints::new(), &[]),
+                None
+            );
+        }
+    }
+
+    mod tests_of_script {
+        use super::*;
+
+        #[test]
+        fn should_generate_bash_script() {
+            let s = script(Shell::Bash, "my-app");
+            assert!(s.contains("_my_app_complete() {"));
+            assert!(s.contains("my-app __complete \"${COMP_LINE}\" \"${COMP_POINT}\""));
+            assert!(s.contains(":dir) COMPREPLY=($(compgen -d -- \"$cur\")) ;;"));
+            assert!(s.contains("complete -o default -F _my_app_complete my-app"));
+        }
+
+        #[test]
+        fn should_generate_zsh_script() {
+            let s = script(Shell::Zsh, "my-app");
+            assert!(s.starts_with("#compdef my-app\n"));
+            assert!(s.contains("my-app __complete \"$BUFFER\" \"$CURSOR\""));
+            assert!(s.contains(":dir) _files -/ ;;"));
+            assert!(s.contains("compdef _my_app_complete my-app"));
+        }
+
+        #[test]
+        fn should_generate_fish_script() {
+            let s = script(Shell::Fish, "my-app");
+            assert!(s.contains("function __my_app_complete"));
+            assert!(s.contains("case :dir\n            __fish_complete_directories"));
+            assert!(s.contains("complete -c my-app -f -a '(__my_app_complete)'"));
+        }
+
+        #[test]
+        fn should_generate_elvish_script() {
+            let s = script(Shell::Elvish, "my-app");
+            assert!(s.starts_with("use str\n"));
+            assert!(s.contains("set edit:completion:arg-completer[my-app] = {|@words|"));
+            assert!(s.contains("my-app __complete (str:join ' ' $words) | from-lines"));
+        }
+
+        #[test]
+        fn should_generate_nushell_script() {
+            let s = script(Shell::Nushell, "my-app");
+            assert!(s.contains("def \"nu-complete my-app\" [context: string] {"));
+            assert!(s.contains("^my-app __complete $context | lines"));
+            assert!(s.contains("export extern \"my-app\" ["));
+            assert!(s.contains("...args: string@\"nu-complete my-app\""));
+        }
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Layers;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Returns the path of the per-user config file of the specified application.
+///
+/// The path is `$XDG_CONFIG_HOME/<app_name>/config.toml` if the environment
+/// variable `XDG_CONFIG_HOME` is set to an absolute path.
+/// Otherwise it is `~/Library/Application Support/<app_name>/config.toml` on
+/// macOS, `%APPDATA%\<app_name>\config.toml` on Windows, and
+/// `~/.config/<app_name>/config.toml` on other platforms.
+/// This function returns `None` if the base directory cannot be determined.
+/// This function does not check whether the file exists.
+pub fn user_config_path(app_name: &str) -> Option<PathBuf> {
+    let dir = config_home()?;
+    Some(dir.join(app_name).join("config.toml"))
+}
+
+fn config_home() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").map(PathBuf::from) {
+        if dir.is_absolute() {
+            return Some(dir);
+        }
+    }
+
+    if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|h| PathBuf::from(h).join("Library/Application Support"))
+    } else {
+        env::var_os("HOME").map(|h| PathBuf::from(h).join(".config"))
+    }
+}
+
+impl Layers {
+    /// Adds a layer which has the option values written in the specified
+    /// config file, with the name of the source.
+    ///
+    /// The config file is written in a subset of TOML: each line is a
+    /// top-level `key = value` pair where the key is a store key and the value
+    /// is a string, a number, a boolean, or an array of them.
+    /// `true` means that the option is specified without arguments, and
+    /// `false` means that the option is not specified.
+    /// Comments starting with `#` and blank lines are ignored.
+    ///
+    /// If the file does not exist, no layer is added.
+    /// If the file cannot be read or has a line which is not in the above
+    /// format, this method returns an `io::Error`.
+    pub fn config_file(self, source: &str, path: impl AsRef<Path>) -> io::Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(self),
+            Err(e) => return Err(e),
+        };
+        let values = parse_config(&content)?;
+        Ok(self.layer(source, values))
+    }
+
+    /// Adds a layer which has the option values written in the per-user
+    /// config file of the specified application, with the name of the source.
+    ///
+    /// The path of the config file is given by `user_config_path`.
+    /// See `config_file` about the format of the file.
+    pub fn user_config(self, source: &str, app_name: &str) -> io::Result<Self> {
+        match user_config_path(app_name) {
+            Some(path) => self.config_file(source, path),
+            None => Ok(self),
+        }
+    }
+}
+
+fn parse_config(content: &str) -> io::Result<Vec<(String, Vec<String>)>> {
+    let mut values = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid config at line {}: {}", i + 1, line),
+            )
+        };
+
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+        let key = unquote(key.trim()).ok_or_else(invalid)?;
+        if key.is_empty() {
+            return Err(invalid());
+        }
+
+        let value = value.trim();
+        let vec = if let Some(inner) = value.strip_prefix('[') {
+            let inner = inner.strip_suffix(']').ok_or_else(invalid)?;
+            let mut vec = Vec::new();
+            for item in split_items(inner) {
+                let item = item.trim();
+                if item.is_empty() {
+                    continue;
+                }
+                vec.push(parse_scalar(item).ok_or_else(invalid)?);
+            }
+            vec
+        } else {
+            match value {
+                "true" => Vec::new(),
+                "false" => continue,
+                _ => vec![parse_scalar(value).ok_or_else(invalid)?],
+            }
+        };
+
+        values.push((key.to_string(), vec));
+    }
+
+    Ok(values)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    for (i, ch) in line.char_indices() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '#' => return &line[..i],
+            None => {}
+        }
+    }
+    line
+}
+
+fn split_items(s: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, ch) in s.char_indices() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == ',' => {
+                items.push(&s[start..i]);
+                start = i + 1;
+            }
+            None => {}
+        }
+    }
+    items.push(&s[start..]);
+    items
+}
+
+fn unquote(s: &str) -> Option<&str> {
+    for q in ['"', '\''] {
+        if let Some(inner) = s.strip_prefix(q) {
+            return inner.strip_suffix(q);
+        }
+    }
+    Some(s)
+}
+
+fn parse_scalar(s: &str) -> Option<String> {
+    if s.starts_with('"') || s.starts_with('\'') {
+        return unquote(s).map(|s| s.to_string());
+    }
+    if s.chars()
+        .any(|ch| ch.is_whitespace() || ch == '[' || ch == ']')
+    {
+        return None;
+    }
+    Some(s.to_string())
+}
+
+#[cfg(test)]
+mod tests_of_config_file {
+    use super::*;
+
+    #[test]
+    fn should_parse_config_content() {
+        let content = r#"
+# comment
+foo = "bar"   # trailing comment
+num = 123
+flag = true
+off = false
+list = ["a", 'b # c', 3]
+"quoted-key" = "x=y"
+"#;
+        let values = parse_config(content).unwrap();
+        assert_eq!(
+            values,
+            vec![
+                ("foo".to_string(), vec!["bar".to_string()]),
+                ("num".to_string(), vec!["123".to_string()]),
+                ("flag".to_string(), vec![]),
+                (
+                    "list".to_string(),
+                    vec!["a".to_string(), "b # c".to_string(), "3".to_string()]
+                ),
+                ("quoted-key".to_string(), vec!["x=y".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_lines() {
+        match parse_config("foo = 1\n[table]\n") {
+            Err(e) => {
+                assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+                assert_eq!(e.to_string(), "invalid config at line 2: [table]");
+            }
+            Ok(_) => panic!(),
+        }
+        assert!(parse_config("foo = two words").is_err());
+        assert!(parse_config("foo = [1, 2").is_err());
+        assert!(parse_config(" = 1").is_err());
+    }
+
+    #[test]
+    fn should_add_layer_of_config_file() {
+        let path = env::temp_dir().join("cliargs_test_3935.toml");
+        fs::write(&path, "foo = \"1\"\nbar = [\"2\", \"3\"]\n").unwrap();
+
+        let layers = Layers::new()
+            .layer("default", [("foo", vec!["0"]), ("baz", vec!["4"])])
+            .config_file("config", &path)
+            .unwrap();
+
+        assert_eq!(layers.opt_arg("foo"), Some("1"));
+        assert_eq!(layers.source_of("foo"), Some("config"));
+        assert_eq!(
+            layers.opt_args("bar"),
+            Some(&["2".to_string(), "3".to_string()] as &[String])
+        );
+        assert_eq!(layers.opt_arg("baz"), Some("4"));
+        assert_eq!(layers.source_of("baz"), Some("default"));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_ignore_missing_config_file() {
+        let layers = Layers::new()
+            .config_file("config", "/nonexistent/config.toml")
+            .unwrap();
+        assert_eq!(layers.store_keys(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn should_locate_user_config_path() {
+        let path = user_config_path("app");
+        if let Some(path) = path {
+            assert!(path.ends_with("app/config.toml"));
+        }
+    }
+}
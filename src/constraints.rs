@@ -0,0 +1,357 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::errors::InvalidOptCombination;
+use crate::Cmd;
+
+/// Declares constraints among options, which are checked after parsing.
+///
+/// Options are specified with their store keys.
+/// An option which has only its default values is treated as not specified.
+///
+/// ```rust
+/// use cliargs::{Cmd, Constraints, OptCfg};
+/// use cliargs::OptCfgParam::names;
+/// use cliargs::errors::InvalidOptCombination;
+///
+/// let opt_cfgs = vec![
+///     OptCfg::with(&[names(&["json"])]),
+///     OptCfg::with(&[names(&["yaml"])]),
+///     OptCfg::with(&[names(&["quiet"])]),
+///     OptCfg::with(&[names(&["verbose"])]),
+/// ];
+/// let constraints = Constraints::new()
+///     .exactly_one_of(&["json", "yaml"])
+///     .at_most_one_of(&["quiet", "verbose"]);
+///
+/// let mut cmd = Cmd::with_strings(["app", "--json", "--quiet", "--verbose"].map(String::from));
+/// cmd.parse_with(&opt_cfgs).unwrap();
+///
+/// assert_eq!(
+///     constraints.check(&cmd),
+///     Err(InvalidOptCombination::OptionsAreConflicted {
+///         options: vec!["quiet".to_string(), "verbose".to_string()],
+///     })
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct Constraints {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+enum Rule {
+    ExactlyOneOf(Vec<String>),
+    AtMostOneOf(Vec<String>),
+    AllOrNone(Vec<String>),
+    RequiredUnless(String, Vec<String>),
+    RequiredIf(String, String),
+}
+
+fn to_strings(keys: &[&str]) -> Vec<String> {
+    keys.iter().map(|s| s.to_string()).collect()
+}
+
+impl Constraints {
+    /// Creates a `Constraints` instance which has no constraint.
+    pub fn new() -> Constraints {
+        Constraints { rules: Vec::new() }
+    }
+
+    /// Adds a constraint that exactly one of the specified options must be
+    /// specified.
+    pub fn exactly_one_of(mut self, keys: &[&str]) -> Self {
+        self.rules.push(Rule::ExactlyOneOf(to_strings(keys)));
+        self
+    }
+
+    /// Adds a constraint that at most one of the specified options can be
+    /// specified.
+    pub fn at_most_one_of(mut self, keys: &[&str]) -> Self {
+        self.rules.push(Rule::AtMostOneOf(to_strings(keys)));
+        self
+    }
+
+    /// Adds a constraint that the specified options must be specified all
+    /// together or not at all.
+    pub fn all_or_none(mut self, keys: &[&str]) -> Self {
+        self.rules.push(Rule::AllOrNone(to_strings(keys)));
+        self
+    }
+
+    /// Adds a constraint that the option is required unless any of the other
+    /// options is specified.
+    pub fn required_unless(mut self, key: &str, unless: &[&str]) -> Self {
+        self.rules
+            .push(Rule::RequiredUnless(key.to_string(), to_strings(unless)));
+        self
+    }
+
+    /// Adds a constraint that the option is required if the other option is
+    /// specified.
+    pub fn required_if(mut self, key: &str, present: &str) -> Self {
+        self.rules
+            .push(Rule::RequiredIf(key.to_string(), present.to_string()));
+        self
+    }
+
+    /// Returns the store keys of the options which conflict with any of the
+    /// specified options by `exactly_one_of` or `at_most_one_of`
+    /// constraints, in the order of the constraints.
+    ///
+    /// This is used to stop offering such options in completions, like
+    /// `--yaml` after `--json`.
+    pub fn conflicting_keys(&self, given_keys: &[&str]) -> Vec<&str> {
+        let mut conflicting = Vec::new();
+        for rule in self.rules.iter() {
+            if let Rule::ExactlyOneOf(keys) | Rule::AtMostOneOf(keys) = rule {
+                if !keys.iter().any(|k| given_keys.contains(&k.as_str())) {
+                    continue;
+                }
+                for key in keys.iter() {
+                    if !given_keys.contains(&key.as_str()) && !conflicting.contains(&key.as_str()) {
+                        conflicting.push(key.as_str());
+                    }
+                }
+            }
+        }
+        conflicting
+    }
+
+    /// Checks the options in the specified `Cmd` instance with the
+    /// constraints in the order of addition, and returns the error of the
+    /// first violated constraint.
+    pub fn check(&self, cmd: &Cmd) -> Result<(), InvalidOptCombination> {
+        match self.check_all(cmd).into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the options in the specified `Cmd` instance with all the
+    /// constraints, and returns the errors of all violated constraints.
+    pub fn check_all(&self, cmd: &Cmd) -> Vec<InvalidOptCombination> {
+        let is_given = |key: &String| cmd.has_opt(key) && !cmd.defaulted.contains(&key.as_str());
+        let given_of = |keys: &[String]| -> Vec<String> {
+            keys.iter().filter(|k| is_given(k)).cloned().collect()
+        };
+
+        let mut errs = Vec::new();
+        for rule in self.rules.iter() {
+            match rule {
+                Rule::ExactlyOneOf(keys) => {
+                    let given = given_of(keys);
+                    if given.is_empty() {
+                        errs.push(InvalidOptCombination::NoneOfOptionsIsSpecified {
+                            options: keys.clone(),
+                        });
+                    } else if given.len() > 1 {
+                        errs.push(InvalidOptCombination::OptionsAreConflicted { options: given });
+                    }
+                }
+                Rule::AtMostOneOf(keys) => {
+                    let given = given_of(keys);
+                    if given.len() > 1 {
+                        errs.push(InvalidOptCombination::OptionsAreConflicted { options: given });
+                    }
+                }
+                Rule::AllOrNone(keys) => {
+                    let given = given_of(keys);
+                    if !given.is_empty() && given.len() < keys.len() {
+                        let missing = keys.iter().filter(|k| !is_given(k)).cloned().collect();
+                        errs.push(InvalidOptCombination::OptionsAreNotAllSpecified {
+                            specified: given,
+                            missing,
+                        });
+                    }
+                }
+                Rule::RequiredUnless(key, unless) => {
+                    if !is_given(key) && !unless.iter().any(is_given) {
+                        errs.push(InvalidOptCombination::OptionIsRequiredUnless {
+                            option: key.clone(),
+                            unless: unless.clone(),
+                        });
+                    }
+                }
+                Rule::RequiredIf(key, present) => {
+                    if !is_given(key) && is_given(present) {
+                        errs.push(InvalidOptCombination::OptionIsRequiredIf {
+                            option: key.clone(),
+                            present: present.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        errs
+    }
+}
+
+#[cfg(test)]
+mod tests_of_constraints {
+    use super::*;
+    use crate::testing::parse_ok;
+    use crate::OptCfg;
+    use crate::OptCfgParam::{defaults, has_arg, names};
+
+    fn opt_cfgs() -> Vec<OptCfg> {
+        vec![
+            OptCfg::with(&[names(&["json"])]),
+            OptCfg::with(&[names(&["yaml"])]),
+            OptCfg::with(&[names(&["user"]), has_arg(true)]),
+            OptCfg::with(&[names(&["password"]), has_arg(true)]),
+            OptCfg::with(&[names(&["output"]), has_arg(true), defaults(&["-"])]),
+            OptCfg::with(&[names(&["dry-run"])]),
+        ]
+    }
+
+    fn strings(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn should_check_exactly_one_of() {
+        let cfgs = opt_cfgs();
+        let constraints = Constraints::new().exactly_one_of(&["json", "yaml"]);
+
+        let cmd = parse_ok(&["--json"], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&[], Some(&cfgs));
+        assert_eq!(
+            constraints.check(&cmd),
+            Err(InvalidOptCombination::NoneOfOptionsIsSpecified {
+                options: strings(&["json", "yaml"]),
+            })
+        );
+
+        let cmd = parse_ok(&["--yaml", "--json"], Some(&cfgs));
+        assert_eq!(
+            constraints.check(&cmd),
+            Err(InvalidOptCombination::OptionsAreConflicted {
+                options: strings(&["json", "yaml"]),
+            })
+        );
+    }
+
+    #[test]
+    fn should_check_at_most_one_of() {
+        let cfgs = opt_cfgs();
+        let constraints = Constraints::new().at_most_one_of(&["json", "yaml"]);
+
+        let cmd = parse_ok(&[], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&["--yaml"], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&["--yaml", "--json"], Some(&cfgs));
+        assert_eq!(
+            constraints.check(&cmd),
+            Err(InvalidOptCombination::OptionsAreConflicted {
+                options: strings(&["json", "yaml"]),
+            })
+        );
+    }
+
+    #[test]
+    fn should_check_all_or_none() {
+        let cfgs = opt_cfgs();
+        let constraints = Constraints::new().all_or_none(&["user", "password"]);
+
+        let cmd = parse_ok(&[], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&["--user=a", "--password=b"], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&["--password=b"], Some(&cfgs));
+        assert_eq!(
+            constraints.check(&cmd),
+            Err(InvalidOptCombination::OptionsAreNotAllSpecified {
+                specified: strings(&["password"]),
+                missing: strings(&["user"]),
+            })
+        );
+    }
+
+    #[test]
+    fn should_check_required_unless() {
+        let cfgs = opt_cfgs();
+        let constraints = Constraints::new().required_unless("output", &["dry-run"]);
+
+        let cmd = parse_ok(&["--output=a"], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&["--dry-run"], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&[], Some(&cfgs));
+        assert_eq!(cmd.opt_arg("output"), Some("-"));
+        assert_eq!(
+            constraints.check(&cmd),
+            Err(InvalidOptCombination::OptionIsRequiredUnless {
+                option: "output".to_string(),
+                unless: strings(&["dry-run"]),
+            })
+        );
+    }
+
+    #[test]
+    fn should_check_required_if() {
+        let cfgs = opt_cfgs();
+        let constraints = Constraints::new().required_if("password", "user");
+
+        let cmd = parse_ok(&[], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&["--password=b"], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&["--user=a", "--password=b"], Some(&cfgs));
+        assert_eq!(constraints.check(&cmd), Ok(()));
+
+        let cmd = parse_ok(&["--user=a"], Some(&cfgs));
+        assert_eq!(
+            constraints.check(&cmd),
+            Err(InvalidOptCombination::OptionIsRequiredIf {
+                option: "password".to_string(),
+                present: "user".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_get_conflicting_keys() {
+        let constraints = Constraints::new()
+            .exactly_one_of(&["json", "yaml"])
+            .at_most_one_of(&["quiet", "verbose", "debug"])
+            .all_or_none(&["user", "password"])
+            .at_most_one_of(&["yaml", "toml"]);
+
+        assert_eq!(constraints.conflicting_keys(&[]), Vec::<&str>::new());
+        assert_eq!(constraints.conflicting_keys(&["json"]), ["yaml"]);
+        assert_eq!(
+            constraints.conflicting_keys(&["yaml", "verbose"]),
+            ["json", "quiet", "debug", "toml"]
+        );
+        assert_eq!(constraints.conflicting_keys(&["user"]), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn should_check_all_constraints() {
+        let cfgs = opt_cfgs();
+        let constraints = Constraints::new()
+            .exactly_one_of(&["json", "yaml"])
+            .all_or_none(&["user", "password"])
+            .required_unless("output", &["dry-run"]);
+
+        let cmd = parse_ok(&["--user=a"], Some(&cfgs));
+        let errs = constraints.check_all(&cmd);
+        assert_eq!(errs.len(), 3);
+        assert_eq!(errs[0].options(), ["json", "yaml"]);
+        assert_eq!(errs[1].options(), ["user", "password"]);
+        assert_eq!(errs[2].options(), ["output", "dry-run"]);
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Cmd;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+impl<'a> Cmd<'a> {
+    /// Loads the variables in the specified `.env` file as the environment
+    /// variables used for the fallbacks of options.
+    ///
+    /// The loaded variables do not modify the environment variables of the
+    /// current process, and are used only when the environment prefix is set
+    /// with `with_env_prefix`.
+    /// If `override_env` is true, the loaded variables take precedence over
+    /// the environment variables of the same names; otherwise the environment
+    /// variables take precedence.
+    ///
+    /// Each line of a `.env` file is `KEY=VALUE`, optionally preceded by
+    /// `export`.
+    /// Blank lines and lines starting with `#` are ignored, and a value
+    /// enclosed in single or double quotes is unquoted.
+    ///
+    /// This method is available when the `dotenv` feature is enabled.
+    pub fn load_dotenv(&mut self, path: impl AsRef<Path>, override_env: bool) -> io::Result<()> {
+        let content = fs::read_to_string(path)?;
+        self.dotenv_vars.extend(parse_dotenv(&content));
+        self.dotenv_override = override_env;
+        Ok(())
+    }
+}
+
+fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    let mut vars = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            continue;
+        }
+        let value = value.trim();
+        let value = if value.len() >= 2
+            && ((value.starts_with('"') && value.ends_with('"'))
+                || (value.starts_with('\'') && value.ends_with('\'')))
+        {
+            &value[1..value.len() - 1]
+        } else {
+            value
+        };
+        vars.push((key.to_string(), value.to_string()));
+    }
+
+    vars
+}
+
+#[cfg(test)]
+mod tests_of_dotenv {
+    use super::*;
+    use crate::OptCfg;
+    use crate::OptCfgParam::{has_arg, names};
+    use std::env;
+
+    #[test]
+    fn should_parse_dotenv_content() {
+        let vars = parse_dotenv(
+            "# comment\n\nFOO=1\nexport BAR = two words \nBAZ=\"quoted # value\"\nQUX='x'\ninvalid\n=none\n",
+        );
+        assert_eq!(
+            vars,
+            vec![
+                ("FOO".to_string(), "1".to_string()),
+                ("BAR".to_string(), "two words".to_string()),
+                ("BAZ".to_string(), "quoted # value".to_string()),
+                ("QUX".to_string(), "x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_use_dotenv_vars_as_fallbacks() {
+        let path = env::temp_dir().join("cliargs_test_3934.env");
+        fs::write(&path, "TEST3934_FOO=from-file\nTEST3934_BAR=from-file\n").unwrap();
+        env::set_var("TEST3934_BAR", "from-env");
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo"]), has_arg(true)]),
+            OptCfg::with(&[names(&["bar"]), has_arg(true)]),
+        ];
+
+        let mut cmd = Cmd::with_strings(["app".to_string()]).with_env_prefix("TEST3934");
+        assert!(cmd.load_dotenv(&path, false).is_ok());
+        assert_eq!(cmd.parse_with(&opt_cfgs), Ok(()));
+        assert_eq!(cmd.opt_arg("foo"), Some("from-file"));
+        assert_eq!(cmd.opt_arg("bar"), Some("from-env"));
+
+        let mut cmd = Cmd::with_strings(["app".to_string()]).with_env_prefix("TEST3934");
+        assert!(cmd.load_dotenv(&path, true).is_ok());
+        assert_eq!(cmd.parse_with(&opt_cfgs), Ok(()));
+        assert_eq!(cmd.opt_arg("foo"), Some("from-file"));
+        assert_eq!(cmd.opt_arg("bar"), Some("from-file"));
+
+        assert!(env::var("TEST3934_FOO").is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_fail_to_load_missing_file() {
+        let mut cmd = Cmd::with_strings(["app".to_string()]);
+        assert!(cmd.load_dotenv("/nonexistent/.env", false).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Cmd;
+use crate::OptCfg;
+use std::env;
+
+impl<'a> Cmd<'a> {
+    /// Sets the prefix of environment variables which are used as fallbacks
+    /// of options not given in command line arguments.
+    ///
+    /// When parsing with option configurations, the value of an option which
+    /// is not given in command line arguments is read from the environment
+    /// variable of which the name is derived from its store key, like
+    /// `MYAPP_FOO_BAR` for `foo-bar` with the prefix `MYAPP`.
+    /// The value of an array option is split with the separator set by
+    /// `env_separator`, which is `,` by default.
+    /// An option which takes no argument is specified if the environment
+    /// variable is set to a value other than an empty string, `0`, or
+    /// `false`.
+    /// The values from environment variables take precedence over default
+    /// values.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::{names, has_arg, is_array};
+    ///
+    /// std::env::set_var("MYAPP_INCLUDE_DIR", "/usr/include,/opt/include");
+    ///
+    /// let mut cmd = Cmd::with_strings(["app".to_string()]).with_env_prefix("MYAPP");
+    /// cmd.parse_with(&[
+    ///     OptCfg::with(&[names(&["include-dir"]), has_arg(true), is_array(true)]),
+    /// ]).unwrap();
+    /// assert_eq!(cmd.opt_args("include-dir"), Some(&["/usr/include", "/opt/include"] as &[&str]));
+    /// ```
+    pub fn with_env_prefix(mut self, prefix: &str) -> Self {
+        self.env_prefix = prefix.to_string();
+        self
+    }
+
+    /// Sets the separator to split the value of an environment variable for
+    /// an array option.
+    pub fn env_separator(mut self, separator: &str) -> Self {
+        self.env_separator = separator.to_string();
+        self
+    }
+
+    /// Returns the name of the environment variable for the specified store
+    /// key, or `None` if no prefix is set.
+    pub fn env_var_name(&self, store_key: &str) -> Option<String> {
+        if self.env_prefix.is_empty() {
+            return None;
+        }
+        let key: String = store_key
+            .chars()
+            .map(|ch| match ch {
+                '-' => '_',
+                _ => ch.to_ascii_uppercase(),
+            })
+            .collect();
+        Some(format!("{}_{}", self.env_prefix, key))
+    }
+
+    pub(crate) fn env_values(&self, cfg: &OptCfg, store_key: &str) -> Option<Vec<String>> {
+        let name = self.env_var_name(store_key)?;
+        let value = self.env_var(&name)?;
+        if !cfg.has_arg {
+            return match value.as_str() {
+                "" | "0" | "false" => None,
+                _ => Some(Vec::new()),
+            };
+        }
+        if cfg.is_array && !self.env_separator.is_empty() {
+            Some(
+                value
+                    .split(self.env_separator.as_str())
+                    .map(|s| s.to_string())
+                    .collect(),
+            )
+        } else {
+            Some(vec![value])
+        }
+    }
+
+    #[cfg(not(feature = "dotenv"))]
+    fn env_var(&self, name: &str) -> Option<String> {
+        env::var(name).ok()
+    }
+
+    #[cfg(feature = "dotenv")]
+    fn env_var(&self, name: &str) -> Option<String> {
+        let dotenv_var = self.dotenv_vars.get(name).cloned();
+        if self.dotenv_override {
+            dotenv_var.or_else(|| env::var(name).ok())
+        } else {
+            env::var(name).ok().or(dotenv_var)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_of_env {
+    use crate::errors::InvalidOption;
+    use crate::validators::validate_number;
+    use crate::Cmd;
+    use crate::OptCfg;
+    use crate::OptCfgParam::store_key as key;
+    use crate::OptCfgParam::{defaults, has_arg, is_array, names, validator};
+    use std::env;
+
+    fn opt_cfgs() -> Vec<OptCfg> {
+        vec![
+            OptCfg::with(&[names(&["verbose", "v"])]),
+            OptCfg::with(&[names(&["quiet"])]),
+            OptCfg::with(&[key("logLevel"), names(&["log-level"]), has_arg(true)]),
+            OptCfg::with(&[names(&["tag"]), has_arg(true), is_array(true)]),
+            OptCfg::with(&[names(&["port"]), has_arg(true), defaults(&["80"])]),
+        ]
+    }
+
+    #[test]
+    fn should_derive_env_var_names() {
+        let cmd = Cmd::with_strings([]);
+        assert_eq!(cmd.env_var_name("foo-bar"), None);
+
+        let cmd = Cmd::with_strings([]).with_env_prefix("MYAPP");
+        assert_eq!(
+            cmd.env_var_name("foo-bar"),
+            Some("MYAPP_FOO_BAR".to_string())
+        );
+        assert_eq!(
+            cmd.env_var_name("logLevel"),
+            Some("MYAPP_LOGLEVEL".to_string())
+        );
+    }
+
+    #[test]
+    fn should_use_env_vars_as_fallbacks() {
+        env::set_var("TEST3933A_VERBOSE", "1");
+        env::set_var("TEST3933A_QUIET", "false");
+        env::set_var("TEST3933A_LOGLEVEL", "debug");
+        env::set_var("TEST3933A_TAG", "a;b;c");
+        env::set_var("TEST3933A_PORT", "8080");
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--port=443".to_string()])
+            .with_env_prefix("TEST3933A")
+            .env_separator(";");
+        assert_eq!(cmd.parse_with(&opt_cfgs()), Ok(()));
+
+        assert!(cmd.has_opt("verbose"));
+        assert!(!cmd.has_opt("quiet"));
+        assert_eq!(cmd.opt_arg("logLevel"), Some("debug"));
+        assert_eq!(cmd.opt_args("tag"), Some(&["a", "b", "c"] as &[&str]));
+        assert_eq!(cmd.opt_arg("port"), Some("443"));
+        assert_eq!(cmd.opt_occurrence_count("verbose"), 0);
+    }
+
+    #[test]
+    fn should_not_use_env_vars_without_prefix() {
+        env::set_var("_VERBOSE", "1");
+
+        let mut cmd = Cmd::with_strings(["app".to_string()]);
+        assert_eq!(cmd.parse_with(&opt_cfgs()), Ok(()));
+        assert!(!cmd.has_opt("verbose"));
+        assert_eq!(cmd.opt_arg("port"), Some("80"));
+    }
+
+    #[test]
+    fn should_validate_env_var_values() {
+        env::set_var("TEST3933B_PORT", "x");
+
+        let mut cmd = Cmd::with_strings(["app".to_string()]).with_env_prefix("TEST3933B");
+        let opt_cfgs = vec![OptCfg::with(&[
+            names(&["port"]),
+            has_arg(true),
+            validator(validate_number::<u16>),
+        ])];
+        match cmd.parse_with(&opt_cfgs) {
+            Err(InvalidOption::OptionArgIsInvalid {
+                store_key,
+                option,
+                opt_arg,
+                ..
+            }) => {
+                assert_eq!(store_key, "port");
+                assert_eq!(option, "TEST3933B_PORT");
+                assert_eq!(opt_arg, "x");
+            }
+            _ => panic!(),
+        }
+    }
+}
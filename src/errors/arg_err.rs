@@ -2,6 +2,7 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
+use super::ErrorKind;
 use std::error;
 use std::ffi;
 use std::fmt;
@@ -23,6 +24,13 @@ pub enum InvalidOsArg {
     },
 }
 
+impl InvalidOsArg {
+    /// Returns the category of this error, which is always `ErrorKind::Os`.
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::Os
+    }
+}
+
 impl fmt::Display for InvalidOsArg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match self {
@@ -60,7 +68,7 @@ mod tests_of_invalid_os_arg {
             });
 
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => {
                     assert_eq!(format!("{err}"), "The command line arguments contains invalid unicode (index: 12, arguments: \"Hello �World\")");
                     assert_eq!(format!("{err:?}"), "OsArgsContainInvalidUnicode { index: 12, os_arg: \"Hello \\xF0\\x90\\x80World\" }");
@@ -68,7 +76,7 @@ mod tests_of_invalid_os_arg {
             }
 
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => match err {
                     InvalidOsArg::OsArgsContainInvalidUnicode { index, os_arg } => {
                         assert_eq!(*index, 12);
@@ -78,7 +86,7 @@ mod tests_of_invalid_os_arg {
             }
 
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOsArg::OsArgsContainInvalidUnicode { index, os_arg }) => {
                     assert_eq!(index, 12);
                     assert_eq!(format!("{os_arg:?}"), "\"Hello \\xF0\\x90\\x80World\"");
@@ -98,7 +106,7 @@ mod tests_of_invalid_os_arg {
             });
 
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => {
                     assert_eq!(format!("{err:?}"), "OsArgsContainInvalidUnicode { index: 12, os_arg: \"Hello \\xF0\\x90\\x80World\" }");
                 }
@@ -117,7 +125,7 @@ mod tests_of_invalid_os_arg {
             });
 
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => {
                     assert_eq!(format!("{err}"), "The command line arguments contains invalid unicode (index: 12, arguments: \"Hello �World\")");
                 }
@@ -143,7 +151,7 @@ mod tests_of_invalid_os_arg {
             }
 
             match returns_dyn_std_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     //println!("{err:?}");
                     if let Some(os_arg_err) = err.downcast_ref::<InvalidOsArg>() {
@@ -157,7 +165,7 @@ mod tests_of_invalid_os_arg {
                             }
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
@@ -0,0 +1,214 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::{ErrorKind, InvalidOption, InvalidOsArg};
+use std::error;
+use std::fmt;
+
+/// The enum type for errors of the whole command line arguments.
+///
+/// The variants of this enum wrap the errors which can occur when reading
+/// and parsing command line arguments at once, like `App#run`.
+#[derive(Debug, PartialEq)]
+pub enum InvalidArgs {
+    /// Indicates that the command line arguments are invalid as `OsString`s.
+    OsArgIsInvalid(InvalidOsArg),
+
+    /// Indicates that an option or an option argument is invalid.
+    OptionIsInvalid(InvalidOption),
+
+    /// Indicates that the sub command is not found.
+    SubCmdIsUnknown {
+        /// The specified sub command name.
+        name: String,
+
+        /// The names of the known sub commands similar to the specified one.
+        suggestions: Vec<String>,
+    },
+
+    /// Indicates that a command argument is invalid as a glob pattern.
+    GlobPatternIsInvalid {
+        /// The command argument.
+        pattern: String,
+
+        /// The details of the error.
+        details: String,
+    },
+
+    /// Indicates that a glob pattern in command arguments matches no path.
+    GlobPatternMatchesNothing {
+        /// The command argument.
+        pattern: String,
+    },
+}
+
+impl InvalidArgs {
+    /// Returns the category of this error.
+    ///
+    /// For the variants wrapping another error, this method returns the
+    /// category of the wrapped error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            InvalidArgs::OsArgIsInvalid(err) => err.kind(),
+            InvalidArgs::OptionIsInvalid(err) => err.kind(),
+            InvalidArgs::SubCmdIsUnknown { .. } => ErrorKind::Usage,
+            InvalidArgs::GlobPatternIsInvalid { .. } => ErrorKind::Usage,
+            InvalidArgs::GlobPatternMatchesNothing { .. } => ErrorKind::Usage,
+        }
+    }
+}
+
+impl fmt::Display for InvalidArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            InvalidArgs::OsArgIsInvalid(err) => err.fmt(f),
+            InvalidArgs::OptionIsInvalid(err) => err.fmt(f),
+            InvalidArgs::SubCmdIsUnknown { name, suggestions } => {
+                write!(
+                    f,
+                    "The sub command is unknown (sub command: \"{}\")",
+                    name.escape_debug()
+                )?;
+                if !suggestions.is_empty() {
+                    let list: Vec<String> = suggestions
+                        .iter()
+                        .map(|s| format!("\"{}\"", s.escape_debug()))
+                        .collect();
+                    write!(f, ", did you mean {}?", list.join(" or "))?;
+                }
+                Ok(())
+            }
+            InvalidArgs::GlobPatternIsInvalid { pattern, details } => write!(
+                f,
+                "The glob pattern is invalid (pattern: \"{}\", details: {})",
+                pattern.escape_debug(),
+                details
+            ),
+            InvalidArgs::GlobPatternMatchesNothing { pattern } => write!(
+                f,
+                "The glob pattern matches nothing (pattern: \"{}\")",
+                pattern.escape_debug()
+            ),
+        }
+    }
+}
+
+impl error::Error for InvalidArgs {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            InvalidArgs::OsArgIsInvalid(err) => Some(err),
+            InvalidArgs::OptionIsInvalid(err) => Some(err),
+            InvalidArgs::SubCmdIsUnknown { .. } => None,
+            InvalidArgs::GlobPatternIsInvalid { .. } => None,
+            InvalidArgs::GlobPatternMatchesNothing { .. } => None,
+        }
+    }
+}
+
+impl From<InvalidOsArg> for InvalidArgs {
+    fn from(err: InvalidOsArg) -> Self {
+        InvalidArgs::OsArgIsInvalid(err)
+    }
+}
+
+impl From<InvalidOption> for InvalidArgs {
+    fn from(err: InvalidOption) -> Self {
+        InvalidArgs::OptionIsInvalid(err)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_invalid_args {
+    use super::*;
+
+    #[test]
+    fn should_wrap_invalid_option() {
+        let err: InvalidArgs = InvalidOption::UnconfiguredOption {
+            option: "foo".to_string(),
+        }
+        .into();
+
+        assert_eq!(
+            format!("{err:?}"),
+            "OptionIsInvalid(UnconfiguredOption { option: \"foo\" })"
+        );
+        assert_eq!(
+            format!("{err}"),
+            "The option is not specified in configurations (option: \"foo\")"
+        );
+
+        let source = error::Error::source(&err).unwrap();
+        match source.downcast_ref::<InvalidOption>() {
+            Some(InvalidOption::UnconfiguredOption { option }) => assert_eq!(option, "foo"),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_wrap_invalid_os_arg() {
+        let err: InvalidArgs = InvalidOsArg::OsArgsContainInvalidUnicode {
+            index: 1,
+            os_arg: std::ffi::OsString::from("foo"),
+        }
+        .into();
+
+        assert_eq!(
+            format!("{err:?}"),
+            "OsArgIsInvalid(OsArgsContainInvalidUnicode { index: 1, os_arg: \"foo\" })"
+        );
+        assert_eq!(
+            format!("{err}"),
+            "The command line arguments contains invalid unicode (index: 1, arguments: \"foo\")"
+        );
+    }
+
+    #[test]
+    fn should_write_unknown_sub_cmd() {
+        let err = InvalidArgs::SubCmdIsUnknown {
+            name: "stauts".to_string(),
+            suggestions: vec!["status".to_string(), "stats".to_string()],
+        };
+        assert_eq!(
+            format!("{err:?}"),
+            "SubCmdIsUnknown { name: \"stauts\", suggestions: [\"status\", \"stats\"] }"
+        );
+        assert_eq!(
+            format!("{err}"),
+            "The sub command is unknown (sub command: \"stauts\"), did you mean \"status\" or \"stats\"?"
+        );
+        assert!(error::Error::source(&err).is_none());
+
+        let err = InvalidArgs::SubCmdIsUnknown {
+            name: "foo".to_string(),
+            suggestions: vec![],
+        };
+        assert_eq!(
+            format!("{err}"),
+            "The sub command is unknown (sub command: \"foo\")"
+        );
+    }
+
+    #[test]
+    fn should_write_glob_errors() {
+        let err = InvalidArgs::GlobPatternIsInvalid {
+            pattern: "[a".to_string(),
+            details: "invalid range pattern".to_string(),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "The glob pattern is invalid (pattern: \"[a\", details: invalid range pattern)"
+        );
+        assert_eq!(err.kind(), ErrorKind::Usage);
+
+        let err = InvalidArgs::GlobPatternMatchesNothing {
+            pattern: "*.txt".to_string(),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "The glob pattern matches nothing (pattern: \"*.txt\")"
+        );
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        assert!(error::Error::source(&err).is_none());
+    }
+}
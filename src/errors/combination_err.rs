@@ -0,0 +1,204 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::ErrorKind;
+use std::error;
+use std::fmt;
+
+/// The enum type for errors of combinations of options.
+///
+/// The variants of this enum indicate that the options specified in command
+/// line arguments violate constraints among them, which are checked by
+/// `Constraints#check`.
+/// Each variant has the store keys of the options involved in the violation.
+#[derive(Debug, PartialEq)]
+pub enum InvalidOptCombination {
+    /// Indicates that none of the options is specified though one of them is
+    /// required.
+    NoneOfOptionsIsSpecified {
+        /// The store keys of the options of which one is required.
+        options: Vec<String>,
+    },
+
+    /// Indicates that multiple options are specified though they are mutually
+    /// exclusive.
+    OptionsAreConflicted {
+        /// The store keys of the specified options which are conflicted.
+        options: Vec<String>,
+    },
+
+    /// Indicates that only a part of options are specified though they must
+    /// be specified all together or not at all.
+    OptionsAreNotAllSpecified {
+        /// The store keys of the specified options.
+        specified: Vec<String>,
+
+        /// The store keys of the options which are not specified.
+        missing: Vec<String>,
+    },
+
+    /// Indicates that the option is not specified though it is required
+    /// unless any of other options is specified.
+    OptionIsRequiredUnless {
+        /// The store key of the required option.
+        option: String,
+
+        /// The store keys of the options which make the option unnecessary.
+        unless: Vec<String>,
+    },
+
+    /// Indicates that the option is not specified though it is required
+    /// because another option is specified.
+    OptionIsRequiredIf {
+        /// The store key of the required option.
+        option: String,
+
+        /// The store key of the specified option which requires the option.
+        present: String,
+    },
+}
+
+impl InvalidOptCombination {
+    /// Returns the store keys of the options involved in this error.
+    pub fn options(&self) -> Vec<&str> {
+        match self {
+            InvalidOptCombination::NoneOfOptionsIsSpecified { options } => {
+                options.iter().map(|s| s.as_str()).collect()
+            }
+            InvalidOptCombination::OptionsAreConflicted { options } => {
+                options.iter().map(|s| s.as_str()).collect()
+            }
+            InvalidOptCombination::OptionsAreNotAllSpecified { specified, missing } => specified
+                .iter()
+                .chain(missing.iter())
+                .map(|s| s.as_str())
+                .collect(),
+            InvalidOptCombination::OptionIsRequiredUnless { option, unless } => {
+                std::iter::once(option)
+                    .chain(unless.iter())
+                    .map(|s| s.as_str())
+                    .collect()
+            }
+            InvalidOptCombination::OptionIsRequiredIf { option, present } => {
+                vec![option.as_str(), present.as_str()]
+            }
+        }
+    }
+
+    /// Returns the category of this error, which is always
+    /// `ErrorKind::Usage`.
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::Usage
+    }
+}
+
+fn quote_all(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|s| format!("\"{}\"", s.escape_debug()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl fmt::Display for InvalidOptCombination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            InvalidOptCombination::NoneOfOptionsIsSpecified { options } => write!(
+                f,
+                "One of the options is required (options: {})",
+                quote_all(options),
+            ),
+            InvalidOptCombination::OptionsAreConflicted { options } => write!(
+                f,
+                "The options cannot be specified together (options: {})",
+                quote_all(options),
+            ),
+            InvalidOptCombination::OptionsAreNotAllSpecified { specified, missing } => write!(
+                f,
+                "The options must be specified together (specified: {}, missing: {})",
+                quote_all(specified),
+                quote_all(missing),
+            ),
+            InvalidOptCombination::OptionIsRequiredUnless { option, unless } => write!(
+                f,
+                "The option is required unless any of the other options is specified \
+                 (option: \"{}\", unless: {})",
+                option.escape_debug(),
+                quote_all(unless),
+            ),
+            InvalidOptCombination::OptionIsRequiredIf { option, present } => write!(
+                f,
+                "The option is required when the other option is specified \
+                 (option: \"{}\", present: \"{}\")",
+                option.escape_debug(),
+                present.escape_debug(),
+            ),
+        }
+    }
+}
+
+impl error::Error for InvalidOptCombination {}
+
+#[cfg(test)]
+mod tests_of_invalid_opt_combination {
+    use super::*;
+
+    fn strings(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn should_write_for_display() {
+        let err = InvalidOptCombination::NoneOfOptionsIsSpecified {
+            options: strings(&["json", "yaml"]),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "One of the options is required (options: \"json\", \"yaml\")"
+        );
+        assert_eq!(err.options(), ["json", "yaml"]);
+
+        let err = InvalidOptCombination::OptionsAreConflicted {
+            options: strings(&["quiet", "verbose"]),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "The options cannot be specified together (options: \"quiet\", \"verbose\")"
+        );
+        assert_eq!(err.options(), ["quiet", "verbose"]);
+
+        let err = InvalidOptCombination::OptionsAreNotAllSpecified {
+            specified: strings(&["user"]),
+            missing: strings(&["password"]),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "The options must be specified together (specified: \"user\", missing: \"password\")"
+        );
+        assert_eq!(err.options(), ["user", "password"]);
+
+        let err = InvalidOptCombination::OptionIsRequiredUnless {
+            option: "output".to_string(),
+            unless: strings(&["dry-run"]),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "The option is required unless any of the other options is specified \
+             (option: \"output\", unless: \"dry-run\")"
+        );
+        assert_eq!(err.options(), ["output", "dry-run"]);
+
+        let err = InvalidOptCombination::OptionIsRequiredIf {
+            option: "key".to_string(),
+            present: "tls".to_string(),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "The option is required when the other option is specified \
+             (option: \"key\", present: \"tls\")"
+        );
+        assert_eq!(err.options(), ["key", "tls"]);
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+/// The enum type for the categories of errors in this crate.
+///
+/// Each error enum of this crate has `kind()` method which returns one of
+/// these categories, so that applications can branch on the category without
+/// matching all variants, which may increase in the future.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// Indicates that the command line arguments given by a user are wrong,
+    /// like an unknown option or a missing option argument.
+    Usage,
+
+    /// Indicates that the option configurations or the command description
+    /// given by an application are wrong.
+    Config,
+
+    /// Indicates that an option argument cannot be converted or is rejected
+    /// by a validator.
+    Conversion,
+
+    /// Indicates that the command line arguments given by the OS cannot be
+    /// handled.
+    Os,
+}
+
+#[cfg(test)]
+mod tests_of_error_kind {
+    use super::*;
+    use crate::errors::{InvalidArgs, InvalidOption, InvalidOsArg, InvalidSpec};
+
+    #[test]
+    fn should_return_kind_of_invalid_option() {
+        let s = || "foo".to_string();
+
+        let err = InvalidOption::OptionContainsInvalidChar { option: s() };
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let err = InvalidOption::UnconfiguredOption { option: s() };
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let err = InvalidOption::OptionNeedsArg {
+            option: s(),
+            store_key: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let err = InvalidOption::OptionTakesNoArg {
+            option: s(),
+            store_key: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let err = InvalidOption::OptionIsNotArray {
+            option: s(),
+            store_key: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let err = InvalidOption::StoreKeyIsDuplicated {
+            store_key: s(),
+            name: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Config);
+        let err = InvalidOption::ConfigIsArrayButHasNoArg {
+            store_key: s(),
+            name: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Config);
+        let err = InvalidOption::ConfigHasDefaultsButHasNoArg {
+            store_key: s(),
+            name: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Config);
+        let err = InvalidOption::OptionNameIsDuplicated {
+            store_key: s(),
+            name: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Config);
+        let err = InvalidOption::ShortNameIsNotAssignable {
+            store_key: s(),
+            name: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Config);
+        let err = InvalidOption::OptionArgIsInvalid {
+            store_key: s(),
+            option: s(),
+            opt_arg: s(),
+            details: s(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Conversion);
+    }
+
+    #[test]
+    fn should_return_kind_of_other_errors() {
+        let err = InvalidOsArg::OsArgsContainInvalidUnicode {
+            index: 1,
+            os_arg: std::ffi::OsString::from("foo"),
+        };
+        assert_eq!(err.kind(), ErrorKind::Os);
+
+        let err = InvalidSpec::JsonIsMalformed {
+            position: 0,
+            details: "".to_string(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Config);
+        let err = InvalidSpec::FieldTypeIsInvalid {
+            field: "".to_string(),
+            expected: "".to_string(),
+        };
+        assert_eq!(err.kind(), ErrorKind::Config);
+
+        let err = InvalidArgs::SubCmdIsUnknown {
+            name: "foo".to_string(),
+            suggestions: vec![],
+        };
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let err: InvalidArgs = InvalidOption::OptionArgIsInvalid {
+            store_key: "".to_string(),
+            option: "".to_string(),
+            opt_arg: "".to_string(),
+            details: "".to_string(),
+        }
+        .into();
+        assert_eq!(err.kind(), ErrorKind::Conversion);
+        let err: InvalidArgs = InvalidOsArg::OsArgsContainInvalidUnicode {
+            index: 1,
+            os_arg: std::ffi::OsString::from("foo"),
+        }
+        .into();
+        assert_eq!(err.kind(), ErrorKind::Os);
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::fmt;
+
+/// Builds a user-facing error message with the context of an application.
+///
+/// This struct wraps an error with the application name, the path of sub
+/// commands, and an optional URL of documents, and formats them as
+/// `myapp sync: error: <error>` by `Display`.
+///
+/// ```
+/// use cliargs::errors::{ErrorMessage, InvalidOption};
+///
+/// let err = InvalidOption::UnconfiguredOption { option: "foo".to_string() };
+/// let msg = ErrorMessage::new(&err)
+///     .app_name("myapp")
+///     .sub_cmd("sync")
+///     .docs_url("https://example.com/docs");
+/// assert_eq!(
+///     msg.to_string(),
+///     "myapp sync: error: The option is not specified in configurations (option: \"foo\")\n\
+///      For more information, see https://example.com/docs"
+/// );
+/// ```
+pub struct ErrorMessage<'a> {
+    err: &'a dyn fmt::Display,
+    app_name: String,
+    sub_cmds: Vec<String>,
+    docs_url: String,
+}
+
+impl<'a> ErrorMessage<'a> {
+    /// Creates a `ErrorMessage` instance which wraps the specified error.
+    pub fn new(err: &'a dyn fmt::Display) -> Self {
+        Self {
+            err,
+            app_name: String::new(),
+            sub_cmds: Vec::new(),
+            docs_url: String::new(),
+        }
+    }
+
+    /// Sets the application name which prefixes the message.
+    pub fn app_name(mut self, name: &str) -> Self {
+        self.app_name = name.to_string();
+        self
+    }
+
+    /// Appends a sub command name to the path which follows the application
+    /// name.
+    pub fn sub_cmd(mut self, name: &str) -> Self {
+        self.sub_cmds.push(name.to_string());
+        self
+    }
+
+    /// Sets the URL of documents which is shown on the line after the error.
+    pub fn docs_url(mut self, url: &str) -> Self {
+        self.docs_url = url.to_string();
+        self
+    }
+}
+
+impl fmt::Display for ErrorMessage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let path: Vec<&str> = std::iter::once(self.app_name.as_str())
+            .chain(self.sub_cmds.iter().map(|s| s.as_str()))
+            .filter(|s| !s.is_empty())
+            .collect();
+        if !path.is_empty() {
+            write!(f, "{}: ", path.join(" "))?;
+        }
+        write!(f, "error: {}", self.err)?;
+        if !self.docs_url.is_empty() {
+            write!(f, "\nFor more information, see {}", self.docs_url)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_of_error_message {
+    use super::*;
+    use crate::errors::{InvalidArgs, InvalidOption};
+
+    #[test]
+    fn should_format_only_error() {
+        let err = InvalidOption::UnconfiguredOption {
+            option: "foo".to_string(),
+        };
+        assert_eq!(
+            ErrorMessage::new(&err).to_string(),
+            "error: The option is not specified in configurations (option: \"foo\")"
+        );
+    }
+
+    #[test]
+    fn should_format_with_app_name_and_sub_cmds() {
+        let err = InvalidOption::OptionNeedsArg {
+            option: "retries".to_string(),
+            store_key: "retries".to_string(),
+        };
+        assert_eq!(
+            ErrorMessage::new(&err).app_name("myapp").to_string(),
+            "myapp: error: The option needs argument(s) (option: \"retries\")"
+        );
+        assert_eq!(
+            ErrorMessage::new(&err)
+                .app_name("myapp")
+                .sub_cmd("remote")
+                .sub_cmd("sync")
+                .to_string(),
+            "myapp remote sync: error: The option needs argument(s) (option: \"retries\")"
+        );
+    }
+
+    #[test]
+    fn should_format_with_docs_url() {
+        let err = InvalidArgs::SubCmdIsUnknown {
+            name: "foo".to_string(),
+            suggestions: vec![],
+        };
+        assert_eq!(
+            ErrorMessage::new(&err)
+                .app_name("myapp")
+                .docs_url("https://example.com")
+                .to_string(),
+            "myapp: error: The sub command is unknown (sub command: \"foo\")
+For more information, see https://example.com"
+        );
+    }
+}
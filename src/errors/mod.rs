@@ -3,7 +3,19 @@
 // See the file LICENSE in this distribution for more details.
 
 mod arg_err;
+mod args_err;
+mod combination_err;
+mod kind;
+mod message;
+mod opt_args_err;
 mod opt_err;
+mod spec_err;
 
 pub use arg_err::InvalidOsArg;
+pub use args_err::InvalidArgs;
+pub use combination_err::InvalidOptCombination;
+pub use kind::ErrorKind;
+pub use message::ErrorMessage;
+pub use opt_args_err::InvalidOptArgs;
 pub use opt_err::InvalidOption;
+pub use spec_err::InvalidSpec;
@@ -0,0 +1,90 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::{ErrorKind, InvalidOption};
+use std::error;
+use std::fmt;
+
+/// The error type for failures of converting multiple option arguments.
+///
+/// This error holds all failures with the indices of the invalid option
+/// arguments among the arguments of the option, instead of only the first
+/// failure.
+#[derive(Debug, PartialEq)]
+pub struct InvalidOptArgs {
+    /// The pairs of the index of an invalid option argument and the error of
+    /// it.
+    pub errors: Vec<(usize, InvalidOption)>,
+}
+
+impl InvalidOptArgs {
+    /// Returns the category of this error, which is always
+    /// `ErrorKind::Conversion`.
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::Conversion
+    }
+}
+
+impl fmt::Display for InvalidOptArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "The option arguments are invalid")?;
+        for (i, (index, err)) in self.errors.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { "; " };
+            write!(f, "{}[{}] {}", sep, index, err)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for InvalidOptArgs {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.errors
+            .first()
+            .map(|(_, err)| err as &(dyn error::Error + 'static))
+    }
+}
+
+#[cfg(test)]
+mod tests_of_invalid_opt_args {
+    use super::*;
+
+    #[test]
+    fn should_write_for_display() {
+        let err = InvalidOptArgs {
+            errors: vec![
+                (
+                    0,
+                    InvalidOption::OptionArgIsInvalid {
+                        store_key: "foo".to_string(),
+                        option: "foo".to_string(),
+                        opt_arg: "a".to_string(),
+                        details: "invalid digit found in string".to_string(),
+                    },
+                ),
+                (
+                    2,
+                    InvalidOption::OptionArgIsInvalid {
+                        store_key: "foo".to_string(),
+                        option: "foo".to_string(),
+                        opt_arg: "".to_string(),
+                        details: "cannot parse integer from empty string".to_string(),
+                    },
+                ),
+            ],
+        };
+        assert_eq!(
+            format!("{err}"),
+            "The option arguments are invalid: \
+             [0] The option argument \"a\" is invalid because: invalid digit found in string (option: \"foo\"); \
+             [2] The option argument \"\" is invalid because: cannot parse integer from empty string (option: \"foo\")"
+        );
+        assert_eq!(err.kind(), ErrorKind::Conversion);
+
+        let source = error::Error::source(&err).unwrap();
+        match source.downcast_ref::<InvalidOption>() {
+            Some(InvalidOption::OptionArgIsInvalid { opt_arg, .. }) => assert_eq!(opt_arg, "a"),
+            _ => panic!(),
+        }
+    }
+}
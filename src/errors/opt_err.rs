@@ -2,6 +2,7 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
+use super::ErrorKind;
 use std::error;
 use std::fmt;
 
@@ -36,6 +37,20 @@ pub enum InvalidOption {
         store_key: String,
     },
 
+    /// Indicates that the option requires arguments in the configuration, but
+    /// it is placed in the middle of a cluster of short options, like `f` of
+    /// `-fv`, where it cannot take the next command line argument.
+    OptionNeedsArgInCluster {
+        /// The option name that caused this error.
+        option: String,
+
+        /// The store key of the specified option in the configuration.
+        store_key: String,
+
+        /// The command line argument of the cluster, like `-fv`.
+        cluster: String,
+    },
+
     /// Indicates that the option is not suppoesed to take an argument in the
     /// configuration, but an argument is specified.
     OptionTakesNoArg {
@@ -98,6 +113,27 @@ pub enum InvalidOption {
         name: String,
     },
 
+    /// Indicates that no short option name can be assigned to an option
+    /// configuration automatically, because all letters of its long name are
+    /// already used by other options.
+    ShortNameIsNotAssignable {
+        /// The store key of the option configuration that caused this error.
+        store_key: String,
+
+        /// The long option name of the option configuration.
+        name: String,
+    },
+
+    /// Indicates that the option argument is empty though the option
+    /// configuration does not allow it.
+    OptionArgIsEmpty {
+        /// The store key of the option configuration that caused this error.
+        store_key: String,
+
+        /// The option name that caused this error.
+        option: String,
+    },
+
     /// Indicates that the option argument is invalidated by the validator
     /// in the option configuration.
     OptionArgIsInvalid {
@@ -113,23 +149,85 @@ pub enum InvalidOption {
         /// The details for the invalidation.
         details: String,
     },
+
+    /// Indicates that the same option argument is given more than once to
+    /// the option of which the configuration has `unique` flag.
+    OptionArgIsDuplicated {
+        /// The store key of the option configuration that caused this error.
+        store_key: String,
+
+        /// The option name that caused this error.
+        option: String,
+
+        /// The option argument that is duplicated.
+        opt_arg: String,
+    },
+
+    /// Indicates that the user did not confirm the option which requires a
+    /// confirmation.
+    OptionIsNotConfirmed {
+        /// The store key of the option configuration that caused this error.
+        store_key: String,
+
+        /// The option name that caused this error.
+        option: String,
+    },
+
+    /// Indicates that an option configuration was neither matched by any
+    /// command line argument nor applied with default values or environment
+    /// variables, in the strict mode of `ParseOptions`.
+    ConfigIsUnused {
+        /// The store key of the option configuration that caused this error.
+        store_key: String,
+
+        /// The first name of the option configuration.
+        name: String,
+    },
 }
 
 impl InvalidOption {
     /// Returns the name of the option that caused the error.
     pub fn option(&self) -> &str {
-        return match self {
-            InvalidOption::OptionContainsInvalidChar { option } => &option,
-            InvalidOption::UnconfiguredOption { option } => &option,
-            InvalidOption::OptionNeedsArg { option, .. } => &option,
-            InvalidOption::OptionTakesNoArg { option, .. } => &option,
-            InvalidOption::OptionIsNotArray { option, .. } => &option,
-            InvalidOption::StoreKeyIsDuplicated { name, .. } => &name,
-            InvalidOption::ConfigIsArrayButHasNoArg { name, .. } => &name,
-            InvalidOption::ConfigHasDefaultsButHasNoArg { name, .. } => &name,
-            InvalidOption::OptionNameIsDuplicated { name, .. } => &name,
-            InvalidOption::OptionArgIsInvalid { option, .. } => &option,
-        };
+        match self {
+            InvalidOption::OptionContainsInvalidChar { option } => option,
+            InvalidOption::UnconfiguredOption { option } => option,
+            InvalidOption::OptionNeedsArg { option, .. } => option,
+            InvalidOption::OptionNeedsArgInCluster { option, .. } => option,
+            InvalidOption::OptionTakesNoArg { option, .. } => option,
+            InvalidOption::OptionIsNotArray { option, .. } => option,
+            InvalidOption::StoreKeyIsDuplicated { name, .. } => name,
+            InvalidOption::ConfigIsArrayButHasNoArg { name, .. } => name,
+            InvalidOption::ConfigHasDefaultsButHasNoArg { name, .. } => name,
+            InvalidOption::OptionNameIsDuplicated { name, .. } => name,
+            InvalidOption::ShortNameIsNotAssignable { name, .. } => name,
+            InvalidOption::OptionArgIsEmpty { option, .. } => option,
+            InvalidOption::OptionArgIsInvalid { option, .. } => option,
+            InvalidOption::OptionArgIsDuplicated { option, .. } => option,
+            InvalidOption::OptionIsNotConfirmed { option, .. } => option,
+            InvalidOption::ConfigIsUnused { name, .. } => name,
+        }
+    }
+
+    /// Returns the category of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            InvalidOption::OptionContainsInvalidChar { .. } => ErrorKind::Usage,
+            InvalidOption::UnconfiguredOption { .. } => ErrorKind::Usage,
+            InvalidOption::OptionNeedsArg { .. } => ErrorKind::Usage,
+            InvalidOption::OptionNeedsArgInCluster { .. } => ErrorKind::Usage,
+            InvalidOption::OptionTakesNoArg { .. } => ErrorKind::Usage,
+            InvalidOption::OptionIsNotArray { .. } => ErrorKind::Usage,
+            InvalidOption::StoreKeyIsDuplicated { .. } => ErrorKind::Config,
+            InvalidOption::ConfigIsArrayButHasNoArg { .. } => ErrorKind::Config,
+            InvalidOption::ConfigHasDefaultsButHasNoArg { .. } => ErrorKind::Config,
+            InvalidOption::OptionNameIsDuplicated { .. } => ErrorKind::Config,
+            InvalidOption::ShortNameIsNotAssignable { .. } => ErrorKind::Config,
+            InvalidOption::OptionArgIsEmpty { .. } => ErrorKind::Usage,
+            InvalidOption::OptionArgIsInvalid { .. } => ErrorKind::Conversion,
+            InvalidOption::OptionArgIsDuplicated { .. } => ErrorKind::Usage,
+            InvalidOption::OptionIsNotConfirmed { .. } => ErrorKind::Usage,
+            InvalidOption::ConfigIsUnused { .. } => ErrorKind::Config,
+        }
     }
 }
 
@@ -151,6 +249,15 @@ impl fmt::Display for InvalidOption {
                 "The option needs argument(s) (option: \"{}\")",
                 option.escape_debug(),
             ),
+            InvalidOption::OptionNeedsArgInCluster {
+                option, cluster, ..
+            } => write!(
+                f,
+                "The option needs argument(s) but is not at the end of the cluster \
+                 (option: \"{}\", cluster: \"{}\")",
+                option.escape_debug(),
+                cluster.escape_debug(),
+            ),
             InvalidOption::OptionTakesNoArg { option, .. } => write!(
                 f,
                 "The option takes no argument (option: \"{}\")",
@@ -161,6 +268,11 @@ impl fmt::Display for InvalidOption {
                 "The option cannot have multiple arguments (option: \"{}\")",
                 option.escape_debug(),
             ),
+            InvalidOption::OptionArgIsEmpty { option, .. } => write!(
+                f,
+                "The option argument is empty (option: \"{}\")",
+                option.escape_debug(),
+            ),
             InvalidOption::OptionArgIsInvalid {
                 option,
                 opt_arg,
@@ -173,6 +285,24 @@ impl fmt::Display for InvalidOption {
                 details.escape_debug(),
                 option.escape_debug(),
             ),
+            InvalidOption::OptionArgIsDuplicated {
+                option, opt_arg, ..
+            } => write!(
+                f,
+                "The option argument \"{}\" is duplicated (option: \"{}\")",
+                opt_arg.escape_debug(),
+                option.escape_debug(),
+            ),
+            InvalidOption::OptionIsNotConfirmed { option, .. } => write!(
+                f,
+                "The option is not confirmed (option: \"{}\")",
+                option.escape_debug(),
+            ),
+            InvalidOption::ConfigIsUnused { name, .. } => write!(
+                f,
+                "The option configuration is not used (option: \"{}\")",
+                name.escape_debug(),
+            ),
             _ => write!(
                 f,
                 "The option configuration is invalid (option: \"{}\")",
@@ -197,15 +327,15 @@ mod tests_of_invalid_option {
                 option: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => assert_eq!(err.option(), "foo-bar"),
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionContainsInvalidChar { option }) => {
                     assert_eq!(option, "foo-bar");
                 }
-                _ => assert!(false),
+                _ => panic!(),
             }
         }
 
@@ -215,7 +345,7 @@ mod tests_of_invalid_option {
                 option: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     //println!("{err}");
                     assert_eq!(
@@ -232,7 +362,7 @@ mod tests_of_invalid_option {
                 option: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     //println!("{err}");
                     assert_eq!(
@@ -255,7 +385,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     //println!("{err}");
                     //println!("{err:?}");
@@ -265,10 +395,10 @@ mod tests_of_invalid_option {
                             InvalidOption::OptionContainsInvalidChar { option } => {
                                 assert_eq!(*option, "b@z");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
@@ -284,15 +414,15 @@ mod tests_of_invalid_option {
                 option: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => assert_eq!(err.option(), "foo-bar"),
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::UnconfiguredOption { option }) => {
                     assert_eq!(option, "foo-bar");
                 }
-                _ => assert!(false),
+                _ => panic!(),
             }
         }
 
@@ -302,7 +432,7 @@ mod tests_of_invalid_option {
                 option: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     println!("{err}");
                     assert_eq!(
@@ -319,7 +449,7 @@ mod tests_of_invalid_option {
                 option: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     //println!("{err}");
                     assert_eq!(
@@ -342,7 +472,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     println!("{err}");
                     println!("{err:?}");
@@ -352,10 +482,10 @@ mod tests_of_invalid_option {
                             InvalidOption::OptionContainsInvalidChar { option } => {
                                 assert_eq!(*option, "b@z");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
@@ -372,16 +502,16 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => assert_eq!(err.option(), "foo-bar"),
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionNeedsArg { option, store_key }) => {
                     assert_eq!(option, "foo-bar");
                     assert_eq!(store_key, "fooBar");
                 }
-                _ => assert!(false),
+                _ => panic!(),
             }
         }
 
@@ -392,7 +522,7 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     println!("{err}");
                     assert_eq!(
@@ -410,7 +540,7 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     //println!("{err}");
                     assert_eq!(
@@ -434,7 +564,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     println!("{err}");
                     println!("{err:?}");
@@ -445,16 +575,63 @@ mod tests_of_invalid_option {
                                 assert_eq!(*option, "b@z");
                                 assert_eq!(*store_key, "BAZ");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
         }
     }
 
+    mod tests_of_option_needs_arg_in_cluster {
+        use super::*;
+
+        #[test]
+        fn should_create_and_handle() {
+            let result: Result<(), InvalidOption> = Err(InvalidOption::OptionNeedsArgInCluster {
+                option: "f".to_string(),
+                store_key: "file".to_string(),
+                cluster: "-fv".to_string(),
+            });
+            match result {
+                Ok(_) => panic!(),
+                Err(ref err) => {
+                    assert_eq!(err.option(), "f");
+                    assert_eq!(err.kind(), ErrorKind::Usage);
+                }
+            }
+            match result {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionNeedsArgInCluster {
+                    option,
+                    store_key,
+                    cluster,
+                }) => {
+                    assert_eq!(option, "f");
+                    assert_eq!(store_key, "file");
+                    assert_eq!(cluster, "-fv");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_write_for_display() {
+            let err = InvalidOption::OptionNeedsArgInCluster {
+                option: "f".to_string(),
+                store_key: "file".to_string(),
+                cluster: "-fv".to_string(),
+            };
+            assert_eq!(
+                format!("{err}"),
+                "The option needs argument(s) but is not at the end of the cluster \
+                 (option: \"f\", cluster: \"-fv\")",
+            );
+        }
+    }
+
     mod tests_of_option_takes_no_arg {
         use super::*;
 
@@ -465,16 +642,16 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => assert_eq!(err.option(), "foo-bar"),
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionTakesNoArg { option, store_key }) => {
                     assert_eq!(option, "foo-bar");
                     assert_eq!(store_key, "fooBar");
                 }
-                _ => assert!(false),
+                _ => panic!(),
             }
         }
 
@@ -485,7 +662,7 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     println!("{err}");
                     assert_eq!(
@@ -503,7 +680,7 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     //println!("{err}");
                     assert_eq!(
@@ -527,7 +704,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     println!("{err}");
                     println!("{err:?}");
@@ -538,10 +715,10 @@ mod tests_of_invalid_option {
                                 assert_eq!(*option, "b@z");
                                 assert_eq!(*store_key, "BAZ");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
@@ -558,16 +735,16 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => assert_eq!(err.option(), "foo-bar"),
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionIsNotArray { option, store_key }) => {
                     assert_eq!(option, "foo-bar");
                     assert_eq!(store_key, "fooBar");
                 }
-                _ => assert!(false),
+                _ => panic!(),
             }
         }
 
@@ -578,7 +755,7 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     println!("{err}");
                     assert_eq!(
@@ -596,7 +773,7 @@ mod tests_of_invalid_option {
                 store_key: "fooBar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     //println!("{err}");
                     assert_eq!(
@@ -620,7 +797,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     println!("{err}");
                     println!("{err:?}");
@@ -631,10 +808,10 @@ mod tests_of_invalid_option {
                                 assert_eq!(*option, "b@z");
                                 assert_eq!(*store_key, "BAZ");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
@@ -651,16 +828,16 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => assert_eq!(err.option(), "foo-bar"),
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::StoreKeyIsDuplicated { store_key, name }) => {
                     assert_eq!(store_key, "fooBar");
                     assert_eq!(name, "foo-bar");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -671,7 +848,7 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err:?}"),
@@ -688,7 +865,7 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err}"),
@@ -711,7 +888,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     if let Some(opt_err) = err.downcast_ref::<InvalidOption>() {
                         assert_eq!(opt_err.option(), "foo-bar");
@@ -720,10 +897,10 @@ mod tests_of_invalid_option {
                                 assert_eq!(*store_key, "fooBar");
                                 assert_eq!(*name, "foo-bar");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
@@ -740,16 +917,16 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => assert_eq!(err.option(), "foo-bar"),
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::ConfigIsArrayButHasNoArg { store_key, name }) => {
                     assert_eq!(store_key, "fooBar");
                     assert_eq!(name, "foo-bar");
                 }
-                _ => assert!(false),
+                _ => panic!(),
             }
         }
 
@@ -760,7 +937,7 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err:?}"),
@@ -777,7 +954,7 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err}"),
@@ -800,7 +977,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     if let Some(opt_err) = err.downcast_ref::<InvalidOption>() {
                         assert_eq!(opt_err.option(), "foo-bar");
@@ -809,10 +986,10 @@ mod tests_of_invalid_option {
                                 assert_eq!(*store_key, "fooBar");
                                 assert_eq!(*name, "foo-bar");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
@@ -830,18 +1007,18 @@ mod tests_of_invalid_option {
                     name: "foo-bar".to_string(),
                 });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => {
                     assert_eq!(err.option(), "foo-bar");
                 }
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::ConfigHasDefaultsButHasNoArg { store_key, name }) => {
                     assert_eq!(store_key, "fooBar");
                     assert_eq!(name, "foo-bar");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -853,7 +1030,7 @@ mod tests_of_invalid_option {
                     name: "foo-bar".to_string(),
                 });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err:?}"),
@@ -871,7 +1048,7 @@ mod tests_of_invalid_option {
                     name: "foo-bar".to_string(),
                 });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err}"),
@@ -894,7 +1071,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     if let Some(opt_err) = err.downcast_ref::<InvalidOption>() {
                         assert_eq!(opt_err.option(), "foo-bar");
@@ -903,10 +1080,10 @@ mod tests_of_invalid_option {
                                 assert_eq!(*store_key, "fooBar");
                                 assert_eq!(*name, "foo-bar");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
@@ -923,18 +1100,18 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => {
                     assert_eq!(err.option(), "foo-bar");
                 }
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionNameIsDuplicated { store_key, name }) => {
                     assert_eq!(store_key, "fooBar");
                     assert_eq!(name, "foo-bar");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -945,7 +1122,7 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err:?}"),
@@ -962,7 +1139,7 @@ mod tests_of_invalid_option {
                 name: "foo-bar".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err}"),
@@ -985,7 +1162,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     if let Some(opt_err) = err.downcast_ref::<InvalidOption>() {
                         assert_eq!(opt_err.option(), "foo-bar");
@@ -994,16 +1171,64 @@ mod tests_of_invalid_option {
                                 assert_eq!(*store_key, "fooBar");
                                 assert_eq!(*name, "foo-bar");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
         }
     }
 
+    mod short_name_is_not_assignable {
+        use super::*;
+
+        #[test]
+        fn should_create_and_handle() {
+            let result: Result<(), InvalidOption> = Err(InvalidOption::ShortNameIsNotAssignable {
+                store_key: "fooBar".to_string(),
+                name: "foo-bar".to_string(),
+            });
+            match result {
+                Ok(_) => panic!(),
+                Err(ref err) => assert_eq!(err.option(), "foo-bar"),
+            }
+            match result {
+                Ok(_) => panic!(),
+                Err(InvalidOption::ShortNameIsNotAssignable { store_key, name }) => {
+                    assert_eq!(store_key, "fooBar");
+                    assert_eq!(name, "foo-bar");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_write_for_debug() {
+            let err = InvalidOption::ShortNameIsNotAssignable {
+                store_key: "fooBar".to_string(),
+                name: "foo-bar".to_string(),
+            };
+            assert_eq!(
+                format!("{err:?}"),
+                "ShortNameIsNotAssignable { store_key: \"fooBar\", name: \"foo-bar\" }",
+            );
+        }
+
+        #[test]
+        fn should_write_for_display() {
+            let err = InvalidOption::ShortNameIsNotAssignable {
+                store_key: "fooBar".to_string(),
+                name: "foo-bar".to_string(),
+            };
+            assert_eq!(
+                format!("{err}"),
+                "The option configuration is invalid (option: \"foo-bar\")",
+            );
+        }
+    }
+
     mod option_arg_is_invalid {
         use super::*;
 
@@ -1016,13 +1241,13 @@ mod tests_of_invalid_option {
                 details: "illegal number format.".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(ref err) => {
                     assert_eq!(err.option(), "foo-bar");
                 }
             }
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -1034,7 +1259,7 @@ mod tests_of_invalid_option {
                     assert_eq!(opt_arg, "x123");
                     assert_eq!(details, "illegal number format.");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -1047,7 +1272,7 @@ mod tests_of_invalid_option {
                 details: "illegal number format.".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err:?}"),
@@ -1066,7 +1291,7 @@ mod tests_of_invalid_option {
                 details: "illegal number format.".to_string(),
             });
             match result {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     assert_eq!(
                         format!("{err}"),
@@ -1091,7 +1316,7 @@ mod tests_of_invalid_option {
                 Ok(())
             }
             match returns_dyn_error() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(err) => {
                     if let Some(opt_err) = err.downcast_ref::<InvalidOption>() {
                         assert_eq!(opt_err.option(), "foo-bar");
@@ -1107,13 +1332,176 @@ mod tests_of_invalid_option {
                                 assert_eq!(*opt_arg, "x123");
                                 assert_eq!(*details, "illegal number format.");
                             }
-                            _ => assert!(false),
+                            _ => panic!(),
                         }
                     } else {
-                        assert!(false);
+                        panic!();
                     }
                 }
             }
         }
     }
+
+    mod option_arg_is_empty {
+        use super::*;
+
+        #[test]
+        fn should_create_and_handle() {
+            let result: Result<(), InvalidOption> = Err(InvalidOption::OptionArgIsEmpty {
+                store_key: "fooBar".to_string(),
+                option: "foo-bar".to_string(),
+            });
+            match result {
+                Ok(_) => panic!(),
+                Err(ref err) => {
+                    assert_eq!(err.option(), "foo-bar");
+                    assert_eq!(err.kind(), ErrorKind::Usage);
+                }
+            }
+            match result {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsEmpty { store_key, option }) => {
+                    assert_eq!(store_key, "fooBar");
+                    assert_eq!(option, "foo-bar");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_write_for_display() {
+            let err = InvalidOption::OptionArgIsEmpty {
+                store_key: "fooBar".to_string(),
+                option: "foo-bar".to_string(),
+            };
+            assert_eq!(
+                format!("{err}"),
+                "The option argument is empty (option: \"foo-bar\")",
+            );
+        }
+    }
+
+    mod option_arg_is_duplicated {
+        use super::*;
+
+        #[test]
+        fn should_create_and_handle() {
+            let result: Result<(), InvalidOption> = Err(InvalidOption::OptionArgIsDuplicated {
+                store_key: "feature".to_string(),
+                option: "F".to_string(),
+                opt_arg: "tls".to_string(),
+            });
+            match result {
+                Ok(_) => panic!(),
+                Err(ref err) => {
+                    assert_eq!(err.option(), "F");
+                    assert_eq!(err.kind(), ErrorKind::Usage);
+                }
+            }
+            match result {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsDuplicated {
+                    store_key,
+                    option,
+                    opt_arg,
+                }) => {
+                    assert_eq!(store_key, "feature");
+                    assert_eq!(option, "F");
+                    assert_eq!(opt_arg, "tls");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_write_for_display() {
+            let err = InvalidOption::OptionArgIsDuplicated {
+                store_key: "feature".to_string(),
+                option: "feature".to_string(),
+                opt_arg: "tls".to_string(),
+            };
+            assert_eq!(
+                format!("{err}"),
+                "The option argument \"tls\" is duplicated (option: \"feature\")",
+            );
+        }
+    }
+
+    mod option_is_not_confirmed {
+        use super::*;
+
+        #[test]
+        fn should_create_and_handle() {
+            let result: Result<(), InvalidOption> = Err(InvalidOption::OptionIsNotConfirmed {
+                store_key: "force".to_string(),
+                option: "f".to_string(),
+            });
+            match result {
+                Ok(_) => panic!(),
+                Err(ref err) => {
+                    assert_eq!(err.option(), "f");
+                    assert_eq!(err.kind(), ErrorKind::Usage);
+                }
+            }
+            match result {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionIsNotConfirmed { store_key, option }) => {
+                    assert_eq!(store_key, "force");
+                    assert_eq!(option, "f");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_write_for_display() {
+            let err = InvalidOption::OptionIsNotConfirmed {
+                store_key: "force".to_string(),
+                option: "force".to_string(),
+            };
+            assert_eq!(
+                format!("{err}"),
+                "The option is not confirmed (option: \"force\")",
+            );
+        }
+    }
+
+    mod config_is_unused {
+        use super::*;
+
+        #[test]
+        fn should_create_and_handle() {
+            let result: Result<(), InvalidOption> = Err(InvalidOption::ConfigIsUnused {
+                store_key: "fooBar".to_string(),
+                name: "foo-bar".to_string(),
+            });
+            match result {
+                Ok(_) => panic!(),
+                Err(ref err) => {
+                    assert_eq!(err.option(), "foo-bar");
+                    assert_eq!(err.kind(), ErrorKind::Config);
+                }
+            }
+            match result {
+                Ok(_) => panic!(),
+                Err(InvalidOption::ConfigIsUnused { store_key, name }) => {
+                    assert_eq!(store_key, "fooBar");
+                    assert_eq!(name, "foo-bar");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_write_for_display() {
+            let err = InvalidOption::ConfigIsUnused {
+                store_key: "fooBar".to_string(),
+                name: "foo-bar".to_string(),
+            };
+            assert_eq!(
+                format!("{err}"),
+                "The option configuration is not used (option: \"foo-bar\")",
+            );
+        }
+    }
 }
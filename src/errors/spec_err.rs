@@ -0,0 +1,159 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::ErrorKind;
+use std::error;
+use std::fmt;
+
+/// The enum type for errors of a machine-readable command description.
+///
+/// The variants of this enum indicates errors that can occur when reading a
+/// JSON text which describes a command and its option configurations.
+#[derive(Debug, PartialEq)]
+pub enum InvalidSpec {
+    /// Indicates that the text is not a valid JSON text.
+    JsonIsMalformed {
+        /// The byte position in the text where this error occurred.
+        position: usize,
+
+        /// The details of this error.
+        details: String,
+    },
+
+    /// Indicates that a field in the JSON text has a value of an unexpected
+    /// type.
+    FieldTypeIsInvalid {
+        /// The path of the field, like `options[0].names`.
+        field: String,
+
+        /// The expected type of the field value.
+        expected: String,
+    },
+}
+
+impl InvalidSpec {
+    /// Returns the category of this error, which is always
+    /// `ErrorKind::Config`.
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::Config
+    }
+}
+
+impl fmt::Display for InvalidSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            InvalidSpec::JsonIsMalformed { position, details } => write!(
+                f,
+                "The spec is not a valid JSON because: {} (position: {})",
+                details.escape_debug(),
+                position,
+            ),
+            InvalidSpec::FieldTypeIsInvalid { field, expected } => write!(
+                f,
+                "The spec field is not {} (field: \"{}\")",
+                expected,
+                field.escape_debug(),
+            ),
+        }
+    }
+}
+
+impl error::Error for InvalidSpec {}
+
+#[cfg(test)]
+mod tests_of_invalid_spec {
+    use super::*;
+
+    mod tests_of_json_is_malformed {
+        use super::*;
+
+        #[test]
+        fn should_create_and_handle() {
+            let result: Result<(), InvalidSpec> = Err(InvalidSpec::JsonIsMalformed {
+                position: 3,
+                details: "unexpected character".to_string(),
+            });
+            match result {
+                Ok(_) => panic!(),
+                Err(InvalidSpec::JsonIsMalformed { position, details }) => {
+                    assert_eq!(position, 3);
+                    assert_eq!(details, "unexpected character");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_write_for_debug_and_display() {
+            let err = InvalidSpec::JsonIsMalformed {
+                position: 3,
+                details: "unexpected character".to_string(),
+            };
+            assert_eq!(
+                format!("{err:?}"),
+                "JsonIsMalformed { position: 3, details: \"unexpected character\" }"
+            );
+            assert_eq!(
+                format!("{err}"),
+                "The spec is not a valid JSON because: unexpected character (position: 3)"
+            );
+        }
+
+        #[test]
+        fn should_handle_as_dyn_std_error() {
+            fn returns_dyn_error() -> Result<(), Box<dyn error::Error>> {
+                Err(InvalidSpec::JsonIsMalformed {
+                    position: 3,
+                    details: "unexpected character".to_string(),
+                })?;
+                Ok(())
+            }
+            match returns_dyn_error() {
+                Ok(_) => panic!(),
+                Err(err) => match err.downcast_ref::<InvalidSpec>() {
+                    Some(InvalidSpec::JsonIsMalformed { position, .. }) => {
+                        assert_eq!(*position, 3);
+                    }
+                    _ => panic!(),
+                },
+            }
+        }
+    }
+
+    mod tests_of_field_type_is_invalid {
+        use super::*;
+
+        #[test]
+        fn should_create_and_handle() {
+            let result: Result<(), InvalidSpec> = Err(InvalidSpec::FieldTypeIsInvalid {
+                field: "options[0].names".to_string(),
+                expected: "an array of strings".to_string(),
+            });
+            match result {
+                Ok(_) => panic!(),
+                Err(InvalidSpec::FieldTypeIsInvalid { field, expected }) => {
+                    assert_eq!(field, "options[0].names");
+                    assert_eq!(expected, "an array of strings");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_write_for_debug_and_display() {
+            let err = InvalidSpec::FieldTypeIsInvalid {
+                field: "options[0].names".to_string(),
+                expected: "an array of strings".to_string(),
+            };
+            assert_eq!(
+                format!("{err:?}"),
+                "FieldTypeIsInvalid { field: \"options[0].names\", expected: \"an array of strings\" }"
+            );
+            assert_eq!(
+                format!("{err}"),
+                "The spec field is not an array of strings (field: \"options[0].names\")"
+            );
+        }
+    }
+}
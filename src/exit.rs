@@ -0,0 +1,91 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::app::error_message;
+use std::env;
+use std::fmt;
+use std::path::Path;
+use std::process;
+
+/// The exit status which indicates that a command succeeded.
+pub const OK: i32 = 0;
+
+/// The exit status which indicates that command line arguments are wrong.
+///
+/// This is the status with which `Cmd#parse_with_or_exit` and
+/// `App#run_or_exit` exit on an error.
+pub const USAGE: i32 = 2;
+
+/// The exit status which indicates that a configuration, like a config file
+/// or option configurations, is wrong.
+///
+/// This is the same as `EX_CONFIG` of `sysexits.h`.
+pub const CONFIG: i32 = 78;
+
+/// The exit status which indicates that an internal error, like a bug,
+/// occurred.
+///
+/// This is the same as `EX_SOFTWARE` of `sysexits.h`.
+pub const INTERNAL: i32 = 70;
+
+/// Prints the specified error with a usage hint to stderr, and exits the
+/// process with the status `USAGE`.
+///
+/// The message is the same as one printed by `Cmd#parse_with_or_exit`, and
+/// the command name in it is the file name of the running executable.
+///
+/// ```no_run
+/// use cliargs::Cmd;
+/// use cliargs::exit;
+///
+/// let mut cmd = Cmd::new().unwrap();
+/// if let Err(err) = cmd.parse() {
+///     exit::usage_error(err);
+/// }
+/// ```
+pub fn usage_error(err: impl fmt::Display) -> ! {
+    eprint!("{}", usage_error_message(&program_name(), &err));
+    process::exit(USAGE);
+}
+
+fn usage_error_message(cmd_name: &str, err: &dyn fmt::Display) -> String {
+    error_message(cmd_name, &format!("{} [OPTIONS]", cmd_name), err)
+}
+
+fn program_name() -> String {
+    env::args_os()
+        .next()
+        .and_then(|arg0| {
+            Path::new(&arg0)
+                .file_name()
+                .map(|s| s.to_string_lossy().into_owned())
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests_of_exit {
+    use super::*;
+    use crate::errors::InvalidOption;
+
+    #[test]
+    fn should_make_usage_error_message() {
+        let err = InvalidOption::UnconfiguredOption {
+            option: "foo".to_string(),
+        };
+        assert_eq!(
+            usage_error_message("app", &err),
+            "app: error: The option is not specified in configurations (option: \"foo\")\n\
+             \n\
+             Usage: app [OPTIONS]\n\
+             Try 'app --help' for more information.\n"
+        );
+    }
+
+    #[test]
+    fn should_get_program_name() {
+        assert!(!program_name().is_empty());
+        assert!(!program_name().contains('/'));
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::completion;
+use crate::spec;
+use crate::Cmd;
+use crate::OptCfg;
+use crate::OptCfgParam::{defaults, has_arg, is_array, names, store_key};
+
+/// Drives the parsers of this crate with arbitrary input.
+///
+/// The input is split into command line arguments at NUL bytes, and the
+/// arguments are parsed with `Cmd#parse` and `Cmd#parse_with`.
+/// The whole input is also passed to `completion::complete` and
+/// `spec::from_json`.
+/// Errors are ignored because only panics are of interest for fuzzing.
+///
+/// ```
+/// cliargs::fuzz::parse_bytes(b"app\0-a\xc3\xa9b\0--foo=1");
+/// ```
+pub fn parse_bytes(data: &[u8]) {
+    let args: Vec<String> = data
+        .split(|b| *b == 0)
+        .map(|b| String::from_utf8_lossy(b).into_owned())
+        .collect();
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let osargs = data
+            .split(|b| *b == 0)
+            .map(|b| OsString::from_vec(b.to_vec()));
+        if let Ok(mut cmd) = Cmd::with_os_strings(osargs) {
+            let _ = cmd.parse();
+        }
+    }
+
+    let mut cmd = Cmd::with_strings(args.clone());
+    let _ = cmd.parse();
+
+    let opt_cfgs = fuzz_opt_cfgs();
+    let mut cmd = Cmd::with_strings(args.clone());
+    let _ = cmd.parse_with(&opt_cfgs);
+
+    let line = args.join(" ");
+    let _ = completion::complete(&opt_cfgs, &line, data.len());
+    let _ = completion::complete(&opt_cfgs, &line, data.len() / 2);
+
+    let _ = spec::from_json(&String::from_utf8_lossy(data));
+}
+
+fn fuzz_opt_cfgs() -> Vec<OptCfg> {
+    vec![
+        OptCfg::with(&[names(&["foo", "f"])]),
+        OptCfg::with(&[names(&["bar", "b"]), has_arg(true)]),
+        OptCfg::with(&[
+            names(&["baz", "z"]),
+            has_arg(true),
+            is_array(true),
+            defaults(&["1", "2"]),
+        ]),
+        OptCfg::with(&[store_key("*")]),
+    ]
+}
+
+#[cfg(test)]
+mod tests_of_fuzz {
+    use super::*;
+
+    #[test]
+    fn should_not_panic_with_adversarial_inputs() {
+        let inputs: &[&[u8]] = &[
+            b"",
+            b"\0",
+            b"app\0-\xc3\xa9",
+            b"app\0-a\xc3\xa9b=\xe6\x97\xa5",
+            b"app\0--\xc3\xa9=1",
+            b"app\0-b",
+            b"app\0-b=\0--",
+            b"app\0-\xff\xfe",
+            b"app\0--=\0-=\0---\0-",
+            b"app\0--baz=\0-zz=1",
+            b"{\"name\":\"\xc3\xa9\",\"options\":[{\"names\":[1]}]",
+            b"[[[[[[[[[[[[[[[[[[[[",
+            b"\"\\u12",
+        ];
+        for input in inputs {
+            parse_bytes(input);
+        }
+    }
+}
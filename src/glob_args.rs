@@ -0,0 +1,133 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::errors::InvalidArgs;
+use crate::Cmd;
+
+/// Enumerates the policies for glob patterns in command arguments which match
+/// no path, used by `Cmd#expand_glob_args`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoMatchPolicy {
+    /// Keeps the pattern as it is, like POSIX shells do by default.
+    Keep,
+
+    /// Removes the pattern.
+    Skip,
+
+    /// Returns `InvalidArgs::GlobPatternMatchesNothing`.
+    Error,
+}
+
+impl<'a> Cmd<'a> {
+    /// Returns the command arguments of which glob patterns are expanded to
+    /// the matched paths.
+    ///
+    /// This method is useful on platforms where the shell does not expand
+    /// glob patterns, like Windows cmd, e.g. calling this only if
+    /// `cfg!(windows)`.
+    /// Only command arguments containing `*`, `?`, or `[` are treated as
+    /// glob patterns, and the matched paths of each pattern are sorted in
+    /// alphabetical order.
+    /// A pattern which matches no path is treated according to `policy`.
+    /// If a command argument is invalid as a glob pattern, this method returns
+    /// `InvalidArgs::GlobPatternIsInvalid`.
+    ///
+    /// This method is available when the `glob` feature is enabled.
+    pub fn expand_glob_args(&self, policy: NoMatchPolicy) -> Result<Vec<String>, InvalidArgs> {
+        let mut expanded = Vec::with_capacity(self.args.len());
+
+        for arg in self.args.iter() {
+            if !arg.contains(['*', '?', '[']) {
+                expanded.push(arg.to_string());
+                continue;
+            }
+
+            let paths = glob::glob(arg).map_err(|e| InvalidArgs::GlobPatternIsInvalid {
+                pattern: arg.to_string(),
+                details: e.msg.to_string(),
+            })?;
+
+            let len = expanded.len();
+            for path in paths.flatten() {
+                expanded.push(path.to_string_lossy().into_owned());
+            }
+            if expanded.len() == len {
+                match policy {
+                    NoMatchPolicy::Keep => expanded.push(arg.to_string()),
+                    NoMatchPolicy::Skip => {}
+                    NoMatchPolicy::Error => {
+                        return Err(InvalidArgs::GlobPatternMatchesNothing {
+                            pattern: arg.to_string(),
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(expanded)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_expand_glob_args {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_expand_glob_patterns() {
+        let dir = env::temp_dir().join("cliargs_test_3950");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["b.txt", "a.txt", "c.md"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let d = dir.to_string_lossy().into_owned();
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            format!("{d}/*.txt"),
+            "plain".to_string(),
+            format!("{d}/*.rs"),
+        ]);
+        cmd.parse().unwrap();
+
+        assert_eq!(
+            cmd.expand_glob_args(NoMatchPolicy::Keep),
+            Ok(vec![
+                format!("{d}/a.txt"),
+                format!("{d}/b.txt"),
+                "plain".to_string(),
+                format!("{d}/*.rs"),
+            ])
+        );
+        assert_eq!(
+            cmd.expand_glob_args(NoMatchPolicy::Skip),
+            Ok(vec![
+                format!("{d}/a.txt"),
+                format!("{d}/b.txt"),
+                "plain".to_string(),
+            ])
+        );
+        assert_eq!(
+            cmd.expand_glob_args(NoMatchPolicy::Error),
+            Err(InvalidArgs::GlobPatternMatchesNothing {
+                pattern: format!("{d}/*.rs"),
+            })
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_fail_if_pattern_is_invalid() {
+        let mut cmd = Cmd::with_strings(["app", "[a"].map(String::from));
+        cmd.parse().unwrap();
+
+        match cmd.expand_glob_args(NoMatchPolicy::Keep) {
+            Err(InvalidArgs::GlobPatternIsInvalid { pattern, .. }) => assert_eq!(pattern, "[a"),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::interpolate_env;
+use crate::Cmd;
+use crate::OptCfg;
+use std::collections::HashMap;
+
+/// Merges option values provided by multiple sources in priority order.
+///
+/// Each layer is a set of option values from one source, like defaults, a
+/// config file, environment variables, or command line arguments, and is
+/// registered with the name of the source.
+/// Layers are registered from the lowest priority to the highest priority,
+/// so the value of a layer registered later overrides the values of the same
+/// store key in layers registered earlier.
+///
+/// ```rust
+/// use cliargs::{Cmd, Layers, OptCfg};
+/// use cliargs::OptCfgParam::{names, has_arg, defaults};
+///
+/// let opt_cfgs = vec![
+///     OptCfg::with(&[names(&["foo"]), has_arg(true), defaults(&["1"])]),
+///     OptCfg::with(&[names(&["bar"]), has_arg(true), defaults(&["2"])]),
+/// ];
+///
+/// let mut cmd = Cmd::with_strings(["app".to_string(), "--bar=3".to_string()]);
+/// cmd.parse_with(&opt_cfgs).unwrap();
+///
+/// let layers = Layers::new()
+///     .defaults("default", &opt_cfgs)
+///     .layer("config", [("foo", vec!["4"])])
+///     .cmd("cli", &cmd);
+///
+/// assert_eq!(layers.opt_arg("foo"), Some("4"));
+/// assert_eq!(layers.source_of("foo"), Some("config"));
+/// assert_eq!(layers.opt_arg("bar"), Some("3"));
+/// assert_eq!(layers.source_of("bar"), Some("cli"));
+/// ```
+#[derive(Debug, Default)]
+pub struct Layers {
+    layers: Vec<Layer>,
+}
+
+#[derive(Debug)]
+struct Layer {
+    source: String,
+    values: HashMap<String, Vec<String>>,
+}
+
+impl Layers {
+    /// Creates a `Layers` instance which has no layer.
+    pub fn new() -> Layers {
+        Layers { layers: Vec::new() }
+    }
+
+    /// Adds a layer which has the specified pairs of a store key and option
+    /// values, with the name of the source.
+    pub fn layer<K, V, S>(mut self, source: &str, values: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<str>,
+        V: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let values = values
+            .into_iter()
+            .map(|(k, v)| {
+                let vec = v.into_iter().map(|s| s.as_ref().to_string()).collect();
+                (k.as_ref().to_string(), vec)
+            })
+            .collect();
+
+        self.layers.push(Layer {
+            source: source.to_string(),
+            values,
+        });
+        self
+    }
+
+    /// Adds a layer which has the default values of the specified option
+    /// configurations, with the name of the source.
+    ///
+    /// The option configurations which have no default values are ignored.
+    /// If `defaults` is not set, the values computed by `default_fn` are used.
+    /// Environment variables in the values are expanded if
+    /// `interpolates_defaults` is `true`, in the same way as `Cmd#parse_with`.
+    pub fn defaults(self, source: &str, opt_cfgs: &[OptCfg]) -> Self {
+        let values = opt_cfgs.iter().filter_map(|cfg| {
+            let store_key = if cfg.store_key.is_empty() {
+                cfg.names.first()?
+            } else {
+                &cfg.store_key
+            };
+            cfg.defaults
+                .clone()
+                .or_else(|| cfg.default_fn.map(|f| f()))
+                .map(|vec| {
+                    if cfg.interpolates_defaults {
+                        (store_key, vec.iter().map(|s| interpolate_env(s)).collect())
+                    } else {
+                        (store_key, vec)
+                    }
+                })
+        });
+        self.layer(source, values)
+    }
+
+    /// Adds a layer which has the options stored in the specified `Cmd`
+    /// instance, with the name of the source.
+    ///
+    /// The options to which `Cmd#parse_with` applied default values are not
+    /// included in this layer, because they were not specified in command
+    /// line arguments.
+    pub fn cmd(self, source: &str, cmd: &Cmd<'_>) -> Self {
+        let values: Vec<(&str, &Vec<&str>)> = cmd
+            .opts
+            .iter()
+            .filter(|(k, _)| !cmd.defaulted.contains(k))
+            .map(|(k, v)| (*k, v))
+            .collect();
+        self.layer(source, values)
+    }
+
+    /// Checks whether an option with the specified store key exists in any
+    /// layer.
+    pub fn has_opt(&self, store_key: &str) -> bool {
+        self.find(store_key).is_some()
+    }
+
+    /// Returns the first option argument of the specified store key in the
+    /// layer of the highest priority which has the key.
+    pub fn opt_arg(&self, store_key: &str) -> Option<&str> {
+        match self.find(store_key) {
+            Some((_, vec)) => vec.first().map(|s| s.as_str()),
+            None => None,
+        }
+    }
+
+    /// Returns the option arguments of the specified store key in the layer of
+    /// the highest priority which has the key.
+    pub fn opt_args(&self, store_key: &str) -> Option<&[String]> {
+        self.find(store_key).map(|(_, vec)| vec.as_slice())
+    }
+
+    /// Returns the name of the source of the layer which provides the values
+    /// of the specified store key.
+    pub fn source_of(&self, store_key: &str) -> Option<&str> {
+        self.find(store_key).map(|(source, _)| source)
+    }
+
+    /// Returns the store keys in all layers, sorted in lexicographical order.
+    pub fn store_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = Vec::new();
+        for layer in self.layers.iter() {
+            for key in layer.values.keys() {
+                keys.push(key);
+            }
+        }
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    /// Renders the effective configuration as a table which has the columns
+    /// of store keys, values, and sources, for a flag like `--show-config`.
+    ///
+    /// The rows are sorted by store keys.
+    /// Multiple values are joined with `, `, and an option without values is
+    /// shown as `(no value)`.
+    ///
+    /// ```rust
+    /// use cliargs::Layers;
+    ///
+    /// let layers = Layers::new()
+    ///     .layer("default", [("port", vec!["80"]), ("host", vec!["localhost"])])
+    ///     .layer("env", [("port", vec!["8080"])])
+    ///     .layer("cli", [("verbose", Vec::<&str>::new())]);
+    ///
+    /// assert_eq!(layers.effective_config(), "\
+    /// KEY      VALUE       SOURCE
+    /// host     localhost   default
+    /// port     8080        env
+    /// verbose  (no value)  cli
+    /// ");
+    /// ```
+    pub fn effective_config(&self) -> String {
+        let mut rows = vec![("KEY".to_string(), "VALUE".to_string(), "SOURCE")];
+        for key in self.store_keys() {
+            if let Some((source, vec)) = self.find(key) {
+                let value = if vec.is_empty() {
+                    "(no value)".to_string()
+                } else {
+                    vec.join(", ")
+                };
+                rows.push((key.to_string(), value, source));
+            }
+        }
+
+        let key_width = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0);
+        let value_width = rows.iter().map(|r| r.1.chars().count()).max().unwrap_or(0);
+
+        let mut text = String::new();
+        for (key, value, source) in rows {
+            text.push_str(&format!(
+                "{:key_width$}  {:value_width$}  {}\n",
+                key, value, source
+            ));
+        }
+        text
+    }
+
+    fn find(&self, store_key: &str) -> Option<(&str, &Vec<String>)> {
+        for layer in self.layers.iter().rev() {
+            if let Some(vec) = layer.values.get(store_key) {
+                return Some((&layer.source, vec));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests_of_layers {
+    use super::*;
+    use crate::OptCfgParam::*;
+
+    #[test]
+    fn should_be_empty_if_no_layer() {
+        let layers = Layers::new();
+
+        assert!(!layers.has_opt("foo"));
+        assert_eq!(layers.opt_arg("foo"), None);
+        assert_eq!(layers.opt_args("foo"), None);
+        assert_eq!(layers.source_of("foo"), None);
+        assert_eq!(layers.store_keys(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn should_override_values_by_layers_added_later() {
+        let layers = Layers::new()
+            .layer("first", [("foo", vec!["1"]), ("bar", vec!["2", "3"])])
+            .layer("second", [("bar", vec!["4"]), ("baz", vec![])]);
+
+        assert!(layers.has_opt("foo"));
+        assert_eq!(layers.opt_arg("foo"), Some("1"));
+        assert_eq!(
+            layers.opt_args("foo"),
+            Some(&["1".to_string()] as &[String])
+        );
+        assert_eq!(layers.source_of("foo"), Some("first"));
+
+        assert!(layers.has_opt("bar"));
+        assert_eq!(layers.opt_arg("bar"), Some("4"));
+        assert_eq!(
+            layers.opt_args("bar"),
+            Some(&["4".to_string()] as &[String])
+        );
+        assert_eq!(layers.source_of("bar"), Some("second"));
+
+        assert!(layers.has_opt("baz"));
+        assert_eq!(layers.opt_arg("baz"), None);
+        assert_eq!(layers.opt_args("baz"), Some(&[] as &[String]));
+        assert_eq!(layers.source_of("baz"), Some("second"));
+
+        assert!(!layers.has_opt("qux"));
+        assert_eq!(layers.store_keys(), vec!["bar", "baz", "foo"]);
+    }
+
+    #[test]
+    fn should_add_layer_of_defaults() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo"]), has_arg(true), defaults(&["1"])]),
+            OptCfg::with(&[
+                store_key("Bar"),
+                names(&["bar"]),
+                has_arg(true),
+                defaults(&["2"]),
+            ]),
+            OptCfg::with(&[names(&["baz"])]),
+        ];
+
+        let layers = Layers::new().defaults("default", &opt_cfgs);
+
+        assert_eq!(layers.opt_arg("foo"), Some("1"));
+        assert_eq!(layers.source_of("foo"), Some("default"));
+        assert_eq!(layers.opt_arg("Bar"), Some("2"));
+        assert_eq!(layers.source_of("Bar"), Some("default"));
+        assert!(!layers.has_opt("bar"));
+        assert!(!layers.has_opt("baz"));
+    }
+
+    #[test]
+    fn should_add_layer_of_cmd() {
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "--foo=1".to_string(),
+            "--bar".to_string(),
+        ]);
+        cmd.parse().unwrap();
+
+        let layers = Layers::new()
+            .layer("env", [("foo", vec!["2"]), ("baz", vec!["3"])])
+            .cmd("cli", &cmd);
+
+        assert_eq!(layers.opt_arg("foo"), Some("1"));
+        assert_eq!(layers.source_of("foo"), Some("cli"));
+        assert_eq!(layers.opt_args("bar"), Some(&[] as &[String]));
+        assert_eq!(layers.source_of("bar"), Some("cli"));
+        assert_eq!(layers.opt_arg("baz"), Some("3"));
+        assert_eq!(layers.source_of("baz"), Some("env"));
+    }
+
+    #[test]
+    fn should_not_add_default_values_applied_by_cmd() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo"]), has_arg(true), defaults(&["1"])]),
+            OptCfg::with(&[names(&["bar"]), has_arg(true), defaults(&["2"])]),
+        ];
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--bar=3".to_string()]);
+        cmd.parse_with(&opt_cfgs).unwrap();
+
+        let layers = Layers::new()
+            .defaults("default", &opt_cfgs)
+            .layer("env", [("foo", vec!["4"])])
+            .cmd("cli", &cmd);
+
+        assert_eq!(layers.opt_arg("foo"), Some("4"));
+        assert_eq!(layers.source_of("foo"), Some("env"));
+        assert_eq!(layers.opt_arg("bar"), Some("3"));
+        assert_eq!(layers.source_of("bar"), Some("cli"));
+    }
+
+    #[test]
+    fn should_render_effective_config() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo"]), has_arg(true), defaults(&["1"])]),
+            OptCfg::with(&[names(&["bar"]), has_arg(true), is_array(true)]),
+            OptCfg::with(&[names(&["baz"])]),
+        ];
+        let mut cmd = Cmd::with_strings(["app", "--bar=x", "--bar=y z", "--baz"].map(String::from));
+        cmd.parse_with(&opt_cfgs).unwrap();
+
+        let layers = Layers::new()
+            .defaults("default", &opt_cfgs)
+            .layer("config", [("qux", vec!["abc"])])
+            .cmd("cli", &cmd);
+
+        assert_eq!(
+            layers.effective_config(),
+            "KEY  VALUE       SOURCE\n\
+             bar  x, y z      cli\n\
+             baz  (no value)  cli\n\
+             foo  1           default\n\
+             qux  abc         config\n"
+        );
+
+        assert_eq!(Layers::new().effective_config(), "KEY  VALUE  SOURCE\n");
+    }
+}
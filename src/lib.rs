@@ -179,16 +179,109 @@
 /// Enums for errors that can occur when parsing command line arguments.
 pub mod errors;
 
+/// A fluent application builder which wraps `Cmd` and dispatches to sub
+/// commands.
+pub mod app;
+
+/// Functions to convert option configurations to/from argument definitions of
+/// [clap](https://docs.rs/clap).
+///
+/// This module is available when the `clap` feature is enabled.
+/// Validators are not converted in either direction, because a validator of
+/// an option configuration is a function pointer but a value parser of `clap`
+/// is not.
+#[cfg(feature = "clap")]
+pub mod clap_interop;
+
+/// Functions and types to complete command line arguments in shells.
+///
+/// The completion is computed at runtime by the command itself: the shell
+/// script generated by `completion::script` invokes the command like
+/// `app __complete <line> <point>`, and the command prints the candidates with
+/// `Cmd#complete_if_requested` or `App#run`.
+pub mod completion;
+
+/// Exit status constants and a function to exit on a usage error, to make
+/// the binaries of a workspace behave alike.
+pub mod exit;
+
+/// Functions to quote strings for shells, to build command lines of child
+/// processes from parsed values.
+pub mod quote;
+
+/// Functions to export and import a machine-readable description of a
+/// command.
+pub mod spec;
+
+/// An entry point to drive the parsers of this crate with arbitrary input for
+/// fuzzing.
+///
+/// This module is available when the `fuzz` feature is enabled.
+/// No function of this crate which parses command line arguments panics on
+/// any input; they report problems only through their `Result` values.
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+
+/// Functions and macros to write tests of parsing command line arguments
+/// concisely.
+pub mod testing;
+
+mod arena;
+mod arg_source;
+mod args_from;
+mod argv;
+mod config_file;
+mod constraints;
+#[cfg(feature = "dotenv")]
+mod dotenv;
+mod env;
+#[cfg(feature = "glob")]
+mod glob_args;
+mod layers;
+mod merge;
 mod opt_cfg;
 mod parse;
+mod path_expansion;
+mod prompt;
+mod similar;
+#[cfg(not(feature = "bench"))]
+mod small_map;
+mod typed;
+
+// Exposes `SmallMap` only to `benches/small_map.rs`.
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod small_map;
 
 pub mod validators;
 
+/// Types of option values which are converted from human-readable strings,
+/// for use with `Cmd#register_type` and `Cmd#opt_args_as`.
+pub mod values;
+
+pub use arg_source::{ArgSource, EnvArgs};
+pub use args_from::read_args_from;
+pub use config_file::user_config_path;
+pub use constraints::Constraints;
+#[cfg(feature = "glob")]
+pub use glob_args::NoMatchPolicy;
+pub use layers::Layers;
+pub use merge::MergePolicy;
+pub use opt_cfg::assign_short_names;
+pub use opt_cfg::validate_cfgs;
+pub use opt_cfg::EmptyArgPolicy;
 pub use opt_cfg::OptCfg;
 pub use opt_cfg::OptCfgParam;
+pub use parse::{DuplicatePolicy, ParseOptions, TokenKind};
+pub use path_expansion::{expand_path, interpolate_env};
+pub use prompt::{Prompt, TerminalPrompt};
+
+use arena::Arena;
+use small_map::SmallMap;
 
+use std::any::Any;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::env;
 use std::ffi::OsString;
 use std::fmt;
 use std::mem;
@@ -206,7 +299,24 @@ use std::path;
 pub struct Cmd<'a> {
     name: &'a str,
     args: Vec<&'a str>,
-    opts: HashMap<&'a str, Vec<&'a str>>,
+    opts: SmallMap<&'a str, Vec<&'a str>>,
+    defaulted: Vec<&'a str>,
+    plus_opts: Vec<&'a str>,
+    opt_indices: HashMap<&'a str, Vec<usize>>,
+    names_used: HashMap<&'a str, &'a str>,
+    path: Vec<String>,
+    is_after_end_opt: bool,
+    arg_offset: usize,
+    cfgs: Vec<OptCfg>,
+    env_prefix: String,
+    env_separator: String,
+    parse_options: ParseOptions,
+    #[cfg(feature = "dotenv")]
+    dotenv_vars: HashMap<String, String>,
+    #[cfg(feature = "dotenv")]
+    dotenv_override: bool,
+    typed_values: HashMap<String, Box<dyn Any + Send + Sync>>,
+    arena: Arena,
 
     _arg_refs: Vec<&'a str>,
 }
@@ -221,11 +331,13 @@ impl<'a> Drop for Cmd<'a> {
 }
 
 impl fmt::Debug for Cmd<'_> {
+    /// Writes this `Cmd` with the options sorted by their store keys, so that
+    /// the output is stable.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Cmd")
             .field("name", &self.name)
             .field("args", &self.args)
-            .field("opts", &self.opts)
+            .field("opts", &self.opts.iter().collect::<BTreeMap<_, _>>())
             .finish()
     }
 }
@@ -238,7 +350,7 @@ impl<'a> Cmd<'a> {
     /// contain invalid unicode data, the return value of this funciton is
     /// [Result] of `Cmd` or `errors::InvalidOsArg`.
     pub fn new() -> Result<Cmd<'a>, errors::InvalidOsArg> {
-        Self::with_os_strings(env::args_os())
+        Self::with_source(&EnvArgs)
     }
 
     /// Creates a `Cmd` instance with the specified iterator of [OsString]s.
@@ -308,11 +420,58 @@ impl<'a> Cmd<'a> {
         Ok(Cmd {
             name: &_arg_refs[0][cmd_name_start..],
             args: Vec::new(),
-            opts: HashMap::new(),
+            opts: SmallMap::new(),
+            defaulted: Vec::new(),
+            plus_opts: Vec::new(),
+            opt_indices: HashMap::new(),
+            names_used: HashMap::new(),
+            path: Vec::new(),
+            is_after_end_opt: false,
+            arg_offset: 0,
+            cfgs: Vec::new(),
+            env_prefix: String::new(),
+            env_separator: ",".to_string(),
+            parse_options: ParseOptions::default(),
+            #[cfg(feature = "dotenv")]
+            dotenv_vars: HashMap::new(),
+            #[cfg(feature = "dotenv")]
+            dotenv_override: false,
+            typed_values: HashMap::new(),
+            arena: Arena::default(),
             _arg_refs,
         })
     }
 
+    /// Creates a `Cmd` instance with the specified iterator of UTF-16
+    /// encoded command line arguments.
+    ///
+    /// This function is for Windows programs which get command line arguments
+    /// with `GetCommandLineW` and `CommandLineToArgvW` instead of the CRT
+    /// argv.
+    /// Since UTF-16 arguments can contain unpaired surrogates, the return
+    /// value of this function is [Result] of `Cmd` or `errors::InvalidOsArg`.
+    #[cfg(windows)]
+    pub fn with_wide_strings(
+        args: impl IntoIterator<Item = Vec<u16>>,
+    ) -> Result<Cmd<'a>, errors::InvalidOsArg> {
+        use std::os::windows::ffi::OsStringExt;
+        Self::with_os_strings(args.into_iter().map(|arg| OsString::from_wide(&arg)))
+    }
+
+    /// Creates a `Cmd` instance with the specified iterator of byte vectors.
+    ///
+    /// This function is for Unix programs which receive command line
+    /// arguments as bytes, like `execve` shims or fuzzers.
+    /// Each argument is validated as UTF-8, and if it is invalid, this
+    /// function returns `errors::InvalidOsArg` with the index of the argument.
+    #[cfg(unix)]
+    pub fn with_bytes(
+        args: impl IntoIterator<Item = Vec<u8>>,
+    ) -> Result<Cmd<'a>, errors::InvalidOsArg> {
+        use std::os::unix::ffi::OsStringExt;
+        Self::with_os_strings(args.into_iter().map(OsString::from_vec))
+    }
+
     /// Creates a `Cmd` instance with the specified iterator of [String]s.
     pub fn with_strings(args: impl IntoIterator<Item = String>) -> Cmd<'a> {
         let arg_iter = args.into_iter();
@@ -326,7 +485,7 @@ impl<'a> Cmd<'a> {
 
         let cmd_name_start: usize;
 
-        if _arg_refs.len() > 0 {
+        if !_arg_refs.is_empty() {
             let path = path::Path::new(_arg_refs[0]);
             let mut base_len = 0;
             if let Some(base_os) = path.file_name() {
@@ -343,11 +502,97 @@ impl<'a> Cmd<'a> {
         Cmd {
             name: &_arg_refs[0][cmd_name_start..],
             args: Vec::new(),
-            opts: HashMap::new(),
+            opts: SmallMap::new(),
+            defaulted: Vec::new(),
+            plus_opts: Vec::new(),
+            opt_indices: HashMap::new(),
+            names_used: HashMap::new(),
+            path: Vec::new(),
+            is_after_end_opt: false,
+            arg_offset: 0,
+            cfgs: Vec::new(),
+            env_prefix: String::new(),
+            env_separator: ",".to_string(),
+            parse_options: ParseOptions::default(),
+            #[cfg(feature = "dotenv")]
+            dotenv_vars: HashMap::new(),
+            #[cfg(feature = "dotenv")]
+            dotenv_override: false,
+            typed_values: HashMap::new(),
+            arena: Arena::default(),
             _arg_refs,
         }
     }
 
+    /// Creates a `Cmd` instance with the specified iterator of [String]s and
+    /// the option configurations.
+    ///
+    /// The option configurations are stored in the `Cmd` instance and used by
+    /// `parse`.
+    /// They are checked when parsing.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::names;
+    ///
+    /// let mut cmd = Cmd::with_strings_and_cfgs(
+    ///     ["app", "--foo"].map(String::from),
+    ///     vec![OptCfg::with(&[names(&["foo"])])],
+    /// );
+    /// cmd.parse().unwrap();
+    /// assert_eq!(cmd.has_opt("foo"), true);
+    /// ```
+    pub fn with_strings_and_cfgs(
+        args: impl IntoIterator<Item = String>,
+        opt_cfgs: Vec<OptCfg>,
+    ) -> Cmd<'a> {
+        let mut cmd = Self::with_strings(args);
+        cmd.cfgs = opt_cfgs;
+        cmd
+    }
+
+    /// Creates a `Cmd` instance with the specified iterator of [String]s
+    /// and the flag which indicates whether the arguments follow the end of
+    /// options (`--`).
+    ///
+    /// This function is for creating a sub command from the remaining
+    /// arguments of a parent command.
+    /// If `is_after_end_opt` is `true`, all arguments except the command name
+    /// are treated as command arguments even if they start with `-`.
+    ///
+    /// ```
+    /// use cliargs::Cmd;
+    ///
+    /// let mut sub = Cmd::with_strings_after_end_opt(["sub", "-x"].map(String::from), true);
+    /// sub.parse().unwrap();
+    /// assert_eq!(sub.is_after_end_opt(), true);
+    /// assert_eq!(sub.args(), &["-x"]);
+    /// ```
+    pub fn with_strings_after_end_opt(
+        args: impl IntoIterator<Item = String>,
+        is_after_end_opt: bool,
+    ) -> Cmd<'a> {
+        let mut cmd = Self::with_strings(args);
+        cmd.is_after_end_opt = is_after_end_opt;
+        cmd
+    }
+
+    /// Creates a `Cmd` instance with the specified iterator of [OsString]s
+    /// and the option configurations.
+    ///
+    /// The option configurations are stored in the `Cmd` instance and used by
+    /// `parse`.
+    /// [OsString]s can contain invalid unicode data, the return value of
+    /// this function is [Result] of `Cmd` or `errors::InvalidOsArg`.
+    pub fn with_os_strings_and_cfgs(
+        osargs: impl IntoIterator<Item = OsString>,
+        opt_cfgs: Vec<OptCfg>,
+    ) -> Result<Cmd<'a>, errors::InvalidOsArg> {
+        let mut cmd = Self::with_os_strings(osargs)?;
+        cmd.cfgs = opt_cfgs;
+        Ok(cmd)
+    }
+
     /// Returns the command name.
     ///
     /// This name is base name extracted from the command path string slice,
@@ -356,6 +601,38 @@ impl<'a> Cmd<'a> {
         self.name
     }
 
+    /// Returns `true` if this `Cmd` instance is created so that all its
+    /// arguments follow the end of options (`--`).
+    pub fn is_after_end_opt(&self) -> bool {
+        self.is_after_end_opt
+    }
+
+    /// Returns the index of the command name of this command in the
+    /// original command line arguments.
+    ///
+    /// For a sub command dispatched by `App#run`, adding this offset to an
+    /// index in this command, like one returned by `opt_indices`, gives the
+    /// position in the command line arguments of the top command.
+    /// For other commands, this offset is zero.
+    /// If hooks added with `App#pre_parse` change the arguments, this offset
+    /// is for the changed arguments.
+    pub fn arg_offset(&self) -> usize {
+        self.arg_offset
+    }
+
+    /// Returns the command names from the top command to this command.
+    ///
+    /// For a sub command dispatched by `App#run`, this path is like
+    /// `["app", "remote", "add"]`, and for other commands, this path
+    /// contains only the command name.
+    pub fn path(&'a self) -> Vec<&'a str> {
+        if self.path.is_empty() {
+            vec![self.name]
+        } else {
+            self.path.iter().map(|s| s.as_str()).collect()
+        }
+    }
+
     /// Returns the command arguments.
     ///
     /// These arguments are retrieved as string slices in an array.
@@ -368,6 +645,44 @@ impl<'a> Cmd<'a> {
         self.opts.contains_key(name)
     }
 
+    /// Checks whether an option with the specified name was specified with
+    /// `+` prefix at its last occurrence, like `+x`.
+    ///
+    /// Only options of which the configurations accept `+` prefix can be
+    /// specified so.
+    pub fn is_plus_opt(&self, name: &str) -> bool {
+        self.plus_opts.contains(&name)
+    }
+
+    /// Returns the number of times the option with the specified name was
+    /// specified in command line arguments.
+    ///
+    /// An option which is not specified but has default values is counted as
+    /// zero.
+    pub fn opt_occurrence_count(&self, name: &str) -> usize {
+        self.opt_indices.get(name).map_or(0, |v| v.len())
+    }
+
+    /// Returns the indices in command line arguments where the option with
+    /// the specified name was specified, in ascending order.
+    ///
+    /// The index of the command path is zero.
+    /// Options in a cluster of short options, like `-abc`, have the same
+    /// index.
+    pub fn opt_indices(&'a self, name: &str) -> &'a [usize] {
+        self.opt_indices.get(name).map_or(&[], |v| v.as_slice())
+    }
+
+    /// Returns the option name which was used to specify the option with the
+    /// specified store key at its last occurrence in command line arguments,
+    /// like `f` for `-f` or `force` for `--force`.
+    ///
+    /// If the option is not specified in command line arguments, this method
+    /// returns `None`.
+    pub fn opt_name_used(&'a self, store_key: &str) -> Option<&'a str> {
+        self.names_used.get(store_key).copied()
+    }
+
     /// Returns the option argument with the specified name.
     ///
     /// If the option has multiple arguments, this method returns the first
@@ -378,7 +693,7 @@ impl<'a> Cmd<'a> {
     /// or [None].
     pub fn opt_arg(&'a self, name: &str) -> Option<&'a str> {
         if let Some(opt_vec) = self.opts.get(name) {
-            if opt_vec.len() > 0 {
+            if !opt_vec.is_empty() {
                 return Some(opt_vec[0]);
             }
         }
@@ -395,10 +710,78 @@ impl<'a> Cmd<'a> {
     /// [None].
     pub fn opt_args(&'a self, name: &str) -> Option<&'a [&'a str]> {
         match self.opts.get(name) {
-            Some(vec) => Some(&vec),
+            Some(vec) => Some(vec),
             None => None,
         }
     }
+
+    /// Returns the first option argument with the specified name, or the
+    /// specified default value if the option has no argument.
+    pub fn opt_arg_or(&'a self, name: &str, default: &'a str) -> &'a str {
+        self.opt_arg(name).unwrap_or(default)
+    }
+
+    /// Returns the first option argument with the specified name as a
+    /// [String].
+    ///
+    /// This method is useful when the option argument is used for a longer
+    /// period than this `Cmd` instance.
+    pub fn opt_arg_owned(&self, name: &str) -> Option<String> {
+        self.opts
+            .get(name)
+            .and_then(|vec| vec.first())
+            .map(|s| s.to_string())
+    }
+
+    /// Checks whether the option with the specified name is turned on.
+    ///
+    /// This method returns `true` if the option is specified, except when it
+    /// was specified with `+` prefix at its last occurrence, like `+x`, which
+    /// turns the option off.
+    pub fn flag(&self, name: &str) -> bool {
+        self.has_opt(name) && !self.is_plus_opt(name)
+    }
+
+    /// Adds the specified option configurations to the ones stored in this
+    /// `Cmd` instance.
+    ///
+    /// This method enables to assemble option configurations from multiple
+    /// modules, like core options and plugin options, before parsing once
+    /// with `parse`.
+    /// The added configurations are checked together with the stored ones,
+    /// and if they have an error like duplicated names, this method returns
+    /// the first error and stores none of the added configurations.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::{names, has_arg};
+    ///
+    /// let mut cmd = Cmd::with_strings(["app", "-v", "--level=2"].map(String::from));
+    /// cmd.add_cfgs([OptCfg::with(&[names(&["verbose", "v"])])]).unwrap();
+    /// cmd.add_cfgs([OptCfg::with(&[names(&["level"]), has_arg(true)])]).unwrap();
+    /// assert!(cmd.add_cfgs([OptCfg::with(&[names(&["v"])])]).is_err());
+    ///
+    /// cmd.parse().unwrap();
+    /// assert_eq!(cmd.has_opt("verbose"), true);
+    /// assert_eq!(cmd.opt_arg("level"), Some("2"));
+    /// ```
+    pub fn add_cfgs(
+        &mut self,
+        opt_cfgs: impl IntoIterator<Item = OptCfg>,
+    ) -> Result<(), errors::InvalidOption> {
+        let len = self.cfgs.len();
+        self.cfgs.extend(opt_cfgs);
+        if let Some(err) = validate_cfgs(&self.cfgs).into_iter().next() {
+            self.cfgs.truncate(len);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Returns the option configurations stored in this `Cmd` instance.
+    pub fn cfgs(&self) -> &[OptCfg] {
+        &self.cfgs
+    }
 }
 
 #[cfg(test)]
@@ -414,7 +797,7 @@ mod tests_of_cmd {
             println!("cmd = {cmd:?}");
             println!("cmd._arg_refs = {:?}", cmd._arg_refs);
             assert!(cmd.name().starts_with("cliargs-"));
-            assert!(cmd._arg_refs.len() > 0);
+            assert!(!cmd._arg_refs.is_empty());
         }
     }
 
@@ -451,6 +834,7 @@ mod tests_of_cmd {
                 "corge".to_string(),
             ]);
             assert_eq!(cmd.name(), "app");
+            assert_eq!(cmd.path(), ["app"]);
         }
 
         #[test]
@@ -520,7 +904,7 @@ mod tests_of_cmd {
                 bad_os_string.clone(),
                 ffi::OsString::from("qux"),
             ]) {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(crate::errors::InvalidOsArg::OsArgsContainInvalidUnicode { index, os_arg }) => {
                     assert_eq!(index, 2);
                     assert_eq!(os_arg, bad_os_string);
@@ -540,7 +924,7 @@ mod tests_of_cmd {
                 ffi::OsString::from("--foo"),
                 ffi::OsString::from("qux"),
             ]) {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(crate::errors::InvalidOsArg::OsArgsContainInvalidUnicode { index, os_arg }) => {
                     assert_eq!(index, 0);
                     assert_eq!(os_arg, bad_os_string);
@@ -562,7 +946,7 @@ mod tests_of_cmd {
             ]) {
                 assert_eq!(cmd.name(), "app");
             } else {
-                assert!(false);
+                panic!();
             }
         }
 
@@ -580,7 +964,7 @@ mod tests_of_cmd {
             ]) {
                 assert_eq!(cmd.name(), "app");
             } else {
-                assert!(false);
+                panic!();
             }
         }
 
@@ -598,7 +982,7 @@ mod tests_of_cmd {
             ]) {
                 assert_eq!(cmd.name(), "app");
             } else {
-                assert!(false);
+                panic!();
             }
         }
 
@@ -607,7 +991,87 @@ mod tests_of_cmd {
             if let Ok(cmd) = Cmd::with_os_strings([]) {
                 assert_eq!(cmd.name(), "");
             } else {
-                assert!(false);
+                panic!();
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    mod tests_of_with_bytes {
+        use super::Cmd;
+        use std::ffi;
+
+        #[test]
+        fn should_create_a_new_instance() {
+            let mut cmd = Cmd::with_bytes([
+                b"/path/to/app".to_vec(),
+                b"--foo".to_vec(),
+                "\u{3042}".as_bytes().to_vec(),
+            ])
+            .unwrap();
+            assert_eq!(cmd.name(), "app");
+
+            cmd.parse().unwrap();
+            assert!(cmd.has_opt("foo"));
+            assert_eq!(cmd.args(), &["\u{3042}"]);
+        }
+
+        #[test]
+        fn should_fail_because_args_contain_invalid_utf8() {
+            let bad_arg = b"bar\xFFbaz".to_vec();
+
+            match Cmd::with_bytes([b"app".to_vec(), b"--foo".to_vec(), bad_arg.clone()]) {
+                Ok(_) => panic!(),
+                Err(crate::errors::InvalidOsArg::OsArgsContainInvalidUnicode { index, os_arg }) => {
+                    use std::os::unix::ffi::OsStringExt;
+                    assert_eq!(index, 2);
+                    assert_eq!(os_arg, ffi::OsString::from_vec(bad_arg));
+                }
+            }
+        }
+
+        #[test]
+        fn should_get_command_name_when_command_line_arguments_is_empty() {
+            let cmd = Cmd::with_bytes([]).unwrap();
+            assert_eq!(cmd.name(), "");
+        }
+    }
+
+    #[cfg(windows)]
+    mod tests_of_with_wide_strings {
+        use super::Cmd;
+        use std::ffi;
+        use std::os::windows::ffi::OsStringExt;
+
+        fn wide(s: &str) -> Vec<u16> {
+            s.encode_utf16().collect()
+        }
+
+        #[test]
+        fn should_create_a_new_instance() {
+            let mut cmd = Cmd::with_wide_strings([
+                wide("C:\\path\\to\\app.exe"),
+                wide("--foo"),
+                wide("\u{3042}\u{3044}"),
+            ])
+            .unwrap();
+            assert_eq!(cmd.name(), "app.exe");
+
+            cmd.parse().unwrap();
+            assert_eq!(cmd.has_opt("foo"), true);
+            assert_eq!(cmd.args(), &["\u{3042}\u{3044}"]);
+        }
+
+        #[test]
+        fn should_fail_because_args_contain_unpaired_surrogate() {
+            let bad_arg: Vec<u16> = vec![0x62, 0xD800, 0x7A];
+
+            match Cmd::with_wide_strings([wide("app"), wide("--foo"), bad_arg.clone()]) {
+                Ok(_) => panic!(),
+                Err(crate::errors::InvalidOsArg::OsArgsContainInvalidUnicode { index, os_arg }) => {
+                    assert_eq!(index, 2);
+                    assert_eq!(os_arg, ffi::OsString::from_wide(&bad_arg));
+                }
             }
         }
     }
@@ -669,9 +1133,9 @@ mod tests_of_cmd {
                 vec![&cmd._arg_refs[3], &cmd._arg_refs[5]],
             );
 
-            assert_eq!(cmd.has_opt("foo"), true);
-            assert_eq!(cmd.has_opt("bar"), true);
-            assert_eq!(cmd.has_opt("baz"), false);
+            assert!(cmd.has_opt("foo"));
+            assert!(cmd.has_opt("bar"));
+            assert!(!cmd.has_opt("baz"));
         }
 
         #[test]
@@ -727,5 +1191,144 @@ mod tests_of_cmd {
             assert_eq!(cmd.opt_args("bar"), Some(&["baz", "qux"] as &[&str]));
             assert_eq!(cmd.opt_args("baz"), None);
         }
+
+        #[test]
+        fn should_get_option_arguments_conveniently() {
+            let mut cmd = Cmd::with_strings([
+                "/path/to/app".to_string(),
+                "--foo".to_string(),
+                "--bar".to_string(),
+                "baz".to_string(),
+                "+x".to_string(),
+            ]);
+
+            cmd.opts
+                .insert(&cmd._arg_refs[1][2..], Vec::with_capacity(0));
+            cmd.opts
+                .insert(&cmd._arg_refs[2][2..], vec![&cmd._arg_refs[3]]);
+            cmd.opts
+                .insert(&cmd._arg_refs[4][1..], Vec::with_capacity(0));
+            cmd.plus_opts.push(&cmd._arg_refs[4][1..]);
+
+            assert_eq!(cmd.opt_arg_or("foo", "1"), "1");
+            assert_eq!(cmd.opt_arg_or("bar", "1"), "baz");
+            assert_eq!(cmd.opt_arg_or("qux", "1"), "1");
+
+            assert_eq!(cmd.opt_arg_owned("foo"), None);
+            assert_eq!(cmd.opt_arg_owned("bar"), Some("baz".to_string()));
+            assert_eq!(cmd.opt_arg_owned("qux"), None);
+
+            assert!(cmd.flag("foo"));
+            assert!(cmd.flag("bar"));
+            assert!(!cmd.flag("x"));
+            assert!(!cmd.flag("qux"));
+        }
+    }
+
+    mod tests_of_cfgs {
+        use super::Cmd;
+        use crate::errors::InvalidOption;
+        use crate::OptCfg;
+        use crate::OptCfgParam::{has_arg, names};
+
+        #[test]
+        fn should_add_cfgs_and_parse_with_them() {
+            let mut cmd = Cmd::with_strings([
+                "app".to_string(),
+                "-v".to_string(),
+                "--level".to_string(),
+                "2".to_string(),
+            ]);
+            assert_eq!(cmd.cfgs().len(), 0);
+
+            assert_eq!(
+                cmd.add_cfgs([OptCfg::with(&[names(&["verbose", "v"])])]),
+                Ok(())
+            );
+            assert_eq!(
+                cmd.add_cfgs(vec![OptCfg::with(&[names(&["level"]), has_arg(true)])]),
+                Ok(())
+            );
+            assert_eq!(cmd.cfgs().len(), 2);
+
+            assert_eq!(cmd.parse(), Ok(()));
+            assert!(cmd.has_opt("verbose"));
+            assert_eq!(cmd.opt_arg("level"), Some("2"));
+            assert_eq!(cmd.args(), &[] as &[&str]);
+            assert_eq!(cmd.cfgs().len(), 2);
+        }
+
+        #[test]
+        fn should_create_with_cfgs() {
+            let mut cmd = Cmd::with_strings_and_cfgs(
+                ["app".to_string(), "-v".to_string(), "foo".to_string()],
+                vec![OptCfg::with(&[names(&["verbose", "v"])])],
+            );
+            assert_eq!(cmd.cfgs().len(), 1);
+            assert_eq!(cmd.parse(), Ok(()));
+            assert!(cmd.has_opt("verbose"));
+            assert_eq!(cmd.args(), &["foo"]);
+
+            let mut cmd = Cmd::with_os_strings_and_cfgs(
+                [
+                    std::ffi::OsString::from("app"),
+                    std::ffi::OsString::from("-w"),
+                ],
+                vec![OptCfg::with(&[names(&["verbose", "v"])])],
+            )
+            .unwrap();
+            match cmd.parse() {
+                Err(InvalidOption::UnconfiguredOption { option }) => assert_eq!(option, "w"),
+                _ => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_fail_to_add_duplicated_cfgs() {
+            let mut cmd = Cmd::with_strings(["app".to_string(), "--quiet".to_string()]);
+            assert_eq!(
+                cmd.add_cfgs([OptCfg::with(&[names(&["verbose", "v"])])]),
+                Ok(())
+            );
+
+            assert_eq!(
+                cmd.add_cfgs([
+                    OptCfg::with(&[names(&["quiet"])]),
+                    OptCfg::with(&[names(&["version", "v"])]),
+                ]),
+                Err(InvalidOption::OptionNameIsDuplicated {
+                    store_key: "version".to_string(),
+                    name: "v".to_string(),
+                })
+            );
+            assert_eq!(cmd.cfgs().len(), 1);
+
+            match cmd.parse() {
+                Err(InvalidOption::UnconfiguredOption { option }) => assert_eq!(option, "quiet"),
+                _ => panic!(),
+            }
+        }
+    }
+
+    mod tests_of_debug {
+        use super::Cmd;
+
+        #[test]
+        fn should_write_opts_in_order_of_store_keys() {
+            let mut cmd = Cmd::with_strings([
+                "/path/to/app".to_string(),
+                "--zoo".to_string(),
+                "-b=1".to_string(),
+                "--mid".to_string(),
+                "--a".to_string(),
+                "qux".to_string(),
+            ]);
+            cmd.parse().unwrap();
+
+            assert_eq!(
+                format!("{cmd:?}"),
+                "Cmd { name: \"app\", args: [\"qux\"], opts: {\"a\": [], \"b\": [\"1\"], \"mid\": [], \"zoo\": []} }"
+            );
+        }
     }
 }
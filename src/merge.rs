@@ -0,0 +1,184 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::Cmd;
+
+/// Enumerates the policies to merge the values of an option in two parse
+/// results with `Cmd::merge`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// The values in the overlay replace the values in the base.
+    OverlayWins,
+
+    /// The values in the overlay are appended to the values in the base.
+    Append,
+}
+
+impl<'a> Cmd<'a> {
+    /// Merges two parse results into a new `Cmd` instance.
+    ///
+    /// This method is used to combine, for example, options parsed from a
+    /// file of persistent default flags as `base` and options parsed from the
+    /// live command line as `overlay`.
+    ///
+    /// The values of an option are merged with the policy specified for its
+    /// store key in `policies`, or with `MergePolicy::OverlayWins` if not
+    /// specified.
+    /// Default values applied by `Cmd#parse_with` are weaker than the values
+    /// specified explicitly in either parse result.
+    /// The command name is taken from `overlay`, and the command arguments
+    /// are taken from `overlay` if it has any, otherwise from `base`.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, MergePolicy};
+    ///
+    /// let mut base = Cmd::with_strings(["app", "--foo=1", "--inc=a"].map(String::from));
+    /// base.parse().unwrap();
+    /// let mut overlay = Cmd::with_strings(["app", "--foo=2", "--inc=b", "file"].map(String::from));
+    /// overlay.parse().unwrap();
+    ///
+    /// let cmd = Cmd::merge(&base, &overlay, &[("inc", MergePolicy::Append)]);
+    /// assert_eq!(cmd.opt_arg("foo"), Some("2"));
+    /// assert_eq!(cmd.opt_args("inc"), Some(&["a", "b"] as &[&str]));
+    /// assert_eq!(cmd.args(), &["file"]);
+    /// ```
+    pub fn merge(base: &Cmd<'_>, overlay: &Cmd<'_>, policies: &[(&str, MergePolicy)]) -> Cmd<'a> {
+        let mut cmd = Cmd::with_strings([overlay.name.to_string()]);
+
+        let mut keys: Vec<&str> = base
+            .opts
+            .keys()
+            .chain(overlay.opts.keys())
+            .copied()
+            .collect();
+        keys.sort();
+        keys.dedup();
+
+        for key in keys {
+            let in_base = base.opts.contains_key(key);
+            let in_overlay = overlay.opts.contains_key(key);
+            let base_explicit = in_base && !base.defaulted.contains(&key);
+            let overlay_explicit = in_overlay && !overlay.defaulted.contains(&key);
+
+            let policy = policies
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map_or(MergePolicy::OverlayWins, |(_, p)| *p);
+
+            let mut sources: Vec<&Cmd<'_>> = Vec::new();
+            if overlay_explicit {
+                if base_explicit && policy == MergePolicy::Append {
+                    sources.push(base);
+                }
+                sources.push(overlay);
+            } else if base_explicit || !in_overlay {
+                sources.push(base);
+            } else {
+                sources.push(overlay);
+            }
+
+            let key_ref = cmd.arena.alloc(key);
+            let mut vec = Vec::new();
+            for src in sources.iter() {
+                for val in src.opts[key].iter() {
+                    vec.push(cmd.arena.alloc(val));
+                }
+            }
+            cmd.opts.insert(key_ref, vec);
+
+            let last = sources[sources.len() - 1];
+            if last.defaulted.contains(&key) {
+                cmd.defaulted.push(key_ref);
+            }
+            if last.plus_opts.contains(&key) {
+                cmd.plus_opts.push(key_ref);
+            }
+            if let Some(name) = last.names_used.get(key) {
+                let name = cmd.arena.alloc(name);
+                cmd.names_used.insert(key_ref, name);
+            }
+            if std::ptr::eq(last, overlay) {
+                if let Some(indices) = overlay.opt_indices.get(key) {
+                    cmd.opt_indices.insert(key_ref, indices.clone());
+                }
+            }
+        }
+
+        let args = if overlay.args.is_empty() {
+            &base.args
+        } else {
+            &overlay.args
+        };
+        for arg in args.iter() {
+            let arg = cmd.arena.alloc(arg);
+            cmd.args.push(arg);
+        }
+
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests_of_merge {
+    use super::*;
+    use crate::OptCfg;
+    use crate::OptCfgParam::{accepts_plus, defaults, has_arg, is_array, names};
+
+    fn opt_cfgs() -> Vec<OptCfg> {
+        vec![
+            OptCfg::with(&[names(&["foo"]), has_arg(true), defaults(&["0"])]),
+            OptCfg::with(&[names(&["inc"]), has_arg(true), is_array(true)]),
+            OptCfg::with(&[names(&["x"]), accepts_plus(true)]),
+            OptCfg::with(&[names(&["bar"]), has_arg(true), defaults(&["d"])]),
+        ]
+    }
+
+    #[test]
+    fn should_merge_with_overlay_wins_by_default() {
+        let mut base = Cmd::with_strings(
+            ["app", "--foo=1", "--inc=a", "--inc=b", "-x", "arg"].map(String::from),
+        );
+        base.parse_with(&opt_cfgs()).unwrap();
+        let mut overlay = Cmd::with_strings(["/bin/app", "--inc=c", "+x"].map(String::from));
+        overlay.parse_with(&opt_cfgs()).unwrap();
+
+        let cmd = Cmd::merge(&base, &overlay, &[]);
+        assert_eq!(cmd.name(), "app");
+        assert_eq!(cmd.opt_arg("foo"), Some("1"));
+        assert_eq!(cmd.opt_args("inc"), Some(&["c"] as &[&str]));
+        assert!(cmd.has_opt("x"));
+        assert!(cmd.is_plus_opt("x"));
+        assert_eq!(cmd.opt_indices("x"), &[2]);
+        assert_eq!(cmd.opt_indices("foo"), &[] as &[usize]);
+        assert_eq!(cmd.opt_arg("bar"), Some("d"));
+        assert_eq!(cmd.args(), &["arg"]);
+    }
+
+    #[test]
+    fn should_merge_with_append_policy() {
+        let mut base = Cmd::with_strings(["app", "--inc=a", "--inc=b"].map(String::from));
+        base.parse_with(&opt_cfgs()).unwrap();
+        let mut overlay =
+            Cmd::with_strings(["app", "--inc=c", "--foo=2", "file"].map(String::from));
+        overlay.parse_with(&opt_cfgs()).unwrap();
+
+        let cmd = Cmd::merge(&base, &overlay, &[("inc", MergePolicy::Append)]);
+        assert_eq!(cmd.opt_args("inc"), Some(&["a", "b", "c"] as &[&str]));
+        assert_eq!(cmd.opt_arg("foo"), Some("2"));
+        assert_eq!(cmd.args(), &["file"]);
+    }
+
+    #[test]
+    fn should_keep_defaulted_values_weaker() {
+        let mut base = Cmd::with_strings(["app", "--bar=b"].map(String::from));
+        base.parse_with(&opt_cfgs()).unwrap();
+        let mut overlay = Cmd::with_strings(["app"].map(String::from));
+        overlay.parse_with(&opt_cfgs()).unwrap();
+
+        let cmd = Cmd::merge(&base, &overlay, &[("bar", MergePolicy::Append)]);
+        assert_eq!(cmd.opt_args("bar"), Some(&["b"] as &[&str]));
+        assert_eq!(cmd.opt_args("foo"), Some(&["0"] as &[&str]));
+        assert_eq!(cmd.to_argv(), ["app", "--bar=b"]);
+    }
+}
@@ -2,13 +2,22 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
+use crate::completion::ValueHint;
 use crate::errors::InvalidOption;
+use std::collections::HashSet;
 use std::fmt;
 
 /// Represents an option configuration for how to parse command line arguments.
 ///
 /// And this is also used when creating the help text for command line
 /// arguments.
+///
+/// When the `serde` feature is enabled, this struct can be serialized and
+/// deserialized except for `validator` and `on_parse`, which are reset to
+/// functions doing nothing when deserialized.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OptCfg {
     /// Is the key to store option value(s) in the option map in a `Cmd`
     /// instance.
@@ -20,17 +29,120 @@ pub struct OptCfg {
     /// The order of the `names` in this array are used in a help text.
     pub names: Vec<String>,
 
+    /// Is the vector of the names in `names` which are hidden, like legacy
+    /// spellings kept for compatibility.
+    /// Hidden names are parsed as well as other names, but never appear in a
+    /// help text or completions.
+    pub hidden_names: Vec<String>,
+
     /// Is the flag which allow the option to take option arguments.
+    /// A short option with this flag can be placed at the end of a cluster of
+    /// short options, like `f` of `-rvf archive.tar`, and takes the next
+    /// command line argument, but cannot be placed in the middle of it.
     pub has_arg: bool,
 
     /// Is the flag which allow the option to take multiple option arguments.
     pub is_array: bool,
 
+    /// Is the flag which disallows the option to have the same option
+    /// argument more than once.
+    /// If this flag and `is_array` are `true`, a repeated option argument in
+    /// command line arguments is rejected with
+    /// `InvalidOption::OptionArgIsDuplicated`, or is silently removed if
+    /// `ParseOptions#duplicate_opt` is `DuplicatePolicy::FirstWins` or
+    /// `DuplicatePolicy::LastWins`, which keeps the first or the last of them.
+    pub unique: bool,
+
+    /// Is the flag which indicates that the option argument is a number.
+    /// If this flag and `has_arg` are `true`, a short option can take a number
+    /// attached directly, like `-n5`.
+    pub is_numeric: bool,
+
+    /// Is the flag which allows the option argument(s) to have an SI prefix as
+    /// a suffix, like `1.5k` or `2M`, which are converted to the numbers in the
+    /// base unit, like `1500` or `2000000`, before validation and storing.
+    ///
+    /// The accepted suffixes are `p`, `n`, `u`, `µ`, `m`, `k`, `K`, `M`, `G`, `T`,
+    /// `P`, and `E`.
+    /// This flag is also applied to the default values and the values of
+    /// environment variables.
+    pub accepts_si_suffix: bool,
+
+    /// Is the flag which indicates that the option argument is a file path.
+    /// If this flag is `true`, a leading `~` and environment variables like
+    /// `$VAR`, `${VAR}`, or `%VAR%` in the option argument(s) are expanded
+    /// before validation and storing.
+    pub is_path: bool,
+
+    /// Is the hint for completing the option argument in shells.
+    ///
+    /// This hint is used by `completion::complete` and the scripts generated by
+    /// `completion::script`.
+    pub complete: ValueHint,
+
+    /// Is the flag which indicates that the surrounding whitespaces of the
+    /// option argument(s) specified in command line arguments are removed
+    /// before validation and storing.
+    pub trims_arg: bool,
+
+    /// Is the policy for an empty option argument specified in command line
+    /// arguments, like `--name=` or `--name ""`.
+    /// This policy is applied after trimming if `trims_arg` is `true`.
+    pub empty_arg: EmptyArgPolicy,
+
+    /// Is the flag which indicates that the option argument(s) specified in
+    /// command line arguments are stored as they are.
+    /// If this flag is `true`, `trims_arg`, `empty_arg`, `accepts_si_suffix`,
+    /// and `is_path` are not applied to them, though `validator` is still
+    /// applied.
+    pub raw_arg: bool,
+
+    /// Is the flag which allows the option to be specified with `+` instead
+    /// of `-`, like `+x` or `+o value`.
+    /// Which prefix was used is retrieved with `Cmd#is_plus_opt`.
+    pub accepts_plus: bool,
+
     /// Is the `Option` of the vector to specify default value(s) for when the
     /// comand option is not given in command line arguments.
     /// If this value is `None`, the default value(s) is not specified.
     pub defaults: Option<Vec<String>>,
 
+    /// Is the vector of conditional default values which depend on other
+    /// options.
+    /// Each element is a tuple of the store key of another option, the
+    /// option argument of it to be matched or `None` to match its presence,
+    /// and the default value(s) used when the condition is met.
+    /// The conditions are evaluated in order against options given in command
+    /// line arguments, and the first met one is used instead of `defaults`.
+    pub defaults_if: Vec<(String, Option<String>, Vec<String>)>,
+
+    /// Is the flag which enables expanding environment variables written like
+    /// `${VAR}` in the default values when they are applied, and unescaping
+    /// `$$` to `$`.
+    /// If this flag is `false`, the default values are used as they are.
+    pub interpolates_defaults: bool,
+
+    /// Is the function pointer to compute the default value(s) of the option
+    /// argument(s), which is called only when the option is not specified in
+    /// command line arguments and neither `defaults_if` nor `defaults` is
+    /// applied.
+    /// This is for default values which are expensive to compute.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub default_fn: Option<fn() -> Vec<String>>,
+
+    /// Is the `Option` of the value used when the option is specified without
+    /// an option argument, like `--color` for `--color[=WHEN]`.
+    /// If this value is `Some` and `has_arg` is `true`, the option argument
+    /// is optional and can be given only with `=`, like `--color=always`, so
+    /// that `--color foo` does not take `foo` as its argument.
+    pub default_missing_value: Option<String>,
+
+    /// Is the `Option` of the message to ask the user for a confirmation when
+    /// the option is specified, for dangerous options like `--force`.
+    /// The confirmation is asked by `Cmd#parse_with_prompt` unless the option
+    /// of which the store key is `yes` is also specified.
+    pub confirm: Option<String>,
+
     /// Is the string field to set the description of the option which is used
     /// in a help text.
     pub desc: String,
@@ -43,7 +155,25 @@ pub struct OptCfg {
     /// Is the function pointer to validate the option argument(s).
     /// If the option argument is invalid, this funciton returns a
     /// `InvalidOption::OptionArgIsInvalid` instance.
+    /// When the `rayon` feature is enabled, the arguments of array options are
+    /// validated in parallel if there are many of them, and the results,
+    /// including which error is returned, are the same as validating them one
+    /// by one.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub validator: fn(store_key: &str, name: &str, arg: &str) -> Result<(), InvalidOption>,
+
+    /// Is the function pointer which is called each time the option is
+    /// encountered in command line arguments, in the order of them.
+    /// This function is called after the option argument is validated, and
+    /// its `arg` is an empty string if the option takes no argument.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_parse: fn(store_key: &str, name: &str, arg: &str),
+}
+
+impl Default for OptCfg {
+    fn default() -> Self {
+        OptCfg::with(&[])
+    }
 }
 
 impl fmt::Debug for OptCfg {
@@ -51,9 +181,23 @@ impl fmt::Debug for OptCfg {
         f.debug_struct("OptCfg")
             .field("store_key", &self.store_key)
             .field("names", &self.names)
+            .field("hidden_names", &self.hidden_names)
             .field("has_arg", &self.has_arg)
             .field("is_array", &self.is_array)
+            .field("unique", &self.unique)
+            .field("is_numeric", &self.is_numeric)
+            .field("accepts_si_suffix", &self.accepts_si_suffix)
+            .field("is_path", &self.is_path)
+            .field("complete", &self.complete)
+            .field("trims_arg", &self.trims_arg)
+            .field("empty_arg", &self.empty_arg)
+            .field("raw_arg", &self.raw_arg)
+            .field("accepts_plus", &self.accepts_plus)
             .field("defaults", &self.defaults)
+            .field("defaults_if", &self.defaults_if)
+            .field("interpolates_defaults", &self.interpolates_defaults)
+            .field("default_missing_value", &self.default_missing_value)
+            .field("confirm", &self.confirm)
             .field("desc", &self.desc)
             .field("arg_in_help", &self.arg_in_help)
             .finish()
@@ -80,12 +224,28 @@ impl OptCfg {
         let mut init = OptCfgInit {
             store_key: &empty_string,
             names: &empty_vec,
+            hidden_names: &empty_vec,
             has_arg: false,
             is_array: false,
+            unique: false,
+            is_numeric: false,
+            accepts_si_suffix: false,
+            is_path: false,
+            complete: ValueHint::Unknown,
+            trims_arg: false,
+            empty_arg: EmptyArgPolicy::Allow,
+            raw_arg: false,
+            accepts_plus: false,
             defaults: None,
+            defaults_if: Vec::new(),
+            interpolates_defaults: false,
+            default_fn: None,
+            default_missing_value: None,
+            confirm: None,
             desc: &empty_string,
             arg_in_help: &empty_string,
             validator: |_, _, _| Ok(()),
+            on_parse: |_, _, _| {},
         };
 
         for param in params.iter() {
@@ -95,16 +255,40 @@ impl OptCfg {
         OptCfg {
             store_key: init.store_key.to_string(),
             names: init.names.iter().map(|s| s.to_string()).collect(),
+            hidden_names: init.hidden_names.iter().map(|s| s.to_string()).collect(),
             has_arg: init.has_arg,
             is_array: init.is_array,
-            defaults: if let Some(sl) = init.defaults {
-                Some(sl.iter().map(|s| s.to_string()).collect())
-            } else {
-                None
-            },
+            unique: init.unique,
+            is_numeric: init.is_numeric,
+            accepts_si_suffix: init.accepts_si_suffix,
+            is_path: init.is_path,
+            complete: init.complete,
+            trims_arg: init.trims_arg,
+            empty_arg: init.empty_arg,
+            raw_arg: init.raw_arg,
+            accepts_plus: init.accepts_plus,
+            defaults: init
+                .defaults
+                .map(|sl| sl.iter().map(|s| s.to_string()).collect()),
+            defaults_if: init
+                .defaults_if
+                .iter()
+                .map(|(key, arg, vals)| {
+                    (
+                        key.to_string(),
+                        arg.map(|s| s.to_string()),
+                        vals.iter().map(|s| s.to_string()).collect(),
+                    )
+                })
+                .collect(),
+            interpolates_defaults: init.interpolates_defaults,
+            default_fn: init.default_fn,
+            default_missing_value: init.default_missing_value.map(|s| s.to_string()),
+            confirm: init.confirm.map(|s| s.to_string()),
             desc: init.desc.to_string(),
             arg_in_help: init.arg_in_help.to_string(),
             validator: init.validator,
+            on_parse: init.on_parse,
         }
     }
 }
@@ -112,12 +296,28 @@ impl OptCfg {
 struct OptCfgInit<'a> {
     store_key: &'a str,
     names: &'a [&'a str],
+    hidden_names: &'a [&'a str],
     has_arg: bool,
     is_array: bool,
+    unique: bool,
+    is_numeric: bool,
+    accepts_si_suffix: bool,
+    is_path: bool,
+    complete: ValueHint,
+    trims_arg: bool,
+    empty_arg: EmptyArgPolicy,
+    raw_arg: bool,
+    accepts_plus: bool,
     defaults: Option<&'a [&'a str]>,
+    defaults_if: Vec<(&'a str, Option<&'a str>, &'a [&'a str])>,
+    interpolates_defaults: bool,
+    default_fn: Option<fn() -> Vec<String>>,
+    default_missing_value: Option<&'a str>,
+    confirm: Option<&'a str>,
     desc: &'a str,
     arg_in_help: &'a str,
     validator: fn(store_key: &str, name: &str, arg: &str) -> Result<(), InvalidOption>,
+    on_parse: fn(store_key: &str, name: &str, arg: &str),
 }
 
 impl<'a> OptCfgInit<'a> {
@@ -125,16 +325,50 @@ impl<'a> OptCfgInit<'a> {
         match param {
             OptCfgParam::store_key(s) => self.store_key = s,
             OptCfgParam::names(v) => self.names = v,
+            OptCfgParam::hidden_names(v) => self.hidden_names = v,
             OptCfgParam::has_arg(b) => self.has_arg = *b,
             OptCfgParam::is_array(b) => self.is_array = *b,
+            OptCfgParam::unique(b) => self.unique = *b,
+            OptCfgParam::is_numeric(b) => self.is_numeric = *b,
+            OptCfgParam::accepts_si_suffix(b) => self.accepts_si_suffix = *b,
+            OptCfgParam::is_path(b) => self.is_path = *b,
+            OptCfgParam::complete(h) => self.complete = h.clone(),
+            OptCfgParam::trims_arg(b) => self.trims_arg = *b,
+            OptCfgParam::empty_arg(p) => self.empty_arg = *p,
+            OptCfgParam::raw_arg(b) => self.raw_arg = *b,
+            OptCfgParam::accepts_plus(b) => self.accepts_plus = *b,
             OptCfgParam::defaults(v) => self.defaults = Some(v),
+            OptCfgParam::defaults_if(key, arg, v) => self.defaults_if.push((key, *arg, v)),
+            OptCfgParam::interpolates_defaults(b) => self.interpolates_defaults = *b,
+            OptCfgParam::default_fn(f) => self.default_fn = Some(*f),
+            OptCfgParam::default_missing_value(s) => self.default_missing_value = Some(s),
+            OptCfgParam::confirm(s) => self.confirm = Some(s),
             OptCfgParam::desc(s) => self.desc = s,
             OptCfgParam::arg_in_help(s) => self.arg_in_help = s,
             OptCfgParam::validator(f) => self.validator = *f,
+            OptCfgParam::on_parse(f) => self.on_parse = *f,
         }
     }
 }
 
+/// Enumerates the policies for an empty option argument specified in command
+/// line arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmptyArgPolicy {
+    /// Stores the empty argument as it is.
+    #[default]
+    Allow,
+
+    /// Returns `InvalidOption::OptionArgIsEmpty`.
+    Error,
+
+    /// Treats the option as specified without an argument, so the
+    /// `default_missing_value` is used if it is set, or
+    /// `InvalidOption::OptionNeedsArg` is returned otherwise.
+    AsMissing,
+}
+
 /// Enables to create a `OptCfg` instance in a manner similar to named
 /// parameters.
 #[allow(non_camel_case_types)]
@@ -145,15 +379,62 @@ pub enum OptCfgParam<'a> {
     /// Holds the value for `OptCfg#names`.
     names(&'a [&'a str]),
 
+    /// Holds the value for `OptCfg#hidden_names`.
+    hidden_names(&'a [&'a str]),
+
     /// Holds the value for `OptCfg#has_arg`.
     has_arg(bool),
 
     /// Holds the value for `OptCfg#is_array`.
     is_array(bool),
 
+    /// Holds the value for `OptCfg#unique`.
+    unique(bool),
+
+    /// Holds the value for `OptCfg#is_numeric`.
+    is_numeric(bool),
+
+    /// Holds the value for `OptCfg#accepts_si_suffix`.
+    accepts_si_suffix(bool),
+
+    /// Holds the value for `OptCfg#is_path`.
+    is_path(bool),
+
+    /// Holds the value for `OptCfg#complete`.
+    complete(ValueHint),
+
+    /// Holds the value for `OptCfg#trims_arg`.
+    trims_arg(bool),
+
+    /// Holds the value for `OptCfg#empty_arg`.
+    empty_arg(EmptyArgPolicy),
+
+    /// Holds the value for `OptCfg#raw_arg`.
+    raw_arg(bool),
+
+    /// Holds the value for `OptCfg#accepts_plus`.
+    accepts_plus(bool),
+
     /// Holds the value for `OptCfg#defaults`.
     defaults(&'a [&'a str]),
 
+    /// Holds an element for `OptCfg#defaults_if`.
+    /// This parameter can be specified multiple times to add multiple
+    /// conditions.
+    defaults_if(&'a str, Option<&'a str>, &'a [&'a str]),
+
+    /// Holds the value for `OptCfg#interpolates_defaults`.
+    interpolates_defaults(bool),
+
+    /// Holds the value for `OptCfg#default_fn`.
+    default_fn(fn() -> Vec<String>),
+
+    /// Holds the value for `OptCfg#default_missing_value`.
+    default_missing_value(&'a str),
+
+    /// Holds the value for `OptCfg#confirm`.
+    confirm(&'a str),
+
     /// Holds the value for `OptCfg#desc`.
     desc(&'a str),
 
@@ -162,6 +443,172 @@ pub enum OptCfgParam<'a> {
 
     /// Holds the value for `OptCfg#validator`.
     validator(fn(&str, &str, &str) -> Result<(), InvalidOption>),
+
+    /// Holds the value for `OptCfg#on_parse`.
+    on_parse(fn(&str, &str, &str)),
+}
+
+/// Checks the specified option configurations and returns all errors found
+/// in them.
+///
+/// This function checks the same things as `Cmd#parse_with` checks before
+/// parsing: duplicated store keys, duplicated option names, and options which
+/// are arrays or have default values but take no argument.
+/// Whereas `Cmd#parse_with` stops at the first error, this function reports
+/// all of them at once, so it is suitable for unit tests and startup checks.
+///
+/// ```
+/// use cliargs::{validate_cfgs, OptCfg};
+/// use cliargs::OptCfgParam::{names, is_array};
+/// use cliargs::errors::InvalidOption;
+///
+/// let opt_cfgs = vec![
+///     OptCfg::with(&[names(&["foo", "f"]), is_array(true)]),
+///     OptCfg::with(&[names(&["bar", "f"])]),
+/// ];
+/// let errs = validate_cfgs(&opt_cfgs);
+/// assert_eq!(errs.len(), 2);
+/// assert_eq!(errs[0], InvalidOption::ConfigIsArrayButHasNoArg {
+///     store_key: "foo".to_string(),
+///     name: "foo".to_string(),
+/// });
+/// assert_eq!(errs[1], InvalidOption::OptionNameIsDuplicated {
+///     store_key: "bar".to_string(),
+///     name: "f".to_string(),
+/// });
+/// ```
+pub fn validate_cfgs(opt_cfgs: &[OptCfg]) -> Vec<InvalidOption> {
+    let mut errs = Vec::new();
+    let mut key_set = HashSet::<&str>::new();
+    let mut name_set = HashSet::<&str>::new();
+
+    for cfg in opt_cfgs.iter() {
+        let store_key: &str = if cfg.store_key.is_empty() && !cfg.names.is_empty() {
+            &cfg.names[0]
+        } else {
+            &cfg.store_key
+        };
+
+        if store_key.is_empty() || store_key == "*" {
+            continue;
+        }
+
+        let first_name = if cfg.names.is_empty() {
+            store_key
+        } else {
+            &cfg.names[0]
+        };
+
+        if !key_set.insert(store_key) {
+            errs.push(InvalidOption::StoreKeyIsDuplicated {
+                store_key: store_key.to_string(),
+                name: first_name.to_string(),
+            });
+        }
+
+        if !cfg.has_arg {
+            if cfg.is_array {
+                errs.push(InvalidOption::ConfigIsArrayButHasNoArg {
+                    store_key: store_key.to_string(),
+                    name: first_name.to_string(),
+                });
+            }
+            if cfg.defaults.as_ref().is_some_and(|vec| !vec.is_empty())
+                || cfg.defaults_if.iter().any(|(_, _, vec)| !vec.is_empty())
+                || cfg.default_fn.is_some()
+            {
+                errs.push(InvalidOption::ConfigHasDefaultsButHasNoArg {
+                    store_key: store_key.to_string(),
+                    name: first_name.to_string(),
+                });
+            }
+        }
+
+        if cfg.names.is_empty() {
+            name_set.insert(first_name);
+        }
+        for name in cfg.names.iter() {
+            if !name_set.insert(name) {
+                errs.push(InvalidOption::OptionNameIsDuplicated {
+                    store_key: store_key.to_string(),
+                    name: name.to_string(),
+                });
+            }
+        }
+    }
+
+    errs
+}
+
+/// Assigns a short option name to each option configuration which has a long
+/// name but no short name.
+///
+/// The short name is the first letter of the first long name which is not
+/// used by other options yet.
+/// The short names already specified in the configurations are reserved
+/// before assigning.
+/// If all letters of a long name are used, this function returns
+/// `InvalidOption::ShortNameIsNotAssignable` and changes no configuration.
+///
+/// ```
+/// use cliargs::{assign_short_names, OptCfg};
+/// use cliargs::OptCfgParam::names;
+///
+/// let mut opt_cfgs = vec![
+///     OptCfg::with(&[names(&["verbose"])]),
+///     OptCfg::with(&[names(&["version"])]),
+///     OptCfg::with(&[names(&["force", "F"])]),
+/// ];
+/// assign_short_names(&mut opt_cfgs).unwrap();
+/// assert_eq!(opt_cfgs[0].names, ["verbose", "v"]);
+/// assert_eq!(opt_cfgs[1].names, ["version", "e"]);
+/// assert_eq!(opt_cfgs[2].names, ["force", "F"]);
+/// ```
+pub fn assign_short_names(opt_cfgs: &mut [OptCfg]) -> Result<(), InvalidOption> {
+    let mut used = HashSet::<char>::new();
+    for cfg in opt_cfgs.iter() {
+        for name in cfg.names.iter() {
+            let mut chars = name.chars();
+            if let (Some(ch), None) = (chars.next(), chars.next()) {
+                used.insert(ch);
+            }
+        }
+    }
+
+    let mut assigned = Vec::<(usize, char)>::new();
+    for (i, cfg) in opt_cfgs.iter().enumerate() {
+        if cfg.names.iter().any(|n| n.chars().count() == 1) {
+            continue;
+        }
+        let Some(long_name) = cfg.names.first() else {
+            continue;
+        };
+        match long_name
+            .chars()
+            .find(|ch| ch.is_ascii_alphabetic() && !used.contains(ch))
+        {
+            Some(ch) => {
+                used.insert(ch);
+                assigned.push((i, ch));
+            }
+            None => {
+                let store_key = if cfg.store_key.is_empty() {
+                    long_name
+                } else {
+                    &cfg.store_key
+                };
+                return Err(InvalidOption::ShortNameIsNotAssignable {
+                    store_key: store_key.to_string(),
+                    name: long_name.to_string(),
+                });
+            }
+        }
+    }
+
+    for (i, ch) in assigned {
+        opt_cfgs[i].names.push(ch.to_string());
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -177,8 +624,8 @@ mod tests_of_opt_cfg {
 
             assert_eq!(cfg.store_key, "fooBar");
             assert_eq!(cfg.names, Vec::<String>::new());
-            assert_eq!(cfg.has_arg, false);
-            assert_eq!(cfg.is_array, false);
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
             assert_eq!(cfg.defaults, None);
             assert_eq!(cfg.desc, "");
             assert_eq!(cfg.arg_in_help, "");
@@ -192,8 +639,8 @@ mod tests_of_opt_cfg {
 
             assert_eq!(cfg.store_key, "");
             assert_eq!(cfg.names, vec!["foo-bar".to_string(), "f".to_string()]);
-            assert_eq!(cfg.has_arg, false);
-            assert_eq!(cfg.is_array, false);
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
             assert_eq!(cfg.defaults, None);
             assert_eq!(cfg.desc, "");
             assert_eq!(cfg.arg_in_help, "");
@@ -207,8 +654,8 @@ mod tests_of_opt_cfg {
 
             assert_eq!(cfg.store_key, "");
             assert_eq!(cfg.names, Vec::<String>::new());
-            assert_eq!(cfg.has_arg, true);
-            assert_eq!(cfg.is_array, false);
+            assert!(cfg.has_arg);
+            assert!(!cfg.is_array);
             assert_eq!(cfg.defaults, None);
             assert_eq!(cfg.desc, "");
             assert_eq!(cfg.arg_in_help, "");
@@ -222,8 +669,57 @@ mod tests_of_opt_cfg {
 
             assert_eq!(cfg.store_key, "");
             assert_eq!(cfg.names, Vec::<String>::new());
-            assert_eq!(cfg.has_arg, false);
-            assert_eq!(cfg.is_array, true);
+            assert!(!cfg.has_arg);
+            assert!(cfg.is_array);
+            assert_eq!(cfg.defaults, None);
+            assert_eq!(cfg.desc, "");
+            assert_eq!(cfg.arg_in_help, "");
+
+            assert_eq!((cfg.validator)("a", "b", "c"), Ok(()));
+        }
+
+        #[test]
+        fn test_of_unique() {
+            let cfg = OptCfg::with(&[OptCfgParam::unique(true)]);
+
+            assert_eq!(cfg.store_key, "");
+            assert_eq!(cfg.names, Vec::<String>::new());
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
+            assert!(cfg.unique);
+            assert_eq!(cfg.defaults, None);
+            assert_eq!(cfg.desc, "");
+            assert_eq!(cfg.arg_in_help, "");
+
+            assert_eq!((cfg.validator)("a", "b", "c"), Ok(()));
+        }
+
+        #[test]
+        fn test_of_is_numeric() {
+            let cfg = OptCfg::with(&[OptCfgParam::is_numeric(true)]);
+
+            assert_eq!(cfg.store_key, "");
+            assert_eq!(cfg.names, Vec::<String>::new());
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
+            assert!(cfg.is_numeric);
+            assert!(!cfg.accepts_plus);
+            assert_eq!(cfg.defaults, None);
+            assert_eq!(cfg.desc, "");
+            assert_eq!(cfg.arg_in_help, "");
+
+            assert_eq!((cfg.validator)("a", "b", "c"), Ok(()));
+        }
+
+        #[test]
+        fn test_of_accepts_plus() {
+            let cfg = OptCfg::with(&[OptCfgParam::accepts_plus(true)]);
+
+            assert_eq!(cfg.store_key, "");
+            assert_eq!(cfg.names, Vec::<String>::new());
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
+            assert!(cfg.accepts_plus);
             assert_eq!(cfg.defaults, None);
             assert_eq!(cfg.desc, "");
             assert_eq!(cfg.arg_in_help, "");
@@ -237,8 +733,8 @@ mod tests_of_opt_cfg {
 
             assert_eq!(cfg.store_key, "");
             assert_eq!(cfg.names, Vec::<String>::new());
-            assert_eq!(cfg.has_arg, false);
-            assert_eq!(cfg.is_array, false);
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
             assert_eq!(
                 cfg.defaults,
                 Some(vec!["123".to_string(), "456".to_string()])
@@ -255,8 +751,8 @@ mod tests_of_opt_cfg {
 
             assert_eq!(cfg.store_key, "");
             assert_eq!(cfg.names, Vec::<String>::new());
-            assert_eq!(cfg.has_arg, false);
-            assert_eq!(cfg.is_array, false);
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
             assert_eq!(cfg.defaults, None);
             assert_eq!(cfg.desc, "description");
             assert_eq!(cfg.arg_in_help, "");
@@ -270,8 +766,8 @@ mod tests_of_opt_cfg {
 
             assert_eq!(cfg.store_key, "");
             assert_eq!(cfg.names, Vec::<String>::new());
-            assert_eq!(cfg.has_arg, false);
-            assert_eq!(cfg.is_array, false);
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
             assert_eq!(cfg.defaults, None);
             assert_eq!(cfg.desc, "");
             assert_eq!(cfg.arg_in_help, "<num>");
@@ -292,14 +788,14 @@ mod tests_of_opt_cfg {
 
             assert_eq!(cfg.store_key, "");
             assert_eq!(cfg.names, Vec::<String>::new());
-            assert_eq!(cfg.has_arg, false);
-            assert_eq!(cfg.is_array, false);
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
             assert_eq!(cfg.defaults, None);
             assert_eq!(cfg.desc, "");
             assert_eq!(cfg.arg_in_help, "");
 
             match (cfg.validator)("a", "b", "c") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -311,24 +807,217 @@ mod tests_of_opt_cfg {
                     assert_eq!(opt_arg, "c");
                     assert_eq!(details, "fail to parse integer");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
+        #[test]
+        fn test_of_on_parse() {
+            let cfg = OptCfg::with(&[OptCfgParam::on_parse(|key, name, arg| {
+                assert_eq!(key, "a");
+                assert_eq!(name, "b");
+                assert_eq!(arg, "c");
+            })]);
+
+            assert_eq!(cfg.store_key, "");
+            assert_eq!(cfg.names, Vec::<String>::new());
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
+            assert_eq!(cfg.defaults, None);
+            assert_eq!(cfg.desc, "");
+            assert_eq!(cfg.arg_in_help, "");
+
+            assert_eq!((cfg.validator)("a", "b", "c"), Ok(()));
+            (cfg.on_parse)("a", "b", "c");
+        }
+
+        #[test]
+        fn test_of_default() {
+            let cfg = OptCfg::default();
+
+            assert_eq!(cfg.store_key, "");
+            assert_eq!(cfg.names, Vec::<String>::new());
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
+            assert_eq!(cfg.defaults, None);
+            assert_eq!(cfg.desc, "");
+            assert_eq!(cfg.arg_in_help, "");
+
+            assert_eq!((cfg.validator)("a", "b", "c"), Ok(()));
+        }
+
         #[test]
         fn test_of_debug() {
             let cfg = OptCfg {
                 store_key: "fooBar".to_string(),
                 names: vec!["foo-bar".to_string(), "baz".to_string()],
+                hidden_names: vec!["baz".to_string()],
                 has_arg: true,
                 is_array: true,
+                unique: true,
+                is_numeric: true,
+                accepts_si_suffix: true,
+                is_path: true,
+                complete: ValueHint::DirPath,
+                trims_arg: true,
+                empty_arg: EmptyArgPolicy::Error,
+                raw_arg: true,
+                accepts_plus: true,
                 defaults: Some(vec!["123".to_string(), "456".to_string()]),
+                defaults_if: vec![("qux".to_string(), None, vec!["0".to_string()])],
+                interpolates_defaults: true,
+                default_fn: None,
+                default_missing_value: Some("789".to_string()),
+                confirm: Some("Are you sure?".to_string()),
                 desc: "option description".to_string(),
                 arg_in_help: "<num>".to_string(),
                 validator: |_, _, _| Ok(()),
+                on_parse: |_, _, _| {},
             };
 
-            assert_eq!(format!("{cfg:?}"), "OptCfg { store_key: \"fooBar\", names: [\"foo-bar\", \"baz\"], has_arg: true, is_array: true, defaults: Some([\"123\", \"456\"]), desc: \"option description\", arg_in_help: \"<num>\" }");
+            assert_eq!(format!("{cfg:?}"), "OptCfg { store_key: \"fooBar\", names: [\"foo-bar\", \"baz\"], hidden_names: [\"baz\"], has_arg: true, is_array: true, unique: true, is_numeric: true, accepts_si_suffix: true, is_path: true, complete: DirPath, trims_arg: true, empty_arg: Error, raw_arg: true, accepts_plus: true, defaults: Some([\"123\", \"456\"]), defaults_if: [(\"qux\", None, [\"0\"])], interpolates_defaults: true, default_missing_value: Some(\"789\"), confirm: Some(\"Are you sure?\"), desc: \"option description\", arg_in_help: \"<num>\" }");
+        }
+    }
+
+    mod tests_of_validate_cfgs {
+        use super::*;
+
+        #[test]
+        fn should_return_empty_if_cfgs_are_valid() {
+            let cfgs = vec![
+                OptCfg::with(&[OptCfgParam::names(&["foo", "f"])]),
+                OptCfg::with(&[
+                    OptCfgParam::names(&["bar"]),
+                    OptCfgParam::has_arg(true),
+                    OptCfgParam::is_array(true),
+                    OptCfgParam::defaults(&["1", "2"]),
+                ]),
+                OptCfg::with(&[OptCfgParam::store_key("*")]),
+            ];
+            assert_eq!(validate_cfgs(&cfgs), vec![]);
+        }
+
+        #[test]
+        fn should_return_all_errors() {
+            let cfgs = vec![
+                OptCfg::with(&[
+                    OptCfgParam::names(&["foo", "f"]),
+                    OptCfgParam::is_array(true),
+                    OptCfgParam::defaults(&["1"]),
+                ]),
+                OptCfg::with(&[OptCfgParam::store_key("foo"), OptCfgParam::names(&["bar"])]),
+                OptCfg::with(&[OptCfgParam::names(&["baz", "f"])]),
+                OptCfg::with(&[
+                    OptCfgParam::names(&["qux"]),
+                    OptCfgParam::defaults_if("baz", None, &["1"]),
+                ]),
+            ];
+            assert_eq!(
+                validate_cfgs(&cfgs),
+                vec![
+                    InvalidOption::ConfigIsArrayButHasNoArg {
+                        store_key: "foo".to_string(),
+                        name: "foo".to_string(),
+                    },
+                    InvalidOption::ConfigHasDefaultsButHasNoArg {
+                        store_key: "foo".to_string(),
+                        name: "foo".to_string(),
+                    },
+                    InvalidOption::StoreKeyIsDuplicated {
+                        store_key: "foo".to_string(),
+                        name: "bar".to_string(),
+                    },
+                    InvalidOption::OptionNameIsDuplicated {
+                        store_key: "baz".to_string(),
+                        name: "f".to_string(),
+                    },
+                    InvalidOption::ConfigHasDefaultsButHasNoArg {
+                        store_key: "qux".to_string(),
+                        name: "qux".to_string(),
+                    },
+                ]
+            );
+        }
+    }
+
+    mod tests_of_assign_short_names {
+        use super::*;
+
+        #[test]
+        fn should_assign_first_free_letters() {
+            let mut cfgs = vec![
+                OptCfg::with(&[OptCfgParam::names(&["dry-run"])]),
+                OptCfg::with(&[OptCfgParam::names(&["debug"])]),
+                OptCfg::with(&[OptCfgParam::names(&["exclude", "d"])]),
+                OptCfg::with(&[OptCfgParam::names(&["2fa"])]),
+                OptCfg::with(&[OptCfgParam::store_key("*")]),
+            ];
+            assert_eq!(assign_short_names(&mut cfgs), Ok(()));
+            assert_eq!(cfgs[0].names, ["dry-run", "r"]);
+            assert_eq!(cfgs[1].names, ["debug", "e"]);
+            assert_eq!(cfgs[2].names, ["exclude", "d"]);
+            assert_eq!(cfgs[3].names, ["2fa", "f"]);
+            assert_eq!(cfgs[4].names, Vec::<String>::new());
+        }
+
+        #[test]
+        fn should_fail_if_letters_collide() {
+            let mut cfgs = vec![
+                OptCfg::with(&[OptCfgParam::names(&["ab"])]),
+                OptCfg::with(&[OptCfgParam::names(&["ba"])]),
+                OptCfg::with(&[OptCfgParam::store_key("aB"), OptCfgParam::names(&["aab"])]),
+            ];
+            assert_eq!(
+                assign_short_names(&mut cfgs),
+                Err(InvalidOption::ShortNameIsNotAssignable {
+                    store_key: "aB".to_string(),
+                    name: "aab".to_string(),
+                })
+            );
+            assert_eq!(cfgs[0].names, ["ab"]);
+            assert_eq!(cfgs[1].names, ["ba"]);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod tests_of_serde {
+        use super::*;
+
+        #[test]
+        fn should_serialize_and_deserialize() {
+            let cfg = OptCfg::with(&[
+                OptCfgParam::store_key("fooBar"),
+                OptCfgParam::names(&["foo-bar", "f"]),
+                OptCfgParam::has_arg(true),
+                OptCfgParam::defaults(&["1"]),
+                OptCfgParam::desc("foo-bar option"),
+                OptCfgParam::validator(|_, _, _| {
+                    Err(InvalidOption::OptionArgIsInvalid {
+                        store_key: "".to_string(),
+                        option: "".to_string(),
+                        opt_arg: "".to_string(),
+                        details: "".to_string(),
+                    })
+                }),
+            ]);
+
+            let json = serde_json::to_string(&cfg).unwrap();
+            assert_eq!(
+                json,
+                "{\"store_key\":\"fooBar\",\"names\":[\"foo-bar\",\"f\"],\"hidden_names\":[],\"has_arg\":true,\"is_array\":false,\"unique\":false,\"is_numeric\":false,\"accepts_si_suffix\":false,\"is_path\":false,\"complete\":\"Unknown\",\"trims_arg\":false,\"empty_arg\":\"Allow\",\"raw_arg\":false,\"accepts_plus\":false,\"defaults\":[\"1\"],\"defaults_if\":[],\"interpolates_defaults\":false,\"default_missing_value\":null,\"confirm\":null,\"desc\":\"foo-bar option\",\"arg_in_help\":\"\"}"
+            );
+
+            let cfg2: OptCfg = serde_json::from_str(&json).unwrap();
+            assert_eq!(format!("{cfg2:?}"), format!("{cfg:?}"));
+            assert_eq!((cfg2.validator)("a", "b", "c"), Ok(()));
+        }
+
+        #[test]
+        fn should_deserialize_with_missing_fields() {
+            let cfg: OptCfg = serde_json::from_str("{\"names\":[\"foo\"]}").unwrap();
+            assert_eq!(cfg.names, vec!["foo".to_string()]);
+            assert!(!cfg.has_arg);
+            assert_eq!(cfg.defaults, None);
         }
     }
 }
@@ -0,0 +1,284 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::{find_abbrev, parse_args};
+use crate::Cmd;
+use crate::OptCfg;
+use crate::ParseOptions;
+use std::collections::HashMap;
+
+/// The enum type for the kinds of command line arguments, which are labeled
+/// by `Cmd#classify_with` and `App#classify`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind<'a> {
+    /// Indicates the command name, which is the first command line argument.
+    Command,
+
+    /// Indicates an option, like `--foo`, `--foo=bar`, `-f`, or `-abc`.
+    ///
+    /// `names` are the option names in the argument, which are more than one
+    /// for combined short options like `-abc`.
+    Option {
+        /// The option names in the argument.
+        names: Vec<&'a str>,
+    },
+
+    /// Indicates an option argument which is separated from its option, like
+    /// `bar` of `--foo bar`.
+    OptArg {
+        /// The name of the option which takes this argument.
+        of: &'a str,
+    },
+
+    /// Indicates a command argument.
+    Positional,
+
+    /// Indicates the end of options, `--`.
+    Terminator,
+
+    /// Indicates a sub command name.
+    SubCmd,
+
+    /// Indicates an argument which looks like an option but is malformed,
+    /// like `--foo@bar`.
+    Invalid,
+}
+
+impl<'a> Cmd<'a> {
+    /// Labels each command line argument of this `Cmd` instance with its kind
+    /// as `parse_with` sees it with the specified option configurations and
+    /// the switches set with `with_parse_options`.
+    ///
+    /// The returned vector has the same length as the command line arguments,
+    /// and its first element is `TokenKind::Command`.
+    /// This method does not modify this `Cmd` instance, and does not fail
+    /// even if the command line arguments are invalid, so that it can be used
+    /// for syntax highlighting or completion of incomplete command lines.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg, TokenKind};
+    /// use cliargs::OptCfgParam::{names, has_arg};
+    ///
+    /// let cmd = Cmd::with_strings(["app", "-v", "--out", "x", "in", "--", "-y"].map(String::from));
+    /// let opt_cfgs = vec![
+    ///     OptCfg::with(&[names(&["verbose", "v"])]),
+    ///     OptCfg::with(&[names(&["out"]), has_arg(true)]),
+    /// ];
+    ///
+    /// assert_eq!(cmd.classify_with(&opt_cfgs), [
+    ///     TokenKind::Command,
+    ///     TokenKind::Option { names: vec!["v"] },
+    ///     TokenKind::Option { names: vec!["out"] },
+    ///     TokenKind::OptArg { of: "out" },
+    ///     TokenKind::Positional,
+    ///     TokenKind::Terminator,
+    ///     TokenKind::Positional,
+    /// ]);
+    /// ```
+    pub fn classify_with(&self, opt_cfgs: &[OptCfg]) -> Vec<TokenKind<'a>> {
+        if self._arg_refs.is_empty() {
+            return Vec::new();
+        }
+
+        let mut kinds = vec![TokenKind::Command];
+        if self.is_after_end_opt {
+            kinds.resize(self._arg_refs.len(), TokenKind::Positional);
+        } else {
+            let args = &self._arg_refs[1..];
+            kinds.extend(classify_args(args, opt_cfgs, &self.parse_options, false).0);
+        }
+        kinds
+    }
+}
+
+/// Labels the specified command line arguments, which do not include a
+/// command name, and returns the labels and the index of the first command
+/// argument if `until_1st_arg` or `options.stops_at_first_arg` is `true`.
+///
+/// If `until_1st_arg` or `options.stops_at_first_arg` is `true`, the
+/// arguments after the first command argument are labeled as
+/// `TokenKind::Positional`.
+pub(crate) fn classify_args<'a>(
+    args: &[&'a str],
+    opt_cfgs: &[OptCfg],
+    options: &ParseOptions,
+    until_1st_arg: bool,
+) -> (Vec<TokenKind<'a>>, Option<usize>) {
+    let mut cfg_map = HashMap::<&str, usize>::new();
+    let mut allows_underscore = false;
+    for (i, cfg) in opt_cfgs.iter().enumerate() {
+        if cfg.names.is_empty() {
+            cfg_map.insert(&cfg.store_key, i);
+        } else {
+            for name in cfg.names.iter() {
+                cfg_map.insert(name, i);
+            }
+        }
+        if cfg.names.iter().any(|n| n.contains('_'))
+            || (cfg.names.is_empty() && cfg.store_key.contains('_'))
+        {
+            allows_underscore = true;
+        }
+    }
+
+    let mut positionals: Vec<&'a str> = Vec::new();
+    let mut opts: Vec<(&'a str, Option<&'a str>, usize)> = Vec::new();
+
+    let result = parse_args(
+        args,
+        |arg| positionals.push(arg),
+        |name, arg_op, index| {
+            opts.push((name, arg_op, index));
+            Ok(())
+        },
+        |name| match cfg_map.get(name) {
+            Some(i) => Some(&opt_cfgs[*i]),
+            None if options.allows_abbrev => find_abbrev(name, &cfg_map).map(|i| &opt_cfgs[i]),
+            None => None,
+        },
+        |_, _| {},
+        options.flags(until_1st_arg, allows_underscore),
+    );
+
+    let mut kinds: Vec<Option<TokenKind<'a>>> = vec![None; args.len()];
+
+    for (name, arg_op, index) in opts {
+        let i = index - 1;
+        match &mut kinds[i] {
+            Some(TokenKind::Option { names }) => names.push(name),
+            kind => *kind = Some(TokenKind::Option { names: vec![name] }),
+        }
+        if let Some(arg) = arg_op {
+            if i + 1 < args.len() && is_same_str(arg, args[i + 1]) && !is_in(arg, args[i]) {
+                kinds[i + 1] = Some(TokenKind::OptArg { of: name });
+            }
+        }
+    }
+
+    let mut cursor = 0;
+    for arg in positionals {
+        while cursor < args.len() {
+            cursor += 1;
+            if is_same_str(arg, args[cursor - 1]) {
+                kinds[cursor - 1] = Some(TokenKind::Positional);
+                break;
+            }
+        }
+    }
+
+    let first_arg = match result {
+        Ok(first_arg) => first_arg,
+        Err(_) if until_1st_arg || options.stops_at_first_arg => {
+            let start = kinds.iter().rposition(|k| k.is_some()).map_or(0, |i| i + 1);
+            (start..args.len()).find(|i| !args[*i].starts_with('-'))
+        }
+        Err(_) => None,
+    };
+
+    let kinds = kinds
+        .into_iter()
+        .enumerate()
+        .map(|(i, kind)| match kind {
+            Some(kind) => kind,
+            None if first_arg.is_some_and(|n| i >= n) => TokenKind::Positional,
+            None if args[i] == "--" => TokenKind::Terminator,
+            None => TokenKind::Invalid,
+        })
+        .collect();
+
+    (kinds, first_arg)
+}
+
+fn is_same_str(a: &str, b: &str) -> bool {
+    a.as_ptr() == b.as_ptr() && a.len() == b.len()
+}
+
+fn is_in(part: &str, whole: &str) -> bool {
+    let range = whole.as_bytes().as_ptr_range();
+    range.contains(&part.as_ptr()) || (part.is_empty() && part.as_ptr() == range.end)
+}
+
+#[cfg(test)]
+mod tests_of_classify_with {
+    use super::*;
+    use crate::OptCfgParam::*;
+
+    fn new_cmd<'a>(args: &[&str]) -> Cmd<'a> {
+        Cmd::with_strings(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn should_classify_args() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo", "f"]), has_arg(true)]),
+            OptCfg::with(&[names(&["bar", "b"])]),
+            OptCfg::with(&[names(&["num", "n"]), has_arg(true), is_numeric(true)]),
+        ];
+
+        let cmd = new_cmd(&[
+            "app", "--foo", "x", "-bf", "y", "--foo=z", "-n3", "a", "-", "--", "--bar",
+        ]);
+        assert_eq!(
+            cmd.classify_with(&opt_cfgs),
+            [
+                TokenKind::Command,
+                TokenKind::Option { names: vec!["foo"] },
+                TokenKind::OptArg { of: "foo" },
+                TokenKind::Option {
+                    names: vec!["b", "f"]
+                },
+                TokenKind::OptArg { of: "f" },
+                TokenKind::Option { names: vec!["foo"] },
+                TokenKind::Option { names: vec!["n"] },
+                TokenKind::Positional,
+                TokenKind::Positional,
+                TokenKind::Terminator,
+                TokenKind::Positional,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_classify_invalid_and_unconfigured_args() {
+        let opt_cfgs = vec![OptCfg::with(&[names(&["foo"]), has_arg(true)])];
+
+        let cmd = new_cmd(&["app", "--b@r", "--baz", "--foo", "--", "--foo"]);
+        assert_eq!(
+            cmd.classify_with(&opt_cfgs),
+            [
+                TokenKind::Command,
+                TokenKind::Invalid,
+                TokenKind::Option { names: vec!["baz"] },
+                TokenKind::Option { names: vec!["foo"] },
+                TokenKind::OptArg { of: "foo" },
+                TokenKind::Option { names: vec!["foo"] },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_classify_args_after_end_opt() {
+        let cmd = Cmd::with_strings_after_end_opt(["app", "-a"].map(String::from), true);
+        assert_eq!(
+            cmd.classify_with(&[]),
+            [TokenKind::Command, TokenKind::Positional]
+        );
+    }
+
+    #[test]
+    fn should_stop_at_first_arg() {
+        let args = ["-a", "--b@r", "sub", "-c"];
+        let (kinds, first_arg) = classify_args(&args, &[], &ParseOptions::default(), true);
+        assert_eq!(first_arg, Some(2));
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::Option { names: vec!["a"] },
+                TokenKind::Invalid,
+                TokenKind::Positional,
+                TokenKind::Positional,
+            ]
+        );
+    }
+}
@@ -2,22 +2,64 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
+mod classify;
+mod options;
+// The module is named after `Cmd#parse`, which it implements.
+#[allow(clippy::module_inception)]
 mod parse;
+mod parse_or_exit;
 mod parse_with;
 
+pub(crate) use classify::classify_args;
+pub use classify::TokenKind;
+pub(crate) use options::find_abbrev;
+pub use options::{DuplicatePolicy, ParseOptions};
+
 use crate::errors::InvalidOption;
+use crate::OptCfg;
+
+/// The switches of `parse_args`.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Flags {
+    until_1st_arg: bool,
+    allows_underscore: bool,
+    allows_attached_arg: bool,
+    accepts_plus: bool,
+}
 
-fn parse_args<'a, F1, F2, F3>(
+fn parse_args<'a, 'c, F1, F2, F3, F4>(
     args: &[&'a str],
     mut collect_args: F1,
     mut collect_opts: F2,
-    take_args: F3,
-) -> Result<(), InvalidOption>
+    find_cfg: F3,
+    mut mark_sign: F4,
+    flags: Flags,
+) -> Result<Option<usize>, InvalidOption>
 where
     F1: FnMut(&'a str),
-    F2: FnMut(&'a str, Option<&'a str>) -> Result<(), InvalidOption>,
-    F3: Fn(&str) -> bool,
+    F2: FnMut(&'a str, Option<&'a str>, usize) -> Result<(), InvalidOption>,
+    F3: Fn(&str) -> Option<&'c OptCfg>,
+    F4: FnMut(&'a str, bool),
 {
+    let take_args = |name: &str| {
+        find_cfg(name).is_some_and(|cfg| cfg.has_arg && cfg.default_missing_value.is_none())
+    };
+    let accepts_plus =
+        |name: &str| flags.accepts_plus || find_cfg(name).is_some_and(|cfg| cfg.accepts_plus);
+    let takes_number = |name: &str| find_cfg(name).is_some_and(|cfg| cfg.has_arg && cfg.is_numeric);
+    let needs_arg_in_cluster = |name: &str, cluster: &str| {
+        let store_key = match find_cfg(name) {
+            Some(cfg) if !cfg.store_key.is_empty() => cfg.store_key.as_str(),
+            Some(cfg) if !cfg.names.is_empty() => cfg.names[0].as_str(),
+            _ => name,
+        };
+        InvalidOption::OptionNeedsArgInCluster {
+            option: name.to_string(),
+            store_key: store_key.to_string(),
+            cluster: cluster.to_string(),
+        }
+    };
+
     let mut is_non_opt = false;
     let mut prev_opt_taking_args = "";
     let mut first_err: Option<InvalidOption> = None;
@@ -26,41 +68,34 @@ where
         if is_non_opt {
             collect_args(arg);
         } else if !prev_opt_taking_args.is_empty() {
-            match collect_opts(prev_opt_taking_args, Some(arg)) {
-                Err(err) => {
-                    if first_err == None {
-                        first_err = Some(err);
-                    }
-                    continue 'L0;
+            if let Err(err) = collect_opts(prev_opt_taking_args, Some(arg), i_arg) {
+                if first_err.is_none() {
+                    first_err = Some(err);
                 }
-                Ok(_) => {}
+                continue 'L0;
             }
             prev_opt_taking_args = "";
-        } else if arg.starts_with("--") {
-            if arg.len() == 2 {
+        } else if let Some(arg) = arg.strip_prefix("--") {
+            if arg.is_empty() {
                 is_non_opt = true;
                 continue 'L0;
             }
 
-            let arg = &arg[2..];
             let mut i = 0;
 
             for ch in arg.chars() {
                 if i > 0 {
                     if ch == '=' {
-                        match collect_opts(&arg[0..i], Some(&arg[i + 1..])) {
-                            Err(err) => {
-                                if first_err == None {
-                                    first_err = Some(err);
-                                }
-                                continue 'L0;
+                        if let Err(err) = collect_opts(&arg[0..i], Some(&arg[i + 1..]), i_arg + 1) {
+                            if first_err.is_none() {
+                                first_err = Some(err);
                             }
-                            Ok(_) => {}
+                            continue 'L0;
                         }
                         break;
                     }
-                    if !is_allowed_character(ch) {
-                        if first_err == None {
+                    if !(is_allowed_character(ch) || (flags.allows_underscore && ch == '_')) {
+                        if first_err.is_none() {
                             first_err = Some(InvalidOption::OptionContainsInvalidChar {
                                 option: String::from(arg),
                             });
@@ -69,7 +104,7 @@ where
                     }
                 } else {
                     if !is_allowed_first_character(ch) {
-                        if first_err == None {
+                        if first_err.is_none() {
                             first_err = Some(InvalidOption::OptionContainsInvalidChar {
                                 option: String::from(arg),
                             });
@@ -85,88 +120,125 @@ where
                     prev_opt_taking_args = arg;
                     continue 'L0;
                 }
-                match collect_opts(arg, None) {
-                    Err(err) => {
-                        if first_err == None {
-                            first_err = Some(err);
-                        }
-                        continue 'L0;
+                if let Err(err) = collect_opts(arg, None, i_arg + 1) {
+                    if first_err.is_none() {
+                        first_err = Some(err);
                     }
-                    Ok(_) => {}
+                    continue 'L0;
                 }
             }
-        } else if arg.starts_with("-") {
-            if arg.len() == 1 {
+        } else if let Some((short, plus)) = strip_short_prefix(arg, &accepts_plus) {
+            if short.is_empty() {
                 collect_args(arg);
                 continue 'L0;
             }
 
-            let arg = &arg[1..];
+            let arg = short;
             let mut name: &str = "";
-            let mut i = 0;
 
-            for ch in arg.chars() {
+            for (i, ch) in arg.char_indices() {
                 if i > 0 {
+                    if ch.is_ascii_digit() && !name.is_empty() && takes_number(name) {
+                        if let Err(err) = collect_opts(name, Some(&arg[i..]), i_arg + 1) {
+                            if first_err.is_none() {
+                                first_err = Some(err);
+                            }
+                        }
+                        continue 'L0;
+                    }
                     if ch == '=' {
                         if !name.is_empty() {
-                            match collect_opts(name, Some(&arg[i + 1..])) {
-                                Err(err) => {
-                                    if first_err == None {
-                                        first_err = Some(err);
-                                    }
+                            if let Err(err) = collect_opts(name, Some(&arg[i + 1..]), i_arg + 1) {
+                                if first_err.is_none() {
+                                    first_err = Some(err);
                                 }
-                                Ok(_) => {}
+                            }
+                        }
+                        continue 'L0;
+                    }
+                    if !name.is_empty() && flags.allows_attached_arg && take_args(name) {
+                        if let Err(err) = collect_opts(name, Some(&arg[i..]), i_arg + 1) {
+                            if first_err.is_none() {
+                                first_err = Some(err);
                             }
                         }
                         continue 'L0;
                     }
                     if !name.is_empty() {
-                        match collect_opts(name, None) {
-                            Err(err) => {
-                                if first_err == None {
-                                    first_err = Some(err);
-                                }
+                        if take_args(name) && is_allowed_first_character(ch) {
+                            if first_err.is_none() {
+                                first_err = Some(needs_arg_in_cluster(name, args[i_arg]));
+                            }
+                        } else if let Err(err) = collect_opts(name, None, i_arg + 1) {
+                            if first_err.is_none() {
+                                first_err = Some(err);
                             }
-                            Ok(_) => {}
                         }
                     }
                 }
                 if !is_allowed_first_character(ch) {
-                    if first_err == None {
+                    if first_err.is_none() {
                         first_err = Some(InvalidOption::OptionContainsInvalidChar {
-                            option: String::from(&arg[i..i + 1]),
+                            option: ch.to_string(),
                         });
                     }
                     name = "";
                 } else {
-                    name = &arg[i..i + 1];
+                    name = &arg[i..i + ch.len_utf8()];
+                    mark_sign(name, plus);
                 }
-                i += 1;
             }
 
-            if i == arg.len() && !name.is_empty() {
+            if !name.is_empty() {
                 if take_args(name) && i_arg < args.len() - 1 {
                     prev_opt_taking_args = name;
                 } else {
-                    match collect_opts(name, None) {
-                        Err(err) => {
-                            if first_err == None {
-                                first_err = Some(err);
-                            }
-                            continue 'L0;
+                    if let Err(err) = collect_opts(name, None, i_arg + 1) {
+                        if first_err.is_none() {
+                            first_err = Some(err);
                         }
-                        Ok(_) => {}
+                        continue 'L0;
                     }
                 }
             }
         } else {
+            if flags.until_1st_arg {
+                return match first_err {
+                    Some(err) => Err(err),
+                    None => Ok(Some(i_arg)),
+                };
+            }
             collect_args(arg);
         }
     }
 
     match first_err {
         Some(err) => Err(err),
-        None => Ok(()),
+        None => Ok(None),
+    }
+}
+
+fn strip_short_prefix<'a, F>(arg: &'a str, accepts_plus: &F) -> Option<(&'a str, bool)>
+where
+    F: Fn(&str) -> bool,
+{
+    if let Some(short) = arg.strip_prefix('-') {
+        return Some((short, false));
+    }
+
+    let short = arg.strip_prefix('+')?;
+    let names = short.split('=').next().unwrap_or("");
+    if names.is_empty() {
+        return None;
+    }
+    let mut buf = [0u8; 4];
+    if names
+        .chars()
+        .all(|ch| accepts_plus(ch.encode_utf8(&mut buf)))
+    {
+        Some((short, true))
+    } else {
+        None
     }
 }
 
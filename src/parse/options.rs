@@ -0,0 +1,375 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use super::Flags;
+use crate::Cmd;
+use std::collections::HashMap;
+
+/// The enum type for the policies of how to treat an option which cannot
+/// take multiple arguments but is given more than once, like `--out a --out b`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Returns `InvalidOption::OptionIsNotArray`.
+    #[default]
+    Error,
+
+    /// Uses the first option argument and ignores the others.
+    FirstWins,
+
+    /// Uses the last option argument and ignores the others.
+    LastWins,
+}
+
+/// The struct type for the switches of parsing behaviors.
+///
+/// An instance of this struct is set to a `Cmd` instance with
+/// `Cmd#with_parse_options` or to an `App` instance with
+/// `App#parse_options`, and is applied by `parse`, `parse_with`, and other
+/// parsing methods.
+/// Since this struct is `Clone`, the same switches can be reused across
+/// commands.
+/// The switches are set with the builder-style methods named after the
+/// fields, since this struct may get more switches in the future.
+///
+/// ```
+/// use cliargs::{Cmd, OptCfg, ParseOptions, DuplicatePolicy};
+/// use cliargs::OptCfgParam::{names, has_arg};
+///
+/// let options = ParseOptions::new()
+///     .stops_at_first_arg(true)
+///     .duplicate_opt(DuplicatePolicy::LastWins);
+/// let opt_cfgs = vec![
+///     OptCfg::with(&[names(&["out", "o"]), has_arg(true)]),
+///     OptCfg::with(&[names(&["verbose", "v"])]),
+/// ];
+///
+/// let mut cmd = Cmd::with_strings(["app", "-o", "a", "-o", "b", "run", "-v"].map(String::from))
+///     .with_parse_options(options.clone());
+/// cmd.parse_with(&opt_cfgs).unwrap();
+/// assert_eq!(cmd.opt_arg("out"), Some("b"));
+/// assert_eq!(cmd.has_opt("verbose"), false);
+/// assert_eq!(cmd.args(), &["run", "-v"]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Is the flag which makes all arguments after the first command argument
+    /// command arguments even if they start with `-`, like POSIX.
+    pub stops_at_first_arg: bool,
+
+    /// Is the flag which allows a short option taking an argument to take the
+    /// rest of the argument as its option argument, like `-ofile` or `f` of
+    /// `-vffile`.
+    pub allows_attached_arg: bool,
+
+    /// Is the flag which allows all options to be specified with `+` instead
+    /// of `-`, like `OptCfg#accepts_plus`.
+    pub accepts_plus: bool,
+
+    /// Is the policy for an option which cannot take multiple arguments but
+    /// is given more than once.
+    pub duplicate_opt: DuplicatePolicy,
+
+    /// Is the flag which allows a long option to be abbreviated to two or
+    /// more leading characters, like `--verb` for `--verbose`, if no other
+    /// option starts with them.
+    /// An exact name takes precedence over abbreviations.
+    pub allows_abbrev: bool,
+
+    /// Is the flag which enables the strict mode, which rejects an option
+    /// configuration which was neither matched by any command line argument
+    /// nor applied with default values or environment variables, with
+    /// `InvalidOption::ConfigIsUnused` after parsing.
+    ///
+    /// This check is per invocation, so an ordinary invocation which omits an
+    /// optional option fails in this mode.
+    /// This mode is intended for tests which parse a command line exercising
+    /// all options, to catch dead or shadowed configurations in large,
+    /// composed option configurations.
+    pub rejects_unused_cfgs: bool,
+}
+
+impl ParseOptions {
+    /// Creates a `ParseOptions` instance of which all switches are off.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value for `ParseOptions#stops_at_first_arg`.
+    pub fn stops_at_first_arg(mut self, b: bool) -> Self {
+        self.stops_at_first_arg = b;
+        self
+    }
+
+    /// Sets the value for `ParseOptions#allows_attached_arg`.
+    pub fn allows_attached_arg(mut self, b: bool) -> Self {
+        self.allows_attached_arg = b;
+        self
+    }
+
+    /// Sets the value for `ParseOptions#accepts_plus`.
+    pub fn accepts_plus(mut self, b: bool) -> Self {
+        self.accepts_plus = b;
+        self
+    }
+
+    /// Sets the value for `ParseOptions#duplicate_opt`.
+    pub fn duplicate_opt(mut self, policy: DuplicatePolicy) -> Self {
+        self.duplicate_opt = policy;
+        self
+    }
+
+    /// Sets the value for `ParseOptions#allows_abbrev`.
+    pub fn allows_abbrev(mut self, b: bool) -> Self {
+        self.allows_abbrev = b;
+        self
+    }
+
+    /// Sets the value for `ParseOptions#rejects_unused_cfgs`.
+    pub fn rejects_unused_cfgs(mut self, b: bool) -> Self {
+        self.rejects_unused_cfgs = b;
+        self
+    }
+
+    pub(crate) fn flags(&self, until_1st_arg: bool, allows_underscore: bool) -> Flags {
+        Flags {
+            until_1st_arg: until_1st_arg || self.stops_at_first_arg,
+            allows_underscore,
+            allows_attached_arg: self.allows_attached_arg,
+            accepts_plus: self.accepts_plus,
+        }
+    }
+}
+
+impl<'a> Cmd<'a> {
+    /// Sets the switches of parsing behaviors, which are applied by `parse`,
+    /// `parse_with`, and other parsing methods.
+    ///
+    /// `allows_attached_arg`, `duplicate_opt`, `allows_abbrev`, and
+    /// `rejects_unused_cfgs` of the switches are applied only when parsing
+    /// with option configurations.
+    pub fn with_parse_options(mut self, options: ParseOptions) -> Self {
+        self.parse_options = options;
+        self
+    }
+
+    /// Returns the switches of parsing behaviors of this `Cmd` instance.
+    pub fn parse_options(&self) -> &ParseOptions {
+        &self.parse_options
+    }
+}
+
+/// Returns the value for the only option of which a long name starts with
+/// the specified abbreviation, or `None` if there is not such option or
+/// there are multiple such options.
+pub(crate) fn find_abbrev<V: Copy + PartialEq>(abbrev: &str, map: &HashMap<&str, V>) -> Option<V> {
+    if abbrev.chars().count() < 2 {
+        return None;
+    }
+
+    let mut found = None;
+    for (name, v) in map.iter() {
+        if name.len() > 1 && name.starts_with(abbrev) {
+            match found {
+                None => found = Some(*v),
+                Some(f) if f == *v => {}
+                Some(_) => return None,
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests_of_parse_options {
+    use super::*;
+    use crate::errors::InvalidOption;
+    use crate::OptCfg;
+    use crate::OptCfgParam::*;
+
+    fn new_cmd<'a>(args: &[&str], options: ParseOptions) -> Cmd<'a> {
+        Cmd::with_strings(args.iter().map(|s| s.to_string())).with_parse_options(options)
+    }
+
+    #[test]
+    fn should_stop_at_first_arg() {
+        let options = ParseOptions::new().stops_at_first_arg(true);
+
+        let mut cmd = new_cmd(&["app", "-a", "x", "-b", "--", "y"], options.clone());
+        match cmd.parse() {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("a"));
+        assert!(!cmd.has_opt("b"));
+        assert_eq!(cmd.args(), &["x", "-b", "--", "y"]);
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["a"])]),
+            OptCfg::with(&[names(&["b"])]),
+        ];
+        let mut cmd = new_cmd(&["app", "-a", "x", "-b"], options);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("a"));
+        assert!(!cmd.has_opt("b"));
+        assert_eq!(cmd.args(), &["x", "-b"]);
+    }
+
+    #[test]
+    fn should_take_attached_arg() {
+        let options = ParseOptions::new().allows_attached_arg(true);
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["out", "o"]), has_arg(true)]),
+            OptCfg::with(&[names(&["v"])]),
+        ];
+
+        let mut cmd = new_cmd(&["app", "-ofile", "-vo=x"], options);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionIsNotArray { option, .. }) => assert_eq!(option, "o"),
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("out"), Some("file"));
+        assert!(cmd.has_opt("v"));
+
+        let mut cmd = new_cmd(&["app", "-vofile"], ParseOptions::default());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionNeedsArgInCluster { option, .. }) => assert_eq!(option, "o"),
+            Err(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_accept_plus_for_all_options() {
+        let options = ParseOptions::new().accepts_plus(true);
+        let opt_cfgs = vec![OptCfg::with(&[names(&["x"])])];
+
+        let mut cmd = new_cmd(&["app", "+x"], options);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("x"));
+        assert!(cmd.is_plus_opt("x"));
+    }
+
+    #[test]
+    fn should_treat_duplicated_options_with_policy() {
+        let opt_cfgs = vec![OptCfg::with(&[names(&["out", "o"]), has_arg(true)])];
+        let args = ["app", "-o", "a", "--out=b"];
+
+        let mut cmd = new_cmd(&args, ParseOptions::default());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionIsNotArray { option, .. }) => assert_eq!(option, "out"),
+            Err(_) => panic!(),
+        }
+
+        let options = ParseOptions::new().duplicate_opt(DuplicatePolicy::FirstWins);
+        let mut cmd = new_cmd(&args, options);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_args("out"), Some(&["a"] as &[&str]));
+
+        let options = ParseOptions::new().duplicate_opt(DuplicatePolicy::LastWins);
+        let mut cmd = new_cmd(&args, options);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_args("out"), Some(&["b"] as &[&str]));
+    }
+
+    #[test]
+    fn should_accept_abbreviated_long_options() {
+        let options = ParseOptions::new().allows_abbrev(true);
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["verbose", "v"])]),
+            OptCfg::with(&[names(&["version"])]),
+            OptCfg::with(&[names(&["output", "out"]), has_arg(true)]),
+        ];
+
+        let mut cmd = new_cmd(&["app", "--verb", "--ou", "x", "--versi"], options.clone());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("verbose"));
+        assert!(cmd.has_opt("version"));
+        assert_eq!(cmd.opt_arg("output"), Some("x"));
+        assert_eq!(cmd.args(), &[] as &[&str]);
+
+        let mut cmd = new_cmd(&["app", "--ver"], options);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::UnconfiguredOption { option }) => assert_eq!(option, "ver"),
+            Err(_) => panic!(),
+        }
+
+        let mut cmd = new_cmd(&["app", "--verb"], ParseOptions::default());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::UnconfiguredOption { option }) => assert_eq!(option, "verb"),
+            Err(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_reject_unused_cfgs() {
+        let options = ParseOptions::new().rejects_unused_cfgs(true);
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["verbose", "v"])]),
+            OptCfg::with(&[names(&["level"]), has_arg(true), defaults(&["1"])]),
+            OptCfg::with(&[store_key("Color"), names(&["color", "colour"])]),
+            OptCfg::with(&[store_key("dry-run")]),
+            OptCfg::with(&[store_key("*")]),
+        ];
+
+        let mut cmd = new_cmd(&["app", "-v", "--colour", "--dry-run"], options.clone());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("level"), Some("1"));
+
+        let mut cmd = new_cmd(&["app", "-v", "--dry-run", "--foo"], options.clone());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::ConfigIsUnused {
+                store_key: key,
+                name,
+            }) => {
+                assert_eq!(key, "Color");
+                assert_eq!(name, "color");
+            }
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("verbose"));
+
+        let mut cmd = new_cmd(&["app", "-v", "--color"], options.clone());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::ConfigIsUnused {
+                store_key: key,
+                name,
+            }) => {
+                assert_eq!(key, "dry-run");
+                assert_eq!(name, "dry-run");
+            }
+            Err(_) => panic!(),
+        }
+
+        let mut cmd = new_cmd(&["app", "-v"], ParseOptions::default());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+    }
+}
@@ -5,6 +5,7 @@
 use super::parse_args;
 use crate::errors::InvalidOption;
 use crate::Cmd;
+use std::mem;
 
 impl<'a> Cmd<'a> {
     /// Parses command line arguments without configurations.
@@ -14,15 +15,22 @@ impl<'a> Cmd<'a> {
     /// GNU:
     /// arguments staring with `-` or `--` are treated as options, and
     /// others are treated as command arguments.
-    /// If an `=` is found within an option, the part before the `=` is treated
-    /// as the option name, and the part after the `=` is treated as the option
-    /// argument.
+    /// If an `=` is found within an option, the part before the first `=` is
+    /// treated as the option name, and the whole part after it is treated as
+    /// the option argument, so `--filter=key=value` has the argument
+    /// `key=value`.
     /// Options starting with `--` are long options and option starting with
     /// `-` are short options.
     /// Multiple short options can be concatenated into a single command line
     /// argument.
     /// If an argument is exactly `--`, all subsequent arguments are treated as
     /// command arguments.
+    /// If this `Cmd` instance is created with `with_strings_after_end_opt` and
+    /// `true`, all arguments are treated as command arguments.
+    ///
+    /// If this `Cmd` instance stores option configurations, which are added
+    /// with `add_cfgs`, this method parses with them in the same way as
+    /// `parse_with`.
     ///
     /// Since the results of parsing are stored into this `Cmd` instance, this
     /// method returns a [Result] which contains an unit value (`()`) if
@@ -42,24 +50,52 @@ impl<'a> Cmd<'a> {
     /// }
     /// ```
     pub fn parse(&mut self) -> Result<(), InvalidOption> {
+        if !self.cfgs.is_empty() {
+            let opt_cfgs = mem::take(&mut self.cfgs);
+            let result = self.parse_with(&opt_cfgs);
+            self.cfgs = opt_cfgs;
+            return result;
+        }
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("parse", cmd = self.name).entered();
+
         let collect_args = |arg| {
             self.args.push(arg);
         };
 
-        let collect_opts = |name, option| {
-            let vec = self.opts.entry(name).or_insert_with(|| Vec::new());
+        let collect_opts = |name, option, index| {
+            let vec = self.opts.get_or_insert_default(name);
             if let Some(arg) = option {
                 vec.push(arg);
             }
+            self.opt_indices.entry(name).or_default().push(index);
+            self.names_used.insert(name, name);
+            #[cfg(feature = "tracing")]
+            tracing::trace!(option = name, arg = ?option, "parsed an option");
             Ok(())
         };
 
-        let take_args = |_arg: &str| false;
-
-        if !self._arg_refs.is_empty() {
-            match parse_args(&self._arg_refs[1..], collect_args, collect_opts, take_args) {
-                Ok(_) => {}
-                Err(err) => return Err(err),
+        if self.is_after_end_opt {
+            self.args.extend_from_slice(&self._arg_refs[1..]);
+        } else if !self._arg_refs.is_empty() {
+            match parse_args(
+                &self._arg_refs[1..],
+                collect_args,
+                collect_opts,
+                |_| None,
+                |_, _| {},
+                self.parse_options.flags(false, false),
+            ) {
+                Ok(Some(i)) => {
+                    self.args.extend_from_slice(&self._arg_refs[i + 1..]);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(error = %err, "failed to parse");
+                    return Err(err);
+                }
             }
         }
 
@@ -80,21 +116,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["/path/to/app".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), false);
+            assert!(!cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), None);
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -104,21 +140,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["/path/to/app".to_string(), "abcd".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), ["abcd"]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), false);
+            assert!(!cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), None);
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -128,21 +164,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["/path/to/app".to_string(), "--silent".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), false);
+            assert!(!cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), None);
-            assert_eq!(cmd.has_opt("silent"), true);
+            assert!(cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), Some(&[] as &[&str]));
         }
@@ -153,21 +189,21 @@ mod tests_of_cmd {
                 Cmd::with_strings(["/path/to/app".to_string(), "--alphabet=ABC".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), true);
+            assert!(cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), Some("ABC"));
             assert_eq!(cmd.opt_args("alphabet"), Some(&["ABC"] as &[&str]));
-            assert_eq!(cmd.has_opt("s"), false);
+            assert!(!cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), None);
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -177,21 +213,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "-s".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), true);
+            assert!(cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -201,21 +237,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "-a=123".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), true);
+            assert!(cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), Some("123"));
             assert_eq!(cmd.opt_args("a"), Some(&["123"] as &[&str]));
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), false);
+            assert!(!cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), None);
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -225,21 +261,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "-sa".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), true);
+            assert!(cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), true);
+            assert!(cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -249,21 +285,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "-sa=123".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), true);
+            assert!(cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), Some("123"));
             assert_eq!(cmd.opt_args("a"), Some(&["123"] as &[&str]));
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), true);
+            assert!(cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -273,12 +309,12 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["app".to_string(), "--aaa-bbb-ccc=123".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("aaa-bbb-ccc"), true);
+            assert!(cmd.has_opt("aaa-bbb-ccc"));
             assert_eq!(cmd.opt_arg("aaa-bbb-ccc"), Some("123"));
             assert_eq!(cmd.opt_args("aaa-bbb-ccc"), Some(&["123"] as &[&str]));
         }
@@ -288,21 +324,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["app".to_string(), "-sa=b=c".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), true);
+            assert!(cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), Some("b=c"));
             assert_eq!(cmd.opt_args("a"), Some(&["b=c"] as &[&str]));
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), true);
+            assert!(cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -312,21 +348,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "-sa=1,2-3".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), true);
+            assert!(cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), Some("1,2-3"));
             assert_eq!(cmd.opt_args("a"), Some(&["1,2-3"] as &[&str]));
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), true);
+            assert!(cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -340,25 +376,25 @@ mod tests_of_cmd {
                 "-a".to_string(),
             ]);
             match cmd.parse() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionContainsInvalidChar { option }) => {
                     assert_eq!(option, "abc%def");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), true);
+            assert!(cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), true);
+            assert!(cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -367,25 +403,25 @@ mod tests_of_cmd {
         fn should_parse_but_fail_because_of_illegal_long_opt_of_which_first_char_is_number() {
             let mut cmd = Cmd::with_strings(["app".to_string(), "--1abc".to_string()]);
             match cmd.parse() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionContainsInvalidChar { option }) => {
                     assert_eq!(option, "1abc");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), false);
+            assert!(!cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), None);
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -394,25 +430,25 @@ mod tests_of_cmd {
         fn should_parse_but_fail_because_of_illegal_long_opt_of_which_first_char_is_hyphen() {
             let mut cmd = Cmd::with_strings(["app".to_string(), "---aaa=123".to_string()]);
             match cmd.parse() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionContainsInvalidChar { option }) => {
                     assert_eq!(option, "-aaa=123");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), false);
+            assert!(!cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), None);
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -426,29 +462,53 @@ mod tests_of_cmd {
                 "-a@".to_string(),
             ]);
             match cmd.parse() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionContainsInvalidChar { option }) => {
                     assert_eq!(option, "@");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &[] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), true);
+            assert!(cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("alphabet"), true);
+            assert!(cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("s"), true);
+            assert!(cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
 
+        #[test]
+        fn should_parse_but_fail_because_of_multi_byte_char_in_short_opt() {
+            let mut cmd = Cmd::with_strings([
+                "path/to/app".to_string(),
+                "-aéb".to_string(),
+                "-ü=1".to_string(),
+                "-c日".to_string(),
+            ]);
+            match cmd.parse() {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionContainsInvalidChar { option }) => {
+                    assert_eq!(option, "é");
+                }
+                Err(_) => panic!(),
+            }
+
+            assert_eq!(cmd.name(), "app");
+            assert_eq!(cmd.args(), &[] as &[&str]);
+            assert!(cmd.has_opt("a"));
+            assert!(cmd.has_opt("b"));
+            assert!(cmd.has_opt("c"));
+            assert!(!cmd.has_opt("ü"));
+        }
+
         #[test]
         fn should_parse_with_end_opt_mark() {
             let mut cmd = Cmd::with_strings([
@@ -462,21 +522,21 @@ mod tests_of_cmd {
             ]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &["-a", "-s@", "--", "xxx"] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), true);
+            assert!(cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -486,21 +546,21 @@ mod tests_of_cmd {
             let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "-".to_string()]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &["-"] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), false);
+            assert!(!cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), None);
-            assert_eq!(cmd.has_opt("alphabet"), false);
+            assert!(!cmd.has_opt("alphabet"));
             assert_eq!(cmd.opt_arg("alphabet"), None);
             assert_eq!(cmd.opt_args("alphabet"), None);
-            assert_eq!(cmd.has_opt("s"), false);
+            assert!(!cmd.has_opt("s"));
             assert_eq!(cmd.opt_arg("s"), None);
             assert_eq!(cmd.opt_args("s"), None);
-            assert_eq!(cmd.has_opt("silent"), false);
+            assert!(!cmd.has_opt("silent"));
             assert_eq!(cmd.opt_arg("silent"), None);
             assert_eq!(cmd.opt_args("silent"), None);
         }
@@ -519,24 +579,24 @@ mod tests_of_cmd {
             ]);
             match cmd.parse() {
                 Ok(_) => {}
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &["qux", "quux"] as &[&str]);
-            assert_eq!(cmd.has_opt("a"), true);
+            assert!(cmd.has_opt("a"));
             assert_eq!(cmd.opt_arg("a"), None);
             assert_eq!(cmd.opt_args("a"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("b"), true);
+            assert!(cmd.has_opt("b"));
             assert_eq!(cmd.opt_arg("b"), None);
             assert_eq!(cmd.opt_args("b"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("c"), true);
+            assert!(cmd.has_opt("c"));
             assert_eq!(cmd.opt_arg("c"), Some("3"));
             assert_eq!(cmd.opt_args("c"), Some(&["3", "4"] as &[&str]));
-            assert_eq!(cmd.has_opt("foo-bar"), true);
+            assert!(cmd.has_opt("foo-bar"));
             assert_eq!(cmd.opt_arg("foo-bar"), None);
             assert_eq!(cmd.opt_args("foo-bar"), Some(&[] as &[&str]));
-            assert_eq!(cmd.has_opt("baz"), true);
+            assert!(cmd.has_opt("baz"));
             assert_eq!(cmd.opt_arg("baz"), None);
             assert_eq!(cmd.opt_args("baz"), Some(&[] as &[&str]));
         }
@@ -552,22 +612,75 @@ mod tests_of_cmd {
                 "baz".to_string(),
             ]);
             match cmd.parse() {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionContainsInvalidChar { option }) => {
                     assert_eq!(option, "1");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
 
             assert_eq!(cmd.name(), "app");
             assert_eq!(cmd.args(), &["baz"] as &[&str]);
-            assert_eq!(cmd.has_opt("foo"), true);
-            assert_eq!(cmd.has_opt("b"), true);
-            assert_eq!(cmd.has_opt("a"), true);
-            assert_eq!(cmd.has_opt("r"), true);
-            assert_eq!(cmd.has_opt("1"), false);
-            assert_eq!(cmd.has_opt("2"), false);
-            assert_eq!(cmd.has_opt("3"), false);
+            assert!(cmd.has_opt("foo"));
+            assert!(cmd.has_opt("b"));
+            assert!(cmd.has_opt("a"));
+            assert!(cmd.has_opt("r"));
+            assert!(!cmd.has_opt("1"));
+            assert!(!cmd.has_opt("2"));
+            assert!(!cmd.has_opt("3"));
+        }
+
+        #[test]
+        fn should_record_occurrences_of_options() {
+            let mut cmd = Cmd::with_strings([
+                "/path/to/app".to_string(),
+                "--foo".to_string(),
+                "-ab".to_string(),
+                "qux".to_string(),
+                "--foo=1".to_string(),
+                "--".to_string(),
+                "--foo".to_string(),
+            ]);
+            match cmd.parse() {
+                Ok(_) => {}
+                Err(_) => panic!(),
+            }
+
+            assert_eq!(cmd.opt_occurrence_count("foo"), 2);
+            assert_eq!(cmd.opt_indices("foo"), &[1, 4]);
+            assert_eq!(cmd.opt_occurrence_count("a"), 1);
+            assert_eq!(cmd.opt_indices("a"), &[2]);
+            assert_eq!(cmd.opt_indices("b"), &[2]);
+            assert_eq!(cmd.opt_occurrence_count("bar"), 0);
+            assert_eq!(cmd.opt_indices("bar"), &[] as &[usize]);
+        }
+
+        #[test]
+        fn should_treat_all_args_as_command_args_if_after_end_opt() {
+            let mut cmd = Cmd::with_strings_after_end_opt(
+                ["sub".to_string(), "--foo".to_string(), "-b".to_string()],
+                true,
+            );
+            assert!(cmd.is_after_end_opt());
+            match cmd.parse() {
+                Ok(_) => {}
+                Err(_) => panic!(),
+            }
+            assert!(!cmd.has_opt("foo"));
+            assert_eq!(cmd.args(), &["--foo", "-b"]);
+
+            let mut cmd = Cmd::with_strings_after_end_opt(
+                ["sub".to_string(), "--foo".to_string(), "-b".to_string()],
+                false,
+            );
+            assert!(!cmd.is_after_end_opt());
+            match cmd.parse() {
+                Ok(_) => {}
+                Err(_) => panic!(),
+            }
+            assert!(cmd.has_opt("foo"));
+            assert!(cmd.has_opt("b"));
+            assert_eq!(cmd.args(), &[] as &[&str]);
         }
     }
 }
@@ -0,0 +1,137 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::app::{error_message, help_text, HelpLayout};
+use crate::exit;
+use crate::similar::{suggest_opt, with_suggestion};
+use crate::Cmd;
+use crate::OptCfg;
+use std::process;
+
+const HELP_KEY: &str = "help";
+const VERSION_KEY: &str = "version";
+
+impl<'a> Cmd<'a> {
+    /// Parses command line arguments with option configurations like
+    /// `parse_with`, but exits the process on an error, a help request, or a
+    /// version request.
+    ///
+    /// If the command line arguments are invalid, this method prints an error
+    /// message and a usage hint to stderr, and exits with the status 2.
+    /// If the error is of an unconfigured option, the message suggests the
+    /// option of which the name is the most similar.
+    /// If an option configuration of which the store key is `"help"` exists
+    /// and the option is specified, this method prints the help text made
+    /// from the option configurations to stdout, and exits with the status 0.
+    /// Likewise, if an option configuration of which the store key is
+    /// `"version"` exists and the option is specified, this method prints the
+    /// command name and the specified version to stdout, and exits with the
+    /// status 0.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::{names, desc};
+    ///
+    /// let mut cmd = Cmd::with_strings(["app".to_string(), "-v".to_string()]);
+    /// let opt_cfgs = vec![
+    ///     OptCfg::with(&[names(&["verbose", "v"]), desc("Prints verbose logs.")]),
+    ///     OptCfg::with(&[names(&["help", "h"]), desc("Prints help.")]),
+    ///     OptCfg::with(&[names(&["version", "V"]), desc("Prints version.")]),
+    /// ];
+    ///
+    /// cmd.parse_with_or_exit(&opt_cfgs, "1.0.0");
+    /// assert_eq!(cmd.has_opt("verbose"), true);
+    /// ```
+    pub fn parse_with_or_exit(&mut self, opt_cfgs: &[OptCfg], version: &str) {
+        let usage = format!("{} [OPTIONS]", self.name);
+
+        if let Err(err) = self.parse_with(opt_cfgs) {
+            let suggestion = suggest_opt(self, &err, opt_cfgs);
+            let msg = with_suggestion(&err, suggestion.as_deref());
+            eprint!("{}", error_message(self.name, &usage, &msg));
+            process::exit(exit::USAGE);
+        }
+
+        if self.is_requested(HELP_KEY, opt_cfgs) {
+            print!(
+                "{}",
+                help_text(&usage, "", opt_cfgs, &[], HelpLayout::default())
+            );
+            process::exit(exit::OK);
+        }
+
+        if self.is_requested(VERSION_KEY, opt_cfgs) {
+            println!("{} {}", self.name, version);
+            process::exit(exit::OK);
+        }
+    }
+
+    fn is_requested(&self, key: &str, opt_cfgs: &[OptCfg]) -> bool {
+        self.has_opt(key)
+            && opt_cfgs.iter().any(|cfg| {
+                cfg.store_key == key
+                    || (cfg.store_key.is_empty()
+                        && cfg.names.first().map(|s| s.as_str()) == Some(key))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests_of_parse_with_or_exit {
+    use super::*;
+    use crate::OptCfgParam::*;
+
+    #[test]
+    fn should_not_exit_if_parsed_successfully() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo", "f"])]),
+            OptCfg::with(&[names(&["help", "h"])]),
+        ];
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "-f".to_string(), "a".to_string()]);
+        cmd.parse_with_or_exit(&opt_cfgs, "1.0.0");
+
+        assert!(cmd.has_opt("foo"));
+        assert_eq!(cmd.args(), &["a"]);
+        assert!(!cmd.is_requested(HELP_KEY, &opt_cfgs));
+    }
+
+    #[test]
+    fn should_not_parse_options_if_after_end_opt() {
+        let opt_cfgs = vec![OptCfg::with(&[names(&["foo", "f"])])];
+
+        let mut cmd = Cmd::with_strings_after_end_opt(["sub", "-f", "-x"].map(String::from), true);
+        cmd.parse_with_or_exit(&opt_cfgs, "1.0.0");
+
+        assert!(!cmd.has_opt("foo"));
+        assert_eq!(cmd.args(), &["-f", "-x"]);
+    }
+
+    #[test]
+    fn should_detect_version_request_only_if_version_is_configured() {
+        let opt_cfgs = vec![OptCfg::with(&[names(&["version", "V"])])];
+        let mut cmd = Cmd::with_strings(["app".to_string(), "-V".to_string()]);
+        cmd.parse_with(&opt_cfgs).unwrap();
+        assert!(cmd.is_requested(VERSION_KEY, &opt_cfgs));
+        assert!(!cmd.is_requested(HELP_KEY, &opt_cfgs));
+
+        let opt_cfgs = vec![OptCfg::with(&[store_key("*")])];
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--version".to_string()]);
+        cmd.parse_with(&opt_cfgs).unwrap();
+        assert!(!cmd.is_requested(VERSION_KEY, &opt_cfgs));
+    }
+
+    #[test]
+    fn should_detect_help_request_only_if_help_is_configured() {
+        let opt_cfgs = vec![OptCfg::with(&[store_key("help"), names(&["h", "usage"])])];
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--usage".to_string()]);
+        cmd.parse_with(&opt_cfgs).unwrap();
+        assert!(cmd.is_requested(HELP_KEY, &opt_cfgs));
+
+        let opt_cfgs = vec![OptCfg::with(&[store_key("*")])];
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--help".to_string()]);
+        cmd.parse_with(&opt_cfgs).unwrap();
+        assert!(!cmd.is_requested(HELP_KEY, &opt_cfgs));
+    }
+}
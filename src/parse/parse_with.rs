@@ -2,10 +2,20 @@
 // This program is free software under MIT License.
 // See the file LICENSE in this distribution for more details.
 
-use super::parse_args;
+#[cfg(feature = "rayon")]
+use super::Flags;
+use super::{find_abbrev, parse_args};
 use crate::errors::InvalidOption;
+use crate::expand_path;
+use crate::interpolate_env;
+use crate::small_map::SmallMap;
+use crate::validate_cfgs;
+use crate::values::expand_si_suffix;
 use crate::Cmd;
+use crate::DuplicatePolicy;
+use crate::EmptyArgPolicy;
 use crate::OptCfg;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 impl<'a> Cmd<'a> {
@@ -20,8 +30,14 @@ impl<'a> Cmd<'a> {
     ///
     /// This method allows only options declared in option configurations,
     /// basically.
+    /// If any option name in the configurations contains `_`, underscores are
+    /// allowed in long option names, like `--dry_run`.
+    /// Some behaviors of parsing, like stopping at the first command argument
+    /// or how to treat duplicated options, can be switched with
+    /// `with_parse_options`.
     /// An option configuration has fields: `store_key`, `names`, `has_arg`,
-    /// `is_array`, `defaults`, `desc`, `arg_in_help`, and `validator`.
+    /// `is_array`, `defaults`, `desc`, `arg_in_help`, `validator`, and
+    /// `on_parse`.
     ///
     /// ```
     /// use cliargs::{Cmd, OptCfg};
@@ -57,14 +73,50 @@ impl<'a> Cmd<'a> {
     /// }
     /// ```
     pub fn parse_with(&mut self, opt_cfgs: &[OptCfg]) -> Result<(), InvalidOption> {
+        self.parse_with_until_1st_arg(opt_cfgs, false)?;
+        Ok(())
+    }
+
+    /// Parses command line arguments with option configurations, and if
+    /// `until_1st_arg` is `true`, stops at the first command argument and
+    /// returns its index in the command line arguments.
+    ///
+    /// The default values of options are applied even if stopped.
+    pub(crate) fn parse_with_until_1st_arg(
+        &mut self,
+        opt_cfgs: &[OptCfg],
+        until_1st_arg: bool,
+    ) -> Result<Option<usize>, InvalidOption> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("parse_with", cmd = self.name).entered();
+
+        let result = self.parse_with_cfgs(opt_cfgs, until_1st_arg);
+
+        #[cfg(feature = "tracing")]
+        if let Err(err) = &result {
+            tracing::debug!(error = %err, "failed to parse");
+        }
+
+        result
+    }
+
+    fn parse_with_cfgs(
+        &mut self,
+        opt_cfgs: &[OptCfg],
+        until_1st_arg: bool,
+    ) -> Result<Option<usize>, InvalidOption> {
+        if let Some(err) = validate_cfgs(opt_cfgs).into_iter().next() {
+            return Err(err);
+        }
+
         let mut cfg_map = HashMap::<&str, usize>::new();
-        let mut opt_map = HashMap::<&str, ()>::new();
 
         const ANY_OPT: &str = "*";
         let mut has_any_opt = false;
+        let mut allows_underscore = false;
 
         for (i, cfg) in opt_cfgs.iter().enumerate() {
-            let store_key: &str = if cfg.store_key.is_empty() && cfg.names.len() > 0 {
+            let store_key: &str = if cfg.store_key.is_empty() && !cfg.names.is_empty() {
                 &cfg.names[0]
             } else {
                 &cfg.store_key
@@ -85,66 +137,52 @@ impl<'a> Cmd<'a> {
                 &cfg.names[0]
             };
 
-            if opt_map.contains_key(store_key) {
-                return Err(InvalidOption::StoreKeyIsDuplicated {
-                    store_key: store_key.to_string(),
-                    name: first_name.to_string(),
-                });
-            }
-            opt_map.insert(store_key, ());
-
-            if !cfg.has_arg {
-                if cfg.is_array {
-                    return Err(InvalidOption::ConfigIsArrayButHasNoArg {
-                        store_key: store_key.to_string(),
-                        name: first_name.to_string(),
-                    });
-                }
-                if let Some(vec) = &cfg.defaults {
-                    if !vec.is_empty() {
-                        return Err(InvalidOption::ConfigHasDefaultsButHasNoArg {
-                            store_key: store_key.to_string(),
-                            name: first_name.to_string(),
-                        });
-                    }
-                }
+            if first_name.contains('_') || cfg.names.iter().any(|n| n.contains('_')) {
+                allows_underscore = true;
             }
 
             if cfg.names.is_empty() {
                 cfg_map.insert(first_name, i);
             } else {
                 for name in cfg.names.iter() {
-                    if cfg_map.contains_key(name.as_str()) {
-                        return Err(InvalidOption::OptionNameIsDuplicated {
-                            store_key: store_key.to_string(),
-                            name: name.to_string(),
-                        });
-                    }
                     cfg_map.insert(name, i);
                 }
             }
         }
 
         if self._arg_refs.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
-        let take_args = |opt: &str| {
-            if let Some(i) = cfg_map.get(opt) {
-                return opt_cfgs[*i].has_arg;
-            }
-            false
+        if self.opts.is_empty() {
+            self.opts = SmallMap::with_capacity(opt_cfgs.len());
+        }
+
+        let allows_abbrev = self.parse_options.allows_abbrev;
+        let find_index = |opt: &str| match cfg_map.get(opt) {
+            Some(i) => Some(*i),
+            None if allows_abbrev => find_abbrev(opt, &cfg_map),
+            None => None,
         };
+        let find_cfg = |opt: &str| find_index(opt).map(|i| &opt_cfgs[i]);
+        let duplicate_opt = self.parse_options.duplicate_opt;
+        let flags = self.parse_options.flags(until_1st_arg, allows_underscore);
 
         let collect_args = |arg| {
             self.args.push(arg);
         };
 
-        let mut str_refs: Vec<&'a str> = Vec::with_capacity(opt_cfgs.len());
+        let mut signs: Vec<(&'a str, bool)> = Vec::new();
+        let mark_sign = |name, plus| {
+            signs.push((name, plus));
+        };
 
-        let collect_opts = |name: &'a str, arg_op: Option<&'a str>| {
-            if let Some(i) = cfg_map.get(name) {
-                let cfg = &opt_cfgs[*i];
+        #[cfg(feature = "rayon")]
+        let mut validated = validate_array_args(&self._arg_refs[1..], find_cfg, flags);
+
+        let collect_opts = |name: &'a str, arg_op: Option<&'a str>, index: usize| {
+            if let Some(i) = find_index(name) {
+                let cfg = &opt_cfgs[i];
 
                 let store_key = if cfg.store_key.is_empty() {
                     cfg.names[0].as_str()
@@ -152,6 +190,19 @@ impl<'a> Cmd<'a> {
                     cfg.store_key.as_str()
                 };
 
+                #[cfg(feature = "rayon")]
+                let validation_key = (
+                    index,
+                    name.as_ptr() as usize,
+                    arg_op.map(|a| a.as_ptr() as usize),
+                );
+
+                let arg_op = match convert_arg(cfg, name, store_key, arg_op)? {
+                    Some(Cow::Borrowed(arg)) => Some(arg),
+                    Some(Cow::Owned(arg)) => Some(self.arena.alloc(&arg)),
+                    None => None,
+                };
+
                 if let Some(arg) = arg_op {
                     if !cfg.has_arg {
                         return Err(InvalidOption::OptionTakesNoArg {
@@ -160,25 +211,56 @@ impl<'a> Cmd<'a> {
                         });
                     }
 
-                    if let Some(vec) = self.opts.get_mut(store_key) {
-                        if !vec.is_empty() {
-                            if !cfg.is_array {
-                                return Err(InvalidOption::OptionIsNotArray {
-                                    option: name.to_string(),
-                                    store_key: store_key.to_string(),
-                                });
+                    let mut replaces = false;
+                    if let Some(vec) = self.opts.get(store_key) {
+                        if !vec.is_empty() && !cfg.is_array {
+                            match duplicate_opt {
+                                DuplicatePolicy::Error => {
+                                    return Err(InvalidOption::OptionIsNotArray {
+                                        option: name.to_string(),
+                                        store_key: store_key.to_string(),
+                                    });
+                                }
+                                DuplicatePolicy::FirstWins => return Ok(()),
+                                DuplicatePolicy::LastWins => replaces = true,
+                            }
+                        }
+                    }
+
+                    if cfg.unique && cfg.is_array {
+                        if let Some(vec) = self.opts.get_mut(store_key) {
+                            if vec.contains(&arg) {
+                                match duplicate_opt {
+                                    DuplicatePolicy::Error => {
+                                        return Err(InvalidOption::OptionArgIsDuplicated {
+                                            store_key: store_key.to_string(),
+                                            option: name.to_string(),
+                                            opt_arg: arg.to_string(),
+                                        });
+                                    }
+                                    DuplicatePolicy::FirstWins => return Ok(()),
+                                    DuplicatePolicy::LastWins => vec.retain(|a| *a != arg),
+                                }
                             }
                         }
+                    }
+
+                    #[cfg(feature = "rayon")]
+                    match validated.remove(&validation_key) {
+                        Some(result) => result?,
+                        None => (cfg.validator)(store_key, name, arg)?,
+                    }
+                    #[cfg(not(feature = "rayon"))]
+                    (cfg.validator)(store_key, name, arg)?;
 
-                        (cfg.validator)(store_key, name, arg)?;
+                    if let Some(vec) = self.opts.get_mut(store_key) {
+                        if replaces {
+                            vec.clear();
+                        }
                         vec.push(arg);
                     } else {
-                        (cfg.validator)(store_key, name, arg)?;
-
-                        let string = String::from(store_key);
-                        let str: &'a str = string.leak();
-                        str_refs.push(str);
-                        self.opts.insert(str, vec![arg]);
+                        let key = self.arena.alloc(store_key);
+                        self.opts.insert(key, vec![arg]);
                     }
                 } else {
                     if cfg.has_arg {
@@ -188,14 +270,22 @@ impl<'a> Cmd<'a> {
                         });
                     }
 
-                    if let None = self.opts.get_mut(store_key) {
-                        let string = String::from(store_key);
-                        let str: &'a str = string.leak();
-                        str_refs.push(str);
-                        self.opts.insert(str, vec![]);
+                    if self.opts.get_mut(store_key).is_none() {
+                        let key = self.arena.alloc(store_key);
+                        self.opts.insert(key, vec![]);
                     }
                 }
 
+                if let Some((key, _)) = self.opts.get_key_value(store_key) {
+                    self.opt_indices.entry(*key).or_default().push(index);
+                    self.names_used.insert(*key, name);
+                }
+
+                (cfg.on_parse)(store_key, name, arg_op.unwrap_or(""));
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(store_key, option = name, arg = ?arg_op, "parsed an option");
+
                 Ok(())
             } else {
                 if !has_any_opt {
@@ -213,18 +303,97 @@ impl<'a> Cmd<'a> {
                 } else {
                     self.opts.insert(name, Vec::with_capacity(0));
                 }
+                self.opt_indices.entry(name).or_default().push(index);
+                self.names_used.insert(name, name);
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(option = name, arg = ?arg_op, "parsed an unconfigured option");
 
                 Ok(())
             }
         };
 
-        let result = parse_args(&self._arg_refs[1..], collect_args, collect_opts, take_args);
+        let result = if self.is_after_end_opt {
+            let mut collect_args = collect_args;
+            for arg in self._arg_refs[1..].iter() {
+                collect_args(arg);
+            }
+            Ok(None)
+        } else {
+            parse_args(
+                &self._arg_refs[1..],
+                collect_args,
+                collect_opts,
+                find_cfg,
+                mark_sign,
+                flags,
+            )
+        };
+
+        for (name, plus) in signs {
+            if let Some(i) = cfg_map.get(name) {
+                let cfg = &opt_cfgs[*i];
+                let store_key = if cfg.store_key.is_empty() {
+                    cfg.names[0].as_str()
+                } else {
+                    cfg.store_key.as_str()
+                };
+                if let Some((key, _)) = self.opts.get_key_value(store_key) {
+                    let key = *key;
+                    self.plus_opts.retain(|k| *k != key);
+                    if plus {
+                        self.plus_opts.push(key);
+                    }
+                }
+            }
+        }
 
-        for str_ref in str_refs {
-            self._arg_refs.push(str_ref);
+        let mut arg_index = result?.map(|i| i + 1);
+        if !until_1st_arg {
+            if let Some(i) = arg_index.take() {
+                self.args.extend_from_slice(&self._arg_refs[i..]);
+            }
         }
 
-        result?;
+        for cfg in opt_cfgs.iter() {
+            if cfg.names.is_empty() {
+                continue;
+            }
+
+            let store_key = if cfg.store_key.is_empty() {
+                cfg.names[0].as_str()
+            } else {
+                cfg.store_key.as_str()
+            };
+
+            if self.opts.contains_key(store_key) {
+                continue;
+            }
+            if let Some(mut env_vals) = self.env_values(cfg, store_key) {
+                if cfg.accepts_si_suffix {
+                    env_vals = env_vals
+                        .into_iter()
+                        .map(|s| expand_si_suffix(&s).unwrap_or(s))
+                        .collect();
+                }
+                if cfg.is_path {
+                    env_vals = env_vals.iter().map(|s| expand_path(s)).collect();
+                }
+                let env_name = self.env_var_name(store_key).unwrap_or_default();
+                #[cfg(feature = "tracing")]
+                tracing::trace!(store_key, env_var = %env_name, values = ?env_vals, "applied an environment variable");
+                for env_val in env_vals.iter() {
+                    (cfg.validator)(store_key, &env_name, env_val)?;
+                }
+
+                let key = self.arena.alloc(store_key);
+                let vec = self.opts.get_or_insert_default(key);
+
+                for env_val in env_vals {
+                    vec.push(self.arena.alloc(&env_val));
+                }
+            }
+        }
 
         for cfg in opt_cfgs.iter() {
             if cfg.names.is_empty() {
@@ -237,31 +406,207 @@ impl<'a> Cmd<'a> {
                 cfg.store_key.as_str()
             };
 
-            if let None = self.opts.get_mut(store_key) {
-                if let Some(def_vec) = &cfg.defaults {
-                    let string = String::from(store_key);
-                    let key: &'a str = string.leak();
-                    self._arg_refs.push(key);
-                    let vec = self.opts.entry(key).or_insert(Vec::new());
+            if self.opts.get_mut(store_key).is_none() {
+                let def_vec = cfg
+                    .defaults_if
+                    .iter()
+                    .find(|(key, arg, _)| {
+                        if self.defaulted.contains(&key.as_str()) {
+                            return false;
+                        }
+                        match (self.opts.get(key.as_str()), arg) {
+                            (Some(vec), Some(arg)) => vec.contains(&arg.as_str()),
+                            (Some(_), None) => true,
+                            (None, _) => false,
+                        }
+                    })
+                    .map(|(_, _, vals)| vals)
+                    .or(cfg.defaults.as_ref());
+                let computed_vec;
+                let def_vec = match (def_vec, cfg.default_fn) {
+                    (None, Some(default_fn)) => {
+                        computed_vec = default_fn();
+                        Some(&computed_vec)
+                    }
+                    _ => def_vec,
+                };
+                if let Some(def_vec) = def_vec {
+                    let key = self.arena.alloc(store_key);
+                    self.defaulted.push(key);
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(store_key, values = ?def_vec, "applied default values");
+                    let vec = self.opts.get_or_insert_default(key);
 
                     for def_val in def_vec.iter() {
-                        let string = String::from(def_val);
-                        let arg: &'a str = string.leak();
-                        self._arg_refs.push(arg);
-                        vec.push(arg);
+                        let mut def_val = Cow::from(def_val);
+                        if cfg.interpolates_defaults {
+                            def_val = Cow::from(interpolate_env(&def_val));
+                        }
+                        if cfg.accepts_si_suffix {
+                            if let Some(expanded) = expand_si_suffix(&def_val) {
+                                def_val = Cow::from(expanded);
+                            }
+                        }
+                        if cfg.is_path {
+                            def_val = Cow::from(expand_path(&def_val));
+                        }
+                        vec.push(self.arena.alloc(&def_val));
                     }
                 }
             }
         }
 
-        Ok(())
+        if self.parse_options.rejects_unused_cfgs {
+            for cfg in opt_cfgs.iter() {
+                let store_key = match (cfg.store_key.is_empty(), cfg.names.first()) {
+                    (true, Some(name)) => name.as_str(),
+                    _ => cfg.store_key.as_str(),
+                };
+                if store_key.is_empty() || store_key == ANY_OPT {
+                    continue;
+                }
+                if !self.opts.contains_key(store_key) {
+                    return Err(InvalidOption::ConfigIsUnused {
+                        store_key: store_key.to_string(),
+                        name: cfg
+                            .names
+                            .first()
+                            .map_or(store_key, |s| s.as_str())
+                            .to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(arg_index)
+    }
+}
+
+/// Applies the conversions specified in the option configuration, like
+/// trimming or expanding a path, to the option argument.
+fn convert_arg<'s>(
+    cfg: &OptCfg,
+    name: &str,
+    store_key: &str,
+    arg_op: Option<&'s str>,
+) -> Result<Option<Cow<'s, str>>, InvalidOption> {
+    let arg_op = match arg_op {
+        Some(arg) if cfg.trims_arg && !cfg.raw_arg => Some(arg.trim()),
+        _ => arg_op,
+    };
+
+    let arg_op = match arg_op {
+        Some("") if cfg.has_arg && !cfg.raw_arg => match cfg.empty_arg {
+            EmptyArgPolicy::Allow => arg_op,
+            EmptyArgPolicy::Error => {
+                return Err(InvalidOption::OptionArgIsEmpty {
+                    option: name.to_string(),
+                    store_key: store_key.to_string(),
+                });
+            }
+            EmptyArgPolicy::AsMissing => None,
+        },
+        _ => arg_op,
+    };
+
+    let arg_op = match (arg_op, &cfg.default_missing_value) {
+        (None, Some(value)) if cfg.has_arg => Some(Cow::Owned(value.clone())),
+        _ => arg_op.map(Cow::Borrowed),
+    };
+
+    let arg_op = match arg_op {
+        Some(arg) if cfg.accepts_si_suffix && !cfg.raw_arg => match expand_si_suffix(&arg) {
+            Some(expanded) => Some(Cow::Owned(expanded)),
+            None => Some(arg),
+        },
+        _ => arg_op,
+    };
+
+    let arg_op = match arg_op {
+        Some(arg) if cfg.is_path && !cfg.raw_arg => {
+            let expanded = expand_path(&arg);
+            if expanded == arg {
+                Some(arg)
+            } else {
+                Some(Cow::Owned(expanded))
+            }
+        }
+        _ => arg_op,
+    };
+
+    Ok(arg_op)
+}
+
+/// Is the key of a validation result, which consists of the index in command
+/// line arguments and the addresses of the option name and the option
+/// argument.
+#[cfg(feature = "rayon")]
+type ValidationKey = (usize, usize, Option<usize>);
+
+/// The minimum number of arguments of array options which are validated in
+/// parallel.
+#[cfg(feature = "rayon")]
+const MIN_PARALLEL_VALIDATIONS: usize = 256;
+
+/// Runs the validators for the arguments of array options in the specified
+/// command line arguments in parallel, if there are many of them.
+///
+/// While parsing, the results are used instead of running the validators, so
+/// the parsing, including which error is returned and which side effects are
+/// made, is the same as without the `rayon` feature.
+#[cfg(feature = "rayon")]
+fn validate_array_args<'c>(
+    args: &[&str],
+    find_cfg: impl Fn(&str) -> Option<&'c OptCfg> + Copy,
+    flags: Flags,
+) -> HashMap<ValidationKey, Result<(), InvalidOption>> {
+    use rayon::prelude::*;
+
+    let mut targets = Vec::new();
+    let _ = parse_args(
+        args,
+        |_| {},
+        |name, arg_op, index| {
+            if let (Some(cfg), Some(arg)) = (find_cfg(name), arg_op) {
+                if cfg.is_array && cfg.has_arg {
+                    targets.push((cfg, name, arg, index));
+                }
+            }
+            Ok(())
+        },
+        find_cfg,
+        |_, _| {},
+        flags,
+    );
+    if targets.len() < MIN_PARALLEL_VALIDATIONS {
+        return HashMap::new();
     }
+
+    let results: Vec<_> = targets
+        .par_iter()
+        .filter_map(|(cfg, name, arg, index)| {
+            let store_key = if cfg.store_key.is_empty() {
+                cfg.names[0].as_str()
+            } else {
+                cfg.store_key.as_str()
+            };
+            match convert_arg(cfg, name, store_key, Some(arg)) {
+                Ok(Some(converted)) => Some((
+                    (*index, name.as_ptr() as usize, Some(arg.as_ptr() as usize)),
+                    (cfg.validator)(store_key, name, &converted),
+                )),
+                _ => None,
+            }
+        })
+        .collect();
+    results.into_iter().collect()
 }
 
 #[cfg(test)]
 mod tests_of_parse_with {
     use super::*;
     use crate::OptCfgParam::*;
+    use crate::ParseOptions;
 
     #[test]
     fn zero_cfg_and_zero_arg() {
@@ -271,11 +616,11 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -289,11 +634,11 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &["foo-bar"] as &[&str]);
@@ -306,24 +651,24 @@ mod tests_of_parse_with {
         let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "--foo-bar".to_string()]);
 
         match cmd.parse_with(&opt_cfgs) {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::UnconfiguredOption { option }) => {
                 assert_eq!(option, "foo-bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "--foo-bar".to_string()]);
 
         match cmd.parse_with(&opt_cfgs) {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(err) => {
                 assert_eq!(err.option(), "foo-bar");
             }
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -336,27 +681,27 @@ mod tests_of_parse_with {
         let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "-f".to_string()]);
 
         match cmd.parse_with(&opt_cfgs) {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::UnconfiguredOption { option }) => {
                 assert_eq!(option, "f");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         let mut cmd = Cmd::with_strings(["path/to/app".to_string(), "-f".to_string()]);
 
         match cmd.parse_with(&opt_cfgs) {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(err) => {
                 assert_eq!(err.option(), "f");
             }
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -370,14 +715,14 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -391,14 +736,14 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &["foo-bar"] as &[&str]);
@@ -412,14 +757,14 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), Some(&[] as &[&str]));
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -433,14 +778,14 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), true);
+        assert!(cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), Some(&[] as &[&str]));
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -461,15 +806,15 @@ mod tests_of_parse_with {
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
 
-        assert_eq!(cmd.has_opt("bar-foo"), false);
+        assert!(!cmd.has_opt("bar-foo"));
         assert_eq!(cmd.opt_arg("bar-foo"), None);
         assert_eq!(cmd.opt_args("bar-foo"), None);
     }
@@ -489,15 +834,15 @@ mod tests_of_parse_with {
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
 
-        assert_eq!(cmd.has_opt("b"), false);
+        assert!(!cmd.has_opt("b"));
         assert_eq!(cmd.opt_arg("b"), None);
         assert_eq!(cmd.opt_args("b"), None);
     }
@@ -513,19 +858,19 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
 
-        assert_eq!(cmd.has_opt("bar-foo"), true);
+        assert!(cmd.has_opt("bar-foo"));
         assert_eq!(cmd.opt_arg("bar-foo"), None);
         assert_eq!(cmd.opt_args("bar-foo"), Some(&[] as &[&str]));
     }
@@ -539,21 +884,18 @@ mod tests_of_parse_with {
 
         let mut cmd = Cmd::with_strings(["app".to_string(), "-b".to_string()]);
 
-        match cmd.parse_with(&opt_cfgs) {
-            Ok(()) => {}
-            Err(_) => assert!(true),
-        }
+        let _ = cmd.parse_with(&opt_cfgs);
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
 
-        assert_eq!(cmd.has_opt("b"), true);
+        assert!(cmd.has_opt("b"));
         assert_eq!(cmd.opt_arg("b"), None);
         assert_eq!(cmd.opt_args("b"), Some(&[] as &[&str]));
     }
@@ -570,11 +912,11 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), Some("ABC"));
         assert_eq!(cmd.opt_args("foo-bar"), Some(&["ABC"] as &[&str]));
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -588,11 +930,11 @@ mod tests_of_parse_with {
 
         match cmd.parse_with(&opt_cfgs) {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("f"), true);
+        assert!(cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), Some("ABC"));
         assert_eq!(cmd.opt_args("f"), Some(&["ABC"] as &[&str]));
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -606,13 +948,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "foo-bar");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionNeedsArg {
                 store_key: sk,
                 option,
@@ -620,11 +962,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "foo-bar");
                 assert_eq!(option, "foo-bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -638,14 +980,14 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(err) => {
                 assert_eq!(err.option(), "f");
             }
         }
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionNeedsArg {
                 store_key: sk,
                 option,
@@ -653,11 +995,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "f");
                 assert_eq!(option, "f");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -676,11 +1018,11 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), Some(&[] as &[&str]));
         assert_eq!(cmd.args(), &["ABC"] as &[&str]);
@@ -689,14 +1031,14 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(err) => {
                 assert_eq!(err.option(), "foo-bar");
             }
         }
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionTakesNoArg {
                 store_key: sk,
                 option,
@@ -704,11 +1046,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "foo-bar");
                 assert_eq!(option, "foo-bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -719,11 +1061,11 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), Some(&[] as &[&str]));
         assert_eq!(cmd.args(), &[""] as &[&str]);
@@ -732,14 +1074,14 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(err) => {
                 assert_eq!(err.option(), "foo-bar");
             }
         }
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionTakesNoArg {
                 store_key: sk,
                 option,
@@ -747,11 +1089,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "foo-bar");
                 assert_eq!(option, "foo-bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -766,11 +1108,11 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("f"), true);
+        assert!(cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), Some(&[] as &[&str]));
         assert_eq!(cmd.args(), &["ABC"] as &[&str]);
@@ -779,14 +1121,14 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(err) => {
                 assert_eq!(err.option(), "f");
             }
         }
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionTakesNoArg {
                 store_key: sk,
                 option,
@@ -794,11 +1136,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "f");
                 assert_eq!(option, "f");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -808,11 +1150,11 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("f"), true);
+        assert!(cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), Some(&[] as &[&str]));
         assert_eq!(cmd.args(), &[""] as &[&str]);
@@ -821,14 +1163,14 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(err) => {
                 assert_eq!(err.option(), "f");
             }
         }
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionTakesNoArg {
                 store_key: sk,
                 option,
@@ -836,11 +1178,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "f");
                 assert_eq!(option, "f");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -858,13 +1200,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "foo-bar");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::ConfigIsArrayButHasNoArg {
                 store_key: sk,
                 name,
@@ -872,11 +1214,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "foo-bar");
                 assert_eq!(name, "foo-bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -893,13 +1235,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "foo-bar");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionNeedsArg {
                 store_key: sk,
                 option,
@@ -907,11 +1249,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "foo-bar");
                 assert_eq!(option, "foo-bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -920,13 +1262,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "f");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionNeedsArg {
                 store_key: sk,
                 option,
@@ -934,11 +1276,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "f");
                 assert_eq!(option, "f");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -962,14 +1304,14 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), Some("ABC"));
         assert_eq!(cmd.opt_args("foo-bar"), Some(&["ABC"] as &[&str]));
-        assert_eq!(cmd.has_opt("f"), true);
+        assert!(cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), Some("DEF"));
         assert_eq!(cmd.opt_args("f"), Some(&["DEF"] as &[&str]));
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -997,14 +1339,14 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), Some("ABC"));
         assert_eq!(cmd.opt_args("foo-bar"), Some(&["ABC", "GHI"] as &[&str]));
-        assert_eq!(cmd.has_opt("f"), true);
+        assert!(cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), Some("DEF"));
         assert_eq!(cmd.opt_args("f"), Some(&["DEF", "JKL"] as &[&str]));
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -1029,14 +1371,14 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), Some("ABC"));
         assert_eq!(cmd.opt_args("foo-bar"), Some(&["ABC", "DEF"] as &[&str]));
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -1058,13 +1400,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "foo-bar");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionIsNotArray {
                 store_key: sk,
                 option,
@@ -1072,14 +1414,14 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "foo-bar");
                 assert_eq!(option, "foo-bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), Some("ABC"));
         assert_eq!(cmd.opt_args("foo-bar"), Some(&["ABC"] as &[&str]));
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), None);
         assert_eq!(cmd.opt_args("f"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -1093,13 +1435,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "f");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionIsNotArray {
                 store_key: sk,
                 option,
@@ -1107,14 +1449,14 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "f");
                 assert_eq!(option, "f");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
-        assert_eq!(cmd.has_opt("f"), true);
+        assert!(cmd.has_opt("f"));
         assert_eq!(cmd.opt_arg("f"), Some("ABC"));
         assert_eq!(cmd.opt_args("f"), Some(&["ABC"] as &[&str]));
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -1137,17 +1479,17 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo"), false);
+        assert!(!cmd.has_opt("foo"));
         assert_eq!(cmd.opt_arg("foo"), None);
         assert_eq!(cmd.opt_args("foo"), None);
-        assert_eq!(cmd.has_opt("bar"), true);
+        assert!(cmd.has_opt("bar"));
         assert_eq!(cmd.opt_arg("bar"), Some("A"));
         assert_eq!(cmd.opt_args("bar"), Some(&["A"] as &[&str]));
-        assert_eq!(cmd.has_opt("baz"), true);
+        assert!(cmd.has_opt("baz"));
         assert_eq!(cmd.opt_arg("baz"), Some("B"));
         assert_eq!(cmd.opt_args("baz"), Some(&["B"] as &[&str]));
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -1161,13 +1503,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "foo-bar");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::ConfigHasDefaultsButHasNoArg {
                 store_key: sk,
                 name,
@@ -1175,11 +1517,11 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "foo-bar");
                 assert_eq!(name, "foo-bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), false);
+        assert!(!cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), None);
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -1208,25 +1550,142 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), None);
         assert_eq!(cmd.opt_args("foo-bar"), Some(&[] as &[&str]));
-        assert_eq!(cmd.has_opt("baz"), true);
+        assert!(cmd.has_opt("baz"));
         assert_eq!(cmd.opt_arg("baz"), Some("1"));
         assert_eq!(cmd.opt_args("baz"), Some(&["1", "2"] as &[&str]));
-        assert_eq!(cmd.has_opt("X"), true);
+        assert!(cmd.has_opt("X"));
         assert_eq!(cmd.opt_arg("X"), None);
         assert_eq!(cmd.opt_args("X"), Some(&[] as &[&str]));
-        assert_eq!(cmd.has_opt("corge"), true);
+        assert!(cmd.has_opt("corge"));
         assert_eq!(cmd.opt_arg("corge"), Some("99"));
         assert_eq!(cmd.opt_args("corge"), Some(&["99"] as &[&str]));
         assert_eq!(cmd.args(), &["qux", "quux"] as &[&str]);
     }
 
+    #[test]
+    fn reject_or_remove_duplicated_args_of_unique_option() {
+        let opt_cfgs = vec![OptCfg::with(&[
+            names(&["feature", "F"]),
+            has_arg(true),
+            is_array(true),
+            unique(true),
+        ])];
+        let args = [
+            "app",
+            "-F",
+            "tls",
+            "--feature=gz",
+            "-F",
+            "tls",
+            "--feature=br",
+        ];
+
+        let mut cmd = Cmd::with_strings(args.map(String::from));
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionArgIsDuplicated {
+                store_key: key,
+                option,
+                opt_arg,
+            }) => {
+                assert_eq!(key, "feature");
+                assert_eq!(option, "F");
+                assert_eq!(opt_arg, "tls");
+            }
+            Err(_) => panic!(),
+        }
+
+        let options = ParseOptions::new().duplicate_opt(DuplicatePolicy::FirstWins);
+        let mut cmd = Cmd::with_strings(args.map(String::from)).with_parse_options(options);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(
+            cmd.opt_args("feature"),
+            Some(&["tls", "gz", "br"] as &[&str])
+        );
+
+        let options = ParseOptions::new().duplicate_opt(DuplicatePolicy::LastWins);
+        let mut cmd = Cmd::with_strings(args.map(String::from)).with_parse_options(options);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(
+            cmd.opt_args("feature"),
+            Some(&["gz", "tls", "br"] as &[&str])
+        );
+
+        let opt_cfgs = vec![OptCfg::with(&[
+            names(&["feature", "F"]),
+            has_arg(true),
+            is_array(true),
+        ])];
+        let mut cmd = Cmd::with_strings(args.map(String::from));
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(
+            cmd.opt_args("feature"),
+            Some(&["tls", "gz", "tls", "br"] as &[&str])
+        );
+    }
+
+    #[test]
+    fn parse_cluster_of_flags_and_option_taking_arg() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["r"])]),
+            OptCfg::with(&[names(&["v"])]),
+            OptCfg::with(&[store_key("file"), names(&["f"]), has_arg(true)]),
+        ];
+
+        let mut cmd = Cmd::with_strings(["app", "-rvf", "archive.tar", "x"].map(String::from));
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("r"));
+        assert!(cmd.has_opt("v"));
+        assert_eq!(cmd.opt_arg("file"), Some("archive.tar"));
+        assert_eq!(cmd.args(), &["x"] as &[&str]);
+
+        let mut cmd = Cmd::with_strings(["app", "-rfv", "archive.tar"].map(String::from));
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionNeedsArgInCluster {
+                option,
+                store_key: key,
+                cluster,
+            }) => {
+                assert_eq!(option, "f");
+                assert_eq!(key, "file");
+                assert_eq!(cluster, "-rfv");
+            }
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("r"));
+        assert!(cmd.has_opt("v"));
+        assert!(!cmd.has_opt("file"));
+        assert_eq!(cmd.args(), &["archive.tar"] as &[&str]);
+
+        let mut cmd = Cmd::with_strings(["app", "-rf=archive.tar"].map(String::from));
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("r"));
+        assert_eq!(cmd.opt_arg("file"), Some("archive.tar"));
+    }
+
     #[test]
     fn parse_all_args_even_if_error() {
         let opt_cfgs = vec![OptCfg::with(&[names(&["foo", "f"])])];
@@ -1235,23 +1694,23 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "e");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::UnconfiguredOption { option }) => {
                 assert_eq!(option, "e");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("e"), false);
-        assert_eq!(cmd.has_opt("f"), false);
-        assert_eq!(cmd.has_opt("foo"), true);
+        assert!(!cmd.has_opt("e"));
+        assert!(!cmd.has_opt("f"));
+        assert!(cmd.has_opt("foo"));
         assert_eq!(cmd.opt_arg("foo"), None);
         assert_eq!(cmd.opt_args("foo"), Some(&[] as &[&str]));
         assert_eq!(cmd.args(), &["bar"] as &[&str]);
@@ -1269,13 +1728,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "f");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionTakesNoArg {
                 store_key: sk,
                 option,
@@ -1283,15 +1742,15 @@ mod tests_of_parse_with {
                 assert_eq!(option, "f");
                 assert_eq!(sk, "foo");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("e"), true);
+        assert!(cmd.has_opt("e"));
         assert_eq!(cmd.opt_arg("e"), None);
         assert_eq!(cmd.opt_args("e"), Some(&[] as &[&str]));
-        assert_eq!(cmd.has_opt("f"), false);
-        assert_eq!(cmd.has_opt("foo"), false);
+        assert!(!cmd.has_opt("f"));
+        assert!(!cmd.has_opt("foo"));
         assert_eq!(cmd.args(), &["bar"] as &[&str]);
     }
 
@@ -1311,13 +1770,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "foo");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionTakesNoArg {
                 store_key: sk,
                 option,
@@ -1325,15 +1784,15 @@ mod tests_of_parse_with {
                 assert_eq!(option, "foo");
                 assert_eq!(sk, "foo");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("e"), true);
+        assert!(cmd.has_opt("e"));
         assert_eq!(cmd.opt_arg("e"), None);
         assert_eq!(cmd.opt_args("e"), Some(&[] as &[&str]));
-        assert_eq!(cmd.has_opt("f"), false);
-        assert_eq!(cmd.has_opt("foo"), false);
+        assert!(!cmd.has_opt("f"));
+        assert!(!cmd.has_opt("foo"));
         assert_eq!(cmd.args(), &["bar"] as &[&str]);
     }
 
@@ -1350,11 +1809,11 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo"), true);
+        assert!(cmd.has_opt("foo"));
         assert_eq!(cmd.args(), &["bar"] as &[&str]);
     }
 
@@ -1370,13 +1829,13 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => {
                 assert_eq!(err.option(), "f");
             }
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::OptionNameIsDuplicated {
                 store_key: sk,
                 name,
@@ -1384,13 +1843,13 @@ mod tests_of_parse_with {
                 assert_eq!(name, "f");
                 assert_eq!(sk, "bar");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo"), false);
-        assert_eq!(cmd.has_opt("bar"), false);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("foo"));
+        assert!(!cmd.has_opt("bar"));
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.args(), &[] as &[&str]);
     }
 
@@ -1404,15 +1863,15 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("FooBar"), true);
+        assert!(cmd.has_opt("FooBar"));
         assert_eq!(cmd.opt_arg("FooBar"), None);
         assert_eq!(cmd.opt_args("FooBar"), Some(&[] as &[&str]));
-        assert_eq!(cmd.has_opt("foo"), false);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("foo"));
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.args(), &["bar"] as &[&str]);
     }
 
@@ -1427,12 +1886,12 @@ mod tests_of_parse_with {
             Ok(()) => {}
             Err(err) => {
                 println!("{:?}", err);
-                assert!(false);
+                panic!();
             }
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("FooBar"), true);
+        assert!(cmd.has_opt("FooBar"));
         assert_eq!(cmd.opt_arg("FooBar"), None);
         assert_eq!(cmd.opt_args("FooBar"), Some(&[] as &[&str]));
         assert_eq!(cmd.args(), &[] as &[&str]);
@@ -1450,11 +1909,11 @@ mod tests_of_parse_with {
 
         let result = cmd.parse_with(&opt_cfgs);
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(ref err) => assert_eq!(err.option(), "b"),
         }
         match result {
-            Ok(()) => assert!(false),
+            Ok(()) => panic!(),
             Err(InvalidOption::StoreKeyIsDuplicated {
                 store_key: sk,
                 name,
@@ -1462,15 +1921,15 @@ mod tests_of_parse_with {
                 assert_eq!(sk, "FooBar");
                 assert_eq!(name, "b");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("FooBar"), false);
-        assert_eq!(cmd.has_opt("foo"), false);
-        assert_eq!(cmd.has_opt("f"), false);
-        assert_eq!(cmd.has_opt("bar"), false);
-        assert_eq!(cmd.has_opt("b"), false);
+        assert!(!cmd.has_opt("FooBar"));
+        assert!(!cmd.has_opt("foo"));
+        assert!(!cmd.has_opt("f"));
+        assert!(!cmd.has_opt("bar"));
+        assert!(!cmd.has_opt("b"));
         assert_eq!(cmd.args(), &[] as &[&str]);
     }
 
@@ -1488,12 +1947,12 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("foo"), true);
-        assert_eq!(cmd.has_opt("bar"), true);
+        assert!(cmd.has_opt("foo"));
+        assert!(cmd.has_opt("bar"));
         assert_eq!(cmd.args(), &["baz"] as &[&str]);
     }
 
@@ -1520,15 +1979,880 @@ mod tests_of_parse_with {
         let result = cmd.parse_with(&opt_cfgs);
         match result {
             Ok(()) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("Bar"), true);
+        assert!(cmd.has_opt("Bar"));
         assert_eq!(cmd.opt_arg("Bar"), Some("1"));
         assert_eq!(cmd.opt_args("Bar"), Some(&["1", "2", "3"] as &[&str]));
-        assert_eq!(cmd.has_opt("foo"), false);
-        assert_eq!(cmd.has_opt("f"), false);
+        assert!(!cmd.has_opt("foo"));
+        assert!(!cmd.has_opt("f"));
         assert_eq!(cmd.args(), &[] as &[&str]);
     }
+
+    #[test]
+    fn call_on_parse_in_order_of_command_line_arguments() {
+        use std::cell::RefCell;
+
+        thread_local! {
+            static CALLS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+        fn record(key: &str, name: &str, arg: &str) {
+            CALLS.with(|c| c.borrow_mut().push(format!("{key}:{name}:{arg}")));
+        }
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["config", "c"]), has_arg(true), on_parse(record)]),
+            OptCfg::with(&[names(&["verbose", "v"]), on_parse(record)]),
+            OptCfg::with(&[
+                names(&["baz"]),
+                has_arg(true),
+                defaults(&["1"]),
+                on_parse(record),
+            ]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "-v".to_string(),
+            "--config".to_string(),
+            "a.toml".to_string(),
+            "arg".to_string(),
+            "--verbose".to_string(),
+        ]);
+
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+
+        CALLS.with(|c| {
+            assert_eq!(
+                *c.borrow(),
+                vec!["verbose:v:", "config:config:a.toml", "verbose:verbose:"]
+            );
+        });
+        assert_eq!(cmd.opt_arg("baz"), Some("1"));
+    }
+
+    #[test]
+    fn allow_underscore_if_a_cfg_name_contains_it() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["dry_run", "n"])]),
+            OptCfg::with(&[store_key("logLevel"), names(&["log_level"]), has_arg(true)]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "--dry_run".to_string(),
+            "--log_level=debug".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(cmd.has_opt("dry_run"));
+        assert_eq!(cmd.opt_arg("logLevel"), Some("debug"));
+
+        let opt_cfgs = vec![OptCfg::with(&[names(&["dry-run"])])];
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--dry_run".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionContainsInvalidChar { option }) => {
+                assert_eq!(option, "dry_run");
+            }
+            Err(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn accept_plus_prefix_for_cfgs_accepting_it() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["x"]), accepts_plus(true)]),
+            OptCfg::with(&[names(&["v"]), accepts_plus(true)]),
+            OptCfg::with(&[
+                names(&["o"]),
+                has_arg(true),
+                is_array(true),
+                accepts_plus(true),
+            ]),
+            OptCfg::with(&[names(&["q"])]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "+x".to_string(),
+            "-v".to_string(),
+            "+o".to_string(),
+            "vi".to_string(),
+            "-o=emacs".to_string(),
+            "+vq".to_string(),
+            "+".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+
+        assert!(cmd.has_opt("x"));
+        assert!(cmd.is_plus_opt("x"));
+        assert!(cmd.has_opt("v"));
+        assert!(!cmd.is_plus_opt("v"));
+        assert_eq!(cmd.opt_args("o"), Some(&["vi", "emacs"] as &[&str]));
+        assert!(!cmd.is_plus_opt("o"));
+        assert!(!cmd.has_opt("q"));
+        assert_eq!(cmd.args(), &["+vq", "+"] as &[&str]);
+    }
+
+    #[test]
+    fn accept_attached_number_for_numeric_short_options() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["lines", "n"]), has_arg(true), is_numeric(true)]),
+            OptCfg::with(&[names(&["jobs", "j"]), has_arg(true), is_numeric(true)]),
+            OptCfg::with(&[names(&["v"])]),
+            OptCfg::with(&[names(&["o"]), has_arg(true)]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "-n5".to_string(),
+            "-vj16".to_string(),
+            "file".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("lines"), Some("5"));
+        assert_eq!(cmd.opt_arg("jobs"), Some("16"));
+        assert!(cmd.has_opt("v"));
+        assert_eq!(cmd.args(), &["file"] as &[&str]);
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "-o5".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionNeedsArg { option, .. }) => {
+                assert_eq!(option, "o");
+            }
+            Err(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn use_default_missing_value_if_option_has_no_arg() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[
+                names(&["color", "c"]),
+                has_arg(true),
+                default_missing_value("auto"),
+            ]),
+            OptCfg::with(&[names(&["v"])]),
+        ];
+
+        let mut cmd =
+            Cmd::with_strings(["app".to_string(), "--color".to_string(), "foo".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("color"), Some("auto"));
+        assert_eq!(cmd.args(), &["foo"] as &[&str]);
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--color=always".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("color"), Some("always"));
+
+        let mut cmd =
+            Cmd::with_strings(["app".to_string(), "-cv".to_string(), "-c=never".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionIsNotArray { option, .. }) => {
+                assert_eq!(option, "c");
+            }
+            Err(_) => panic!(),
+        }
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "-vc".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("color"), Some("auto"));
+        assert!(cmd.has_opt("v"));
+    }
+
+    #[test]
+    fn record_occurrences_of_options() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[
+                names(&["foo", "f"]),
+                has_arg(true),
+                is_array(true),
+                defaults(&["0"]),
+            ]),
+            OptCfg::with(&[store_key("Bar"), names(&["bar", "b"])]),
+            OptCfg::with(&[names(&["baz"]), has_arg(true), defaults(&["0"])]),
+            OptCfg::with(&[store_key("*")]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "-f".to_string(),
+            "1".to_string(),
+            "--bar".to_string(),
+            "qux".to_string(),
+            "--foo=2".to_string(),
+            "-bf=3".to_string(),
+            "--quux".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+
+        assert_eq!(cmd.opt_args("foo"), Some(&["1", "2", "3"] as &[&str]));
+        assert_eq!(cmd.opt_occurrence_count("foo"), 3);
+        assert_eq!(cmd.opt_indices("foo"), &[1, 5, 6]);
+        assert_eq!(cmd.opt_occurrence_count("Bar"), 2);
+        assert_eq!(cmd.opt_indices("Bar"), &[3, 6]);
+        assert_eq!(cmd.opt_occurrence_count("bar"), 0);
+        assert_eq!(cmd.opt_arg("baz"), Some("0"));
+        assert_eq!(cmd.opt_occurrence_count("baz"), 0);
+        assert_eq!(cmd.opt_indices("quux"), &[7]);
+    }
+
+    #[test]
+    fn trim_option_args() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo"]), has_arg(true), trims_arg(true)]),
+            OptCfg::with(&[names(&["bar"]), has_arg(true)]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "--foo= a b ".to_string(),
+            "--bar".to_string(),
+            " c ".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("foo"), Some("a b"));
+        assert_eq!(cmd.opt_arg("bar"), Some(" c "));
+    }
+
+    #[test]
+    fn apply_empty_arg_policies() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo"]), has_arg(true)]),
+            OptCfg::with(&[
+                names(&["bar"]),
+                has_arg(true),
+                trims_arg(true),
+                empty_arg(EmptyArgPolicy::Error),
+            ]),
+            OptCfg::with(&[
+                names(&["baz"]),
+                has_arg(true),
+                empty_arg(EmptyArgPolicy::AsMissing),
+                default_missing_value("auto"),
+            ]),
+            OptCfg::with(&[
+                names(&["qux"]),
+                has_arg(true),
+                empty_arg(EmptyArgPolicy::AsMissing),
+            ]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "--foo=".to_string(),
+            "--baz".to_string(),
+            "".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("foo"), Some(""));
+        assert_eq!(cmd.opt_arg("baz"), Some("auto"));
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--bar".to_string(), " ".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionArgIsEmpty {
+                option,
+                store_key: key,
+            }) => {
+                assert_eq!(option, "bar");
+                assert_eq!(key, "bar");
+            }
+            Err(_) => panic!(),
+        }
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--qux=".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionNeedsArg {
+                option,
+                store_key: key,
+            }) => {
+                assert_eq!(option, "qux");
+                assert_eq!(key, "qux");
+            }
+            Err(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn split_option_name_and_arg_at_first_equal_sign() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["filter"]), has_arg(true)]),
+            OptCfg::with(&[names(&["f"]), has_arg(true)]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "--filter=key=value".to_string(),
+            "-f==x".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("filter"), Some("key=value"));
+        assert_eq!(cmd.opt_arg("f"), Some("=x"));
+    }
+
+    #[test]
+    fn store_raw_option_args() {
+        let opt_cfgs = vec![OptCfg::with(&[
+            names(&["foo"]),
+            has_arg(true),
+            is_array(true),
+            trims_arg(true),
+            empty_arg(EmptyArgPolicy::Error),
+            is_path(true),
+            raw_arg(true),
+        ])];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "--foo= ~/$HOME ".to_string(),
+            "--foo=".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_args("foo"), Some(&[" ~/$HOME ", ""] as &[&str]));
+    }
+
+    #[test]
+    fn treat_all_args_as_command_args_if_after_end_opt() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo"])]),
+            OptCfg::with(&[names(&["bar"]), has_arg(true), defaults(&["1"])]),
+        ];
+
+        let mut cmd = Cmd::with_strings_after_end_opt(
+            ["sub".to_string(), "--foo".to_string(), "--baz".to_string()],
+            true,
+        );
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(!cmd.has_opt("foo"));
+        assert_eq!(cmd.opt_arg("bar"), Some("1"));
+        assert_eq!(cmd.args(), &["--foo", "--baz"]);
+
+        let mut cmd =
+            Cmd::with_strings_and_cfgs(["sub".to_string(), "--foo".to_string()], opt_cfgs.clone());
+        cmd.is_after_end_opt = true;
+        match cmd.parse() {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert!(!cmd.has_opt("foo"));
+        assert_eq!(cmd.args(), &["--foo"]);
+    }
+
+    #[test]
+    fn validate_many_args_of_array_option_in_order() {
+        let opt_cfgs = vec![OptCfg::with(&[
+            names(&["num", "n"]),
+            has_arg(true),
+            is_array(true),
+            validator(crate::validators::validate_number::<u32>),
+        ])];
+
+        let mut args = vec!["app".to_string()];
+        for i in 0..1000 {
+            match i {
+                500 => args.push("--num=x500".to_string()),
+                700 => args.push("--num=x700".to_string()),
+                _ => args.push(format!("-n={i}")),
+            }
+        }
+
+        let mut cmd = Cmd::with_strings(args);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionArgIsInvalid {
+                option, opt_arg, ..
+            }) => {
+                assert_eq!(option, "num");
+                assert_eq!(opt_arg, "x500");
+            }
+            Err(_) => panic!(),
+        }
+
+        let args = (0..1000).map(|i| format!("-n={i}"));
+        let mut cmd = Cmd::with_strings(["app".to_string()].into_iter().chain(args));
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_args("num").map(|v| v.len()), Some(1000));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn emit_tracing_events() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        struct Messages(Arc<Mutex<Vec<String>>>);
+
+        struct Message(String);
+
+        impl Visit for Message {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0 = format!("{:?}", value);
+                }
+            }
+        }
+
+        impl Subscriber for Messages {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut message = Message(String::new());
+                event.record(&mut message);
+                self.0.lock().unwrap().push(message.0);
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo"]), has_arg(true), defaults(&["1"])]),
+            OptCfg::with(&[names(&["bar"]), has_arg(true)]),
+        ];
+
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        tracing::subscriber::with_default(Messages(messages.clone()), || {
+            let mut cmd = Cmd::with_strings(["app", "--bar=2"].map(String::from));
+            cmd.parse_with(&opt_cfgs).unwrap();
+
+            let mut cmd = Cmd::with_strings(["app", "--bar"].map(String::from));
+            assert!(cmd.parse_with(&opt_cfgs).is_err());
+        });
+
+        assert_eq!(
+            *messages.lock().unwrap(),
+            [
+                "parsed an option",
+                "applied default values",
+                "failed to parse"
+            ]
+        );
+    }
+
+    #[test]
+    fn record_option_names_used() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[store_key("force"), names(&["force", "f"])]),
+            OptCfg::with(&[names(&["bar", "b"]), has_arg(true), is_array(true)]),
+            OptCfg::with(&[names(&["baz"])]),
+            OptCfg::with(&[store_key("*")]),
+        ];
+
+        let mut cmd = Cmd::with_strings([
+            "app".to_string(),
+            "--bar=1".to_string(),
+            "-f".to_string(),
+            "-b".to_string(),
+            "2".to_string(),
+            "--qux".to_string(),
+        ]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+
+        assert_eq!(cmd.opt_name_used("force"), Some("f"));
+        assert_eq!(cmd.opt_name_used("bar"), Some("b"));
+        assert_eq!(cmd.opt_name_used("baz"), None);
+        assert_eq!(cmd.opt_name_used("qux"), Some("qux"));
+    }
+
+    #[test]
+    fn use_conditional_defaults() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["machine"])]),
+            OptCfg::with(&[names(&["color"]), has_arg(true)]),
+            OptCfg::with(&[
+                names(&["format"]),
+                has_arg(true),
+                defaults(&["table"]),
+                defaults_if("machine", None, &["json"]),
+                defaults_if("color", Some("never"), &["plain"]),
+            ]),
+            OptCfg::with(&[
+                names(&["width"]),
+                has_arg(true),
+                defaults_if("format", None, &["80"]),
+            ]),
+        ];
+
+        let mut cmd = Cmd::with_strings(["app".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("format"), Some("table"));
+        assert!(!cmd.has_opt("width"));
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--machine".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("format"), Some("json"));
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--color=never".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("format"), Some("plain"));
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--color=always".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("format"), Some("table"));
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--format=csv".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("format"), Some("csv"));
+        assert_eq!(cmd.opt_arg("width"), Some("80"));
+    }
+
+    #[test]
+    fn compute_defaults_only_if_needed() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn cpu_count() -> Vec<String> {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            vec!["8".to_string()]
+        }
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["fast"])]),
+            OptCfg::with(&[
+                names(&["jobs", "j"]),
+                has_arg(true),
+                defaults_if("fast", None, &["16"]),
+                default_fn(cpu_count),
+            ]),
+        ];
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "-j".to_string(), "2".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("jobs"), Some("2"));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--fast".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("jobs"), Some("16"));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+
+        let mut cmd = Cmd::with_strings(["app".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("jobs"), Some("8"));
+        assert!(cmd.defaulted.contains(&"jobs"));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn interpolate_env_vars_in_defaults() {
+        std::env::set_var("TEST3964_PARSE_HOME", "/home/me");
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[
+                names(&["cache"]),
+                has_arg(true),
+                defaults(&["${TEST3964_PARSE_HOME}/.cache/app"]),
+                interpolates_defaults(true),
+            ]),
+            OptCfg::with(&[
+                names(&["price"]),
+                has_arg(true),
+                defaults(&["$$5 ${TEST3964_PARSE_HOME}"]),
+                interpolates_defaults(true),
+            ]),
+            OptCfg::with(&[
+                names(&["template"]),
+                has_arg(true),
+                defaults(&["${TEST3964_PARSE_HOME}"]),
+            ]),
+            OptCfg::with(&[
+                names(&["pattern"]),
+                has_arg(true),
+                defaults(&["^\\$[0-9]+$$"]),
+            ]),
+        ];
+
+        let mut cmd = Cmd::with_strings(["app".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("cache"), Some("/home/me/.cache/app"));
+        assert_eq!(cmd.opt_arg("price"), Some("$5 /home/me"));
+        assert_eq!(cmd.opt_arg("template"), Some("${TEST3964_PARSE_HOME}"));
+        assert_eq!(cmd.opt_arg("pattern"), Some("^\\$[0-9]+$$"));
+
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--cache=${X}".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("cache"), Some("${X}"));
+    }
+
+    #[test]
+    fn reuse_cfgs_across_multiple_parses() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["foo", "f"]), has_arg(true), defaults(&["0"])]),
+            OptCfg::with(&[names(&["bar"])]),
+        ];
+
+        let mut cmd1 = Cmd::with_strings(["app".to_string(), "-f=1".to_string()]);
+        let mut cmd2 = Cmd::with_strings(["app".to_string(), "--bar".to_string()]);
+        match (cmd1.parse_with(&opt_cfgs), cmd2.parse_with(&opt_cfgs)) {
+            (Ok(()), Ok(())) => {}
+            _ => panic!(),
+        }
+
+        assert_eq!(cmd1.opt_arg("foo"), Some("1"));
+        assert!(!cmd1.has_opt("bar"));
+        assert_eq!(cmd2.opt_arg("foo"), Some("0"));
+        assert!(cmd2.has_opt("bar"));
+        assert_eq!(opt_cfgs.len(), 2);
+    }
+
+    #[test]
+    fn expand_path_options() {
+        std::env::set_var("TEST3949_DATA", "/data");
+        let home = std::env::var("HOME").unwrap();
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[
+                names(&["input"]),
+                has_arg(true),
+                is_array(true),
+                is_path(true),
+            ]),
+            OptCfg::with(&[
+                names(&["out"]),
+                has_arg(true),
+                is_path(true),
+                defaults(&["~/out"]),
+            ]),
+            OptCfg::with(&[names(&["name"]), has_arg(true)]),
+        ];
+
+        let mut cmd = Cmd::with_strings(
+            [
+                "app",
+                "--input=~/a.txt",
+                "--input",
+                "$TEST3949_DATA/b.txt",
+                "--name=~/x",
+            ]
+            .map(String::from),
+        );
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(
+            cmd.opt_args("input"),
+            Some(&[format!("{home}/a.txt").as_str(), "/data/b.txt"] as &[&str])
+        );
+        assert_eq!(cmd.opt_arg("out"), Some(format!("{home}/out").as_str()));
+        assert_eq!(cmd.opt_arg("name"), Some("~/x"));
+    }
+
+    #[test]
+    fn convert_option_args_with_si_suffixes() {
+        std::env::set_var("TEST3976_SI_BURST", "2k");
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[
+                names(&["rate"]),
+                has_arg(true),
+                accepts_si_suffix(true),
+                validator(crate::validators::validate_number::<f64>),
+            ]),
+            OptCfg::with(&[
+                names(&["limit"]),
+                has_arg(true),
+                is_array(true),
+                accepts_si_suffix(true),
+                validator(crate::validators::validate_number::<u64>),
+            ]),
+            OptCfg::with(&[names(&["burst"]), has_arg(true), accepts_si_suffix(true)]),
+            OptCfg::with(&[
+                names(&["timeout"]),
+                has_arg(true),
+                accepts_si_suffix(true),
+                defaults(&["1.5k"]),
+            ]),
+            OptCfg::with(&[names(&["name"]), has_arg(true)]),
+            OptCfg::with(&[
+                names(&["raw"]),
+                has_arg(true),
+                accepts_si_suffix(true),
+                raw_arg(true),
+            ]),
+        ];
+
+        let mut cmd = Cmd::with_strings(
+            [
+                "app",
+                "--rate=1.5m",
+                "--limit",
+                "2M",
+                "--limit=10",
+                "--name=3k",
+                "--raw=4k",
+            ]
+            .map(String::from),
+        )
+        .with_env_prefix("TEST3976_SI");
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_arg("rate"), Some("0.0015"));
+        assert_eq!(cmd.opt_args("limit"), Some(&["2000000", "10"] as &[&str]));
+        assert_eq!(cmd.opt_arg("burst"), Some("2000"));
+        assert_eq!(cmd.opt_arg("timeout"), Some("1500"));
+        assert_eq!(cmd.opt_arg("name"), Some("3k"));
+        assert_eq!(cmd.opt_arg("raw"), Some("4k"));
+
+        let mut cmd = Cmd::with_strings(["app", "--limit=1.5k", "--limit=1.5"].map(String::from));
+        match cmd.parse_with(&opt_cfgs) {
+            Err(InvalidOption::OptionArgIsInvalid { opt_arg, .. }) => {
+                assert_eq!(opt_arg, "1.5");
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn return_first_error_in_order_of_args_for_many_array_args() {
+        use crate::validators::validate_number;
+        use std::cell::RefCell;
+
+        thread_local! {
+            static CALLS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+        fn record(_key: &str, _name: &str, arg: &str) {
+            CALLS.with(|c| c.borrow_mut().push(arg.to_string()));
+        }
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[
+                names(&["num", "n"]),
+                has_arg(true),
+                is_array(true),
+                validator(validate_number::<u32>),
+                on_parse(record),
+            ]),
+            OptCfg::with(&[names(&["verbose", "v"])]),
+        ];
+
+        // More arguments than validated in parallel with the rayon feature.
+        let mut args = vec!["app".to_string()];
+        for i in 0..300 {
+            args.push("-n".to_string());
+            args.push(if i == 3 {
+                "x".to_string()
+            } else {
+                i.to_string()
+            });
+        }
+        args.push("--foo".to_string());
+
+        let mut cmd = Cmd::with_strings(args.clone());
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::OptionArgIsInvalid {
+                option, opt_arg, ..
+            }) => {
+                assert_eq!(option, "n");
+                assert_eq!(opt_arg, "x");
+            }
+            Err(_) => panic!(),
+        }
+        let opt_args = cmd.opt_args("num").unwrap();
+        assert_eq!(opt_args.len(), 299);
+        assert!(!opt_args.contains(&"x"));
+        assert_eq!(CALLS.with(|c| c.borrow().len()), 299);
+        assert!(!CALLS.with(|c| c.borrow().contains(&"x".to_string())));
+
+        args.insert(1, "--foo".to_string());
+        let mut cmd = Cmd::with_strings(args);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => panic!(),
+            Err(InvalidOption::UnconfiguredOption { option }) => assert_eq!(option, "foo"),
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.opt_args("num").unwrap().len(), 299);
+    }
 }
@@ -0,0 +1,190 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::env;
+
+/// Expands a leading `~` to the home directory and environment variables like
+/// `$VAR`, `${VAR}`, or `%VAR%` in the specified path string.
+///
+/// `~` is expanded only when it is the whole string or is followed by a path
+/// separator.
+/// The home directory is taken from the environment variable `HOME`, or
+/// `USERPROFILE` on Windows.
+/// Undefined environment variables are left as they are.
+///
+/// This function is applied to the option arguments of options of which
+/// `OptCfg#is_path` is `true` when parsing.
+///
+/// ```
+/// use cliargs::expand_path;
+///
+/// std::env::set_var("CLIARGS_DOC_DIR", "/opt/data");
+/// assert_eq!(expand_path("$CLIARGS_DOC_DIR/in.txt"), "/opt/data/in.txt");
+/// assert_eq!(expand_path("${CLIARGS_DOC_DIR}/in.txt"), "/opt/data/in.txt");
+/// assert_eq!(expand_path("%CLIARGS_DOC_DIR%/in.txt"), "/opt/data/in.txt");
+/// ```
+pub fn expand_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut rest = path;
+
+    if let Some(after) = rest.strip_prefix('~') {
+        if after.is_empty() || after.starts_with('/') || after.starts_with('\\') {
+            if let Some(home) = home_dir() {
+                out.push_str(&home);
+                rest = after;
+            }
+        }
+    }
+
+    while let Some(i) = rest.find(['$', '%']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        match expand_var(tail) {
+            Some((value, len)) => {
+                out.push_str(&value);
+                rest = &tail[len..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+/// Expands environment variables written like `${VAR}` in the specified
+/// string, and unescapes `$$` to `$`.
+///
+/// Undefined environment variables are left as they are, and `$` which is
+/// not followed by `{` or `$` is left as it is.
+///
+/// This function is applied to the default values of options of which
+/// `OptCfg#interpolates_defaults` is `true` when parsing.
+///
+/// ```
+/// use cliargs::interpolate_env;
+///
+/// std::env::set_var("CLIARGS_DOC_HOME", "/home/me");
+/// assert_eq!(interpolate_env("${CLIARGS_DOC_HOME}/.cache/app"), "/home/me/.cache/app");
+/// assert_eq!(interpolate_env("$${CLIARGS_DOC_HOME}"), "${CLIARGS_DOC_HOME}");
+/// ```
+pub fn interpolate_env(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(i) = rest.find('$') {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if let Some(after) = tail.strip_prefix("$$") {
+            out.push('$');
+            rest = after;
+            continue;
+        }
+        match tail.starts_with("${").then(|| expand_var(tail)).flatten() {
+            Some((value, len)) => {
+                out.push_str(&value);
+                rest = &tail[len..];
+            }
+            None => {
+                out.push('$');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+fn expand_var(s: &str) -> Option<(String, usize)> {
+    let (name, len) = if let Some(t) = s.strip_prefix("${") {
+        let end = t.find('}')?;
+        (&t[..end], end + 3)
+    } else if let Some(t) = s.strip_prefix('$') {
+        let end = t
+            .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')
+            .unwrap_or(t.len());
+        (&t[..end], end + 1)
+    } else {
+        let t = &s[1..];
+        let end = t.find('%')?;
+        (&t[..end], end + 2)
+    };
+
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(ch) if ch.is_ascii_alphabetic() || ch == '_' => {}
+        _ => return None,
+    }
+    if !chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_') {
+        return None;
+    }
+
+    env::var(name).ok().map(|value| (value, len))
+}
+
+fn home_dir() -> Option<String> {
+    if cfg!(windows) {
+        env::var("USERPROFILE").ok()
+    } else {
+        env::var("HOME").ok()
+    }
+}
+
+#[cfg(test)]
+mod tests_of_expand_path {
+    use super::*;
+
+    #[test]
+    fn should_expand_tilde() {
+        let home = home_dir().unwrap();
+        assert_eq!(expand_path("~"), home);
+        assert_eq!(expand_path("~/a/b"), format!("{}/a/b", home));
+        assert_eq!(expand_path("~user/a"), "~user/a");
+        assert_eq!(expand_path("a/~/b"), "a/~/b");
+    }
+
+    #[test]
+    fn should_expand_env_vars() {
+        env::set_var("TEST3949_DIR", "/x/y");
+        env::set_var("TEST3949_NAME", "z");
+
+        assert_eq!(
+            expand_path("$TEST3949_DIR/$TEST3949_NAME.txt"),
+            "/x/y/z.txt"
+        );
+        assert_eq!(expand_path("${TEST3949_DIR}abc"), "/x/yabc");
+        assert_eq!(expand_path("%TEST3949_DIR%\\f"), "/x/y\\f");
+        assert_eq!(
+            expand_path("$TEST3949_UNDEFINED/a"),
+            "$TEST3949_UNDEFINED/a"
+        );
+        assert_eq!(expand_path("${TEST3949_DIR"), "${TEST3949_DIR");
+        assert_eq!(expand_path("100% $ %"), "100% $ %");
+        assert_eq!(expand_path("$1"), "$1");
+    }
+
+    #[test]
+    fn should_interpolate_env_vars() {
+        env::set_var("TEST3964_HOME", "/home/me");
+
+        assert_eq!(
+            interpolate_env("${TEST3964_HOME}/.cache/app"),
+            "/home/me/.cache/app"
+        );
+        assert_eq!(interpolate_env("$TEST3964_HOME/a"), "$TEST3964_HOME/a");
+        assert_eq!(interpolate_env("%TEST3964_HOME%"), "%TEST3964_HOME%");
+        assert_eq!(interpolate_env("$${TEST3964_HOME}"), "${TEST3964_HOME}");
+        assert_eq!(interpolate_env("$$${TEST3964_HOME}"), "$/home/me");
+        assert_eq!(
+            interpolate_env("${TEST3964_UNDEFINED}/a"),
+            "${TEST3964_UNDEFINED}/a"
+        );
+        assert_eq!(interpolate_env("${TEST3964_HOME"), "${TEST3964_HOME");
+        assert_eq!(interpolate_env("cost: $5"), "cost: $5");
+    }
+}
@@ -0,0 +1,393 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::errors::InvalidOption;
+use crate::Cmd;
+use crate::OptCfg;
+use std::io::{self, BufRead, Write};
+
+/// The trait for backends which ask the user for values interactively.
+///
+/// This trait is used by the interactive features of this crate, like
+/// `Cmd#prompt_if_missing`, and GUI or TUI applications can implement it to
+/// show their own dialogs.
+pub trait Prompt {
+    /// Asks the user for a value with the specified message.
+    fn ask(&mut self, message: &str) -> io::Result<String>;
+
+    /// Asks the user for a secret value, like a password, with the specified
+    /// message, without showing the input.
+    fn ask_secret(&mut self, message: &str) -> io::Result<String>;
+
+    /// Asks the user for a yes/no confirmation with the specified message,
+    /// and returns `true` if the answer is yes.
+    fn confirm(&mut self, message: &str) -> io::Result<bool>;
+}
+
+/// The default implementation of `Prompt` which reads answers from the
+/// standard input and writes messages to the standard error.
+///
+/// `ask_secret` disables the echo of the terminal with `stty` on Unix-like
+/// platforms while reading the answer.
+/// On other platforms, or if `stty` is not available, the input is echoed.
+pub struct TerminalPrompt {
+    reader: Box<dyn BufRead>,
+    writer: Box<dyn Write>,
+    is_tty: bool,
+}
+
+impl TerminalPrompt {
+    /// Creates a `TerminalPrompt` instance which uses the standard input and
+    /// the standard error.
+    pub fn new() -> Self {
+        TerminalPrompt {
+            reader: Box::new(io::BufReader::new(io::stdin())),
+            writer: Box::new(io::stderr()),
+            is_tty: true,
+        }
+    }
+
+    /// Creates a `TerminalPrompt` instance which uses the specified reader and
+    /// writer instead of the standard input and the standard error.
+    pub fn with_io(reader: impl BufRead + 'static, writer: impl Write + 'static) -> Self {
+        TerminalPrompt {
+            reader: Box::new(reader),
+            writer: Box::new(writer),
+            is_tty: false,
+        }
+    }
+
+    fn read_answer(&mut self, message: &str) -> io::Result<String> {
+        write!(self.writer, "{}", message)?;
+        self.writer.flush()?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "no answer is given",
+            ));
+        }
+        Ok(line.trim_end_matches(['\n', '\r']).to_string())
+    }
+
+    fn set_echo(&self, on: bool) -> bool {
+        if !self.is_tty || !cfg!(unix) {
+            return false;
+        }
+        std::process::Command::new("stty")
+            .arg(if on { "echo" } else { "-echo" })
+            .stdin(std::process::Stdio::inherit())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    }
+}
+
+impl Default for TerminalPrompt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Prompt for TerminalPrompt {
+    fn ask(&mut self, message: &str) -> io::Result<String> {
+        self.read_answer(message)
+    }
+
+    fn ask_secret(&mut self, message: &str) -> io::Result<String> {
+        let echo_off = self.set_echo(false);
+        let result = self.read_answer(message);
+        if echo_off {
+            self.set_echo(true);
+            writeln!(self.writer)?;
+        }
+        result
+    }
+
+    fn confirm(&mut self, message: &str) -> io::Result<bool> {
+        loop {
+            let answer = self.read_answer(&format!("{} [y/N] ", message))?;
+            match answer.trim().to_ascii_lowercase().as_str() {
+                "y" | "yes" => return Ok(true),
+                "" | "n" | "no" => return Ok(false),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'a> Cmd<'a> {
+    /// Asks the user for the value of the option with the specified store key
+    /// through the specified `Prompt` if the option is not specified.
+    ///
+    /// This method should be called after parsing.
+    /// If `secret` is `true`, the value is asked with `Prompt#ask_secret`.
+    /// The answer is stored as the option argument, and the option is no
+    /// longer treated as defaulted.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, TerminalPrompt};
+    ///
+    /// let mut cmd = Cmd::with_strings(["app".to_string()]);
+    /// cmd.parse().unwrap();
+    ///
+    /// let mut prompt = TerminalPrompt::with_io(&b"alice\n"[..], std::io::sink());
+    /// cmd.prompt_if_missing("user", "User: ", false, &mut prompt).unwrap();
+    /// assert_eq!(cmd.opt_arg("user"), Some("alice"));
+    /// ```
+    pub fn prompt_if_missing(
+        &mut self,
+        store_key: &str,
+        message: &str,
+        secret: bool,
+        prompt: &mut dyn Prompt,
+    ) -> io::Result<()> {
+        if self.opts.contains_key(store_key) && !self.defaulted.contains(&store_key) {
+            return Ok(());
+        }
+
+        let answer = if secret {
+            prompt.ask_secret(message)?
+        } else {
+            prompt.ask(message)?
+        };
+
+        self.defaulted.retain(|k| *k != store_key);
+        self.opts.retain(|k, _| *k != store_key);
+
+        let key = self.arena.alloc(store_key);
+        let arg = self.arena.alloc(&answer);
+        self.opts.insert(key, vec![arg]);
+
+        Ok(())
+    }
+}
+
+impl<'a> Cmd<'a> {
+    /// Parses command line arguments with option configurations, and asks the
+    /// user for confirmations of the specified options which require them.
+    ///
+    /// An option requires a confirmation if its `OptCfg#confirm` is `Some`,
+    /// and the confirmation is asked through the specified `Prompt` with the
+    /// message only when the option is specified in command line arguments.
+    /// If the option of which the store key is `yes`, like `--yes`, is also
+    /// specified, no confirmation is asked.
+    /// If the user does not confirm, or the prompt fails, this method returns
+    /// `InvalidOption::OptionIsNotConfirmed`.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg, TerminalPrompt};
+    /// use cliargs::errors::InvalidOption;
+    /// use cliargs::OptCfgParam::{names, confirm};
+    ///
+    /// let opt_cfgs = vec![
+    ///     OptCfg::with(&[names(&["force", "f"]), confirm("Overwrite files?")]),
+    ///     OptCfg::with(&[names(&["yes", "y"])]),
+    /// ];
+    ///
+    /// let mut cmd = Cmd::with_strings(["app", "-f"].map(String::from));
+    /// let mut prompt = TerminalPrompt::with_io(&b"n\n"[..], std::io::sink());
+    /// match cmd.parse_with_prompt(&opt_cfgs, &mut prompt) {
+    ///     Err(InvalidOption::OptionIsNotConfirmed { store_key, .. }) => {
+    ///         assert_eq!(store_key, "force");
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// let mut cmd = Cmd::with_strings(["app", "-f", "-y"].map(String::from));
+    /// assert_eq!(cmd.parse_with_prompt(&opt_cfgs, &mut prompt), Ok(()));
+    /// ```
+    pub fn parse_with_prompt(
+        &mut self,
+        opt_cfgs: &[OptCfg],
+        prompt: &mut dyn Prompt,
+    ) -> Result<(), InvalidOption> {
+        self.parse_with(opt_cfgs)?;
+
+        let is_given =
+            |cmd: &Cmd, key: &str| cmd.opts.contains_key(key) && !cmd.defaulted.contains(&key);
+        if is_given(self, "yes") {
+            return Ok(());
+        }
+
+        for cfg in opt_cfgs.iter() {
+            let Some(message) = &cfg.confirm else {
+                continue;
+            };
+            let Some(name) = cfg.names.first() else {
+                continue;
+            };
+            let store_key = if cfg.store_key.is_empty() {
+                name.as_str()
+            } else {
+                cfg.store_key.as_str()
+            };
+            if !is_given(self, store_key) {
+                continue;
+            }
+
+            if !prompt.confirm(message).unwrap_or(false) {
+                return Err(InvalidOption::OptionIsNotConfirmed {
+                    store_key: store_key.to_string(),
+                    option: name.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_of_prompt {
+    use super::*;
+    use crate::OptCfgParam::{confirm, defaults, has_arg, names, store_key};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct Output(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Output {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn should_ask_values() {
+        let out = Output::default();
+        let mut prompt = TerminalPrompt::with_io(&b"foo\r\nbar\n"[..], out.clone());
+
+        assert_eq!(prompt.ask("Name: ").unwrap(), "foo");
+        assert_eq!(prompt.ask_secret("Password: ").unwrap(), "bar");
+        assert_eq!(out.text(), "Name: Password: ");
+
+        match prompt.ask("Name: ") {
+            Err(e) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            Ok(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn should_confirm() {
+        let out = Output::default();
+        let mut prompt = TerminalPrompt::with_io(&b"maybe\nYes\n\nn\n"[..], out.clone());
+
+        assert!(prompt.confirm("Delete?").unwrap());
+        assert!(!prompt.confirm("Delete?").unwrap());
+        assert!(!prompt.confirm("Delete?").unwrap());
+        assert_eq!(
+            out.text(),
+            "Delete? [y/N] Delete? [y/N] Delete? [y/N] Delete? [y/N] "
+        );
+    }
+
+    #[test]
+    fn should_not_confirm_if_after_end_opt() {
+        let opt_cfgs = vec![OptCfg::with(&[names(&["force"]), confirm("Sure?")])];
+        let mut cmd = Cmd::with_strings_after_end_opt(["sub", "--force"].map(String::from), true);
+
+        let out = Output::default();
+        let mut prompt = TerminalPrompt::with_io(&b""[..], out.clone());
+        match cmd.parse_with_prompt(&opt_cfgs, &mut prompt) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(out.text(), "");
+        assert!(!cmd.has_opt("force"));
+        assert_eq!(cmd.args(), &["--force"]);
+    }
+
+    #[test]
+    fn should_prompt_only_if_option_is_missing() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["user"]), has_arg(true)]),
+            OptCfg::with(&[names(&["host"]), has_arg(true), defaults(&["localhost"])]),
+            OptCfg::with(&[names(&["password"]), has_arg(true)]),
+        ];
+        let mut cmd = Cmd::with_strings(["app", "--user=bob"].map(String::from));
+        cmd.parse_with(&opt_cfgs).unwrap();
+
+        let out = Output::default();
+        let mut prompt = TerminalPrompt::with_io(&b"example.com\nsecret\n"[..], out.clone());
+        cmd.prompt_if_missing("user", "User: ", false, &mut prompt)
+            .unwrap();
+        cmd.prompt_if_missing("host", "Host: ", false, &mut prompt)
+            .unwrap();
+        cmd.prompt_if_missing("password", "Password: ", true, &mut prompt)
+            .unwrap();
+
+        assert_eq!(out.text(), "Host: Password: ");
+        assert_eq!(cmd.opt_arg("user"), Some("bob"));
+        assert_eq!(cmd.opt_arg("host"), Some("example.com"));
+        assert_eq!(cmd.opt_arg("password"), Some("secret"));
+        assert_eq!(
+            cmd.to_argv(),
+            [
+                "app",
+                "--host=example.com",
+                "--password=secret",
+                "--user=bob"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_confirm_options_in_parse_flow() {
+        let opt_cfgs = vec![
+            OptCfg::with(&[
+                store_key("deleteAll"),
+                names(&["delete-all"]),
+                confirm("Delete all?"),
+            ]),
+            OptCfg::with(&[names(&["force"]), confirm("Force?")]),
+            OptCfg::with(&[names(&["yes", "y"])]),
+        ];
+
+        let out = Output::default();
+        let mut prompt = TerminalPrompt::with_io(&b"y\n"[..], out.clone());
+        let mut cmd = Cmd::with_strings(["app", "--delete-all"].map(String::from));
+        assert_eq!(cmd.parse_with_prompt(&opt_cfgs, &mut prompt), Ok(()));
+        assert_eq!(out.text(), "Delete all? [y/N] ");
+
+        let out = Output::default();
+        let mut prompt = TerminalPrompt::with_io(&b"y\nn\n"[..], out.clone());
+        let mut cmd = Cmd::with_strings(["app", "--force", "--delete-all"].map(String::from));
+        assert_eq!(
+            cmd.parse_with_prompt(&opt_cfgs, &mut prompt),
+            Err(InvalidOption::OptionIsNotConfirmed {
+                store_key: "force".to_string(),
+                option: "force".to_string(),
+            })
+        );
+        assert_eq!(out.text(), "Delete all? [y/N] Force? [y/N] ");
+
+        let out = Output::default();
+        let mut prompt = TerminalPrompt::with_io(&b""[..], out.clone());
+        let mut cmd = Cmd::with_strings(["app", "--force", "-y"].map(String::from));
+        assert_eq!(cmd.parse_with_prompt(&opt_cfgs, &mut prompt), Ok(()));
+        assert_eq!(out.text(), "");
+
+        let mut cmd = Cmd::with_strings(["app", "--force"].map(String::from));
+        assert_eq!(
+            cmd.parse_with_prompt(&opt_cfgs, &mut prompt),
+            Err(InvalidOption::OptionIsNotConfirmed {
+                store_key: "force".to_string(),
+                option: "force".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+/// Quotes the specified string for POSIX shells like `sh` or `bash`, if
+/// needed.
+///
+/// A string which consists of only safe characters is returned as it is, and
+/// other strings are enclosed in single quotes.
+/// This function is used by `Cmd#to_command_line`.
+///
+/// ```
+/// use cliargs::quote;
+///
+/// assert_eq!(quote::sh("/tmp/a.txt"), "/tmp/a.txt");
+/// assert_eq!(quote::sh("it's $HOME"), r"'it'\''s $HOME'");
+/// ```
+pub fn sh(s: &str) -> String {
+    let is_safe = |ch: char| ch.is_ascii_alphanumeric() || "_@%+=:,./-".contains(ch);
+    if !s.is_empty() && s.chars().all(is_safe) {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+/// Quotes the specified string for PowerShell, if needed.
+///
+/// A string which consists of only safe characters is returned as it is, and
+/// other strings are enclosed in single quotes, in which single quotes,
+/// including typographic ones, are doubled.
+///
+/// ```
+/// use cliargs::quote;
+///
+/// assert_eq!(quote::powershell(r"C:\tmp\a.txt"), r"C:\tmp\a.txt");
+/// assert_eq!(quote::powershell("it's $HOME"), "'it''s $HOME'");
+/// ```
+pub fn powershell(s: &str) -> String {
+    let is_safe = |ch: char| ch.is_ascii_alphanumeric() || "_./:\\-".contains(ch);
+    if !s.is_empty() && s.chars().all(is_safe) {
+        return s.to_string();
+    }
+
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('\'');
+    for ch in s.chars() {
+        if matches!(ch, '\'' | '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}') {
+            quoted.push(ch);
+        }
+        quoted.push(ch);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Quotes the specified string for `cmd.exe`, if needed.
+///
+/// The string is quoted first so that it is split back as one argument by
+/// the Microsoft C runtime, and then the metacharacters of `cmd.exe`, which
+/// are `(`, `)`, `%`, `!`, `^`, `"`, `<`, `>`, `&`, and `|`, are escaped with
+/// `^`.
+///
+/// ```
+/// use cliargs::quote;
+///
+/// assert_eq!(quote::cmd(r"C:\tmp\a.txt"), r"C:\tmp\a.txt");
+/// assert_eq!(quote::cmd(r"a b\"), r#"^"a b\\^""#);
+/// assert_eq!(quote::cmd("a&b"), "a^&b");
+/// ```
+pub fn cmd(s: &str) -> String {
+    let needs_quotes = s.is_empty() || s.contains([' ', '\t', '\n', '\x0b', '"']);
+
+    let mut arg = String::with_capacity(s.len() + 2);
+    if needs_quotes {
+        arg.push('"');
+        let mut backslashes = 0;
+        for ch in s.chars() {
+            match ch {
+                '\\' => backslashes += 1,
+                '"' => {
+                    arg.push_str(&"\\".repeat(backslashes * 2 + 1));
+                    backslashes = 0;
+                }
+                _ => {
+                    arg.push_str(&"\\".repeat(backslashes));
+                    backslashes = 0;
+                }
+            }
+            if ch != '\\' {
+                arg.push(ch);
+            }
+        }
+        arg.push_str(&"\\".repeat(backslashes * 2));
+        arg.push('"');
+    } else {
+        arg.push_str(s);
+    }
+
+    let mut quoted = String::with_capacity(arg.len());
+    for ch in arg.chars() {
+        if "()%!^\"<>&|".contains(ch) {
+            quoted.push('^');
+        }
+        quoted.push(ch);
+    }
+    quoted
+}
+
+#[cfg(test)]
+mod tests_of_quote {
+    use super::*;
+
+    #[test]
+    fn should_quote_for_sh() {
+        assert_eq!(sh("abc"), "abc");
+        assert_eq!(sh("--foo=a,b"), "--foo=a,b");
+        assert_eq!(sh(""), "''");
+        assert_eq!(sh("a b"), "'a b'");
+        assert_eq!(sh("it's"), "'it'\\''s'");
+        assert_eq!(sh("$x"), "'$x'");
+    }
+
+    #[test]
+    fn should_quote_for_powershell() {
+        assert_eq!(powershell("abc"), "abc");
+        assert_eq!(powershell("C:\\a\\b.txt"), "C:\\a\\b.txt");
+        assert_eq!(powershell(""), "''");
+        assert_eq!(powershell("a b"), "'a b'");
+        assert_eq!(powershell("it's"), "'it''s'");
+        assert_eq!(powershell("it\u{2019}s"), "'it\u{2019}\u{2019}s'");
+        assert_eq!(powershell("$x"), "'$x'");
+        assert_eq!(powershell("@a"), "'@a'");
+        assert_eq!(powershell("a,b"), "'a,b'");
+    }
+
+    #[test]
+    fn should_quote_for_cmd() {
+        assert_eq!(cmd("abc"), "abc");
+        assert_eq!(cmd("C:\\a\\b.txt"), "C:\\a\\b.txt");
+        assert_eq!(cmd(""), "^\"^\"");
+        assert_eq!(cmd("a b"), "^\"a b^\"");
+        assert_eq!(cmd("a\"b"), "^\"a\\^\"b^\"");
+        assert_eq!(cmd("a\\\"b c"), "^\"a\\\\\\^\"b c^\"");
+        assert_eq!(cmd("a b\\"), "^\"a b\\\\^\"");
+        assert_eq!(cmd("a\\b c"), "^\"a\\b c^\"");
+        assert_eq!(cmd("100%"), "100^%");
+        assert_eq!(cmd("a|b>c"), "a^|b^>c");
+    }
+}
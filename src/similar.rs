@@ -0,0 +1,272 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::errors::InvalidOption;
+use crate::Cmd;
+use crate::OptCfg;
+use std::fmt;
+
+/// Returns the candidates similar to the specified name, in order of
+/// similarity.
+///
+/// A candidate is regarded as similar if its edit distance from the name is
+/// at most a third of the name length (and at least 1).
+pub(crate) fn similar_names<'b>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'b str>,
+) -> Vec<&'b str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    let mut found: Vec<(usize, &'b str)> = candidates
+        .into_iter()
+        .filter_map(|cand| {
+            let d = edit_distance(name, cand);
+            if d <= max_distance {
+                Some((d, cand))
+            } else {
+                None
+            }
+        })
+        .collect();
+    found.sort_by_key(|(d, _)| *d);
+    found.into_iter().map(|(_, cand)| cand).collect()
+}
+
+/// Returns the option configurations which have names similar to the
+/// specified name, with the most similar name of each, in order of
+/// similarity.
+///
+/// A name is regarded as similar if its edit distance from the specified name
+/// is at most a third of the length of the specified name, so a name shorter
+/// than three characters is matched only exactly.
+/// The hidden names of the configurations are not compared.
+pub(crate) fn similar_opts<'c>(name: &str, opt_cfgs: &'c [OptCfg]) -> Vec<(&'c OptCfg, &'c str)> {
+    let max_distance = name.chars().count() / 3;
+
+    let mut found: Vec<(usize, &'c OptCfg, &'c str)> = Vec::new();
+    for cfg in opt_cfgs.iter() {
+        let best = cfg
+            .names
+            .iter()
+            .filter(|n| !cfg.hidden_names.contains(n))
+            .map(|n| (edit_distance(name, n), n.as_str()))
+            .min_by_key(|(d, _)| *d);
+        if let Some((d, n)) = best {
+            if d <= max_distance {
+                found.push((d, cfg, n));
+            }
+        }
+    }
+    found.sort_by_key(|(d, _, _)| *d);
+    found.into_iter().map(|(_, cfg, n)| (cfg, n)).collect()
+}
+
+/// Returns the option name, like `--verbose`, which is suggested for the
+/// specified error if it is of an unconfigured option.
+pub(crate) fn suggest_opt(cmd: &Cmd, err: &InvalidOption, opt_cfgs: &[OptCfg]) -> Option<String> {
+    let InvalidOption::UnconfiguredOption { option } = err else {
+        return None;
+    };
+    let key = cmd.find_opt_fuzzy(option, opt_cfgs)?;
+    let (_, name) = similar_opts(option, opt_cfgs)
+        .into_iter()
+        .find(|(cfg, _)| store_key_of(cfg) == key)?;
+    let prefix = if name.chars().count() == 1 { "-" } else { "--" };
+    Some(format!("{}{}", prefix, name))
+}
+
+/// Returns the message of the specified error followed by the suggested
+/// option name if any.
+pub(crate) fn with_suggestion(err: &dyn fmt::Display, suggestion: Option<&str>) -> String {
+    match suggestion {
+        Some(name) => format!("{}, did you mean \"{}\"?", err, name),
+        None => err.to_string(),
+    }
+}
+
+fn store_key_of(cfg: &OptCfg) -> &str {
+    if cfg.store_key.is_empty() {
+        cfg.names.first().map(|s| s.as_str()).unwrap_or("")
+    } else {
+        &cfg.store_key
+    }
+}
+
+impl<'a> Cmd<'a> {
+    /// Finds the option which has the name most similar to the specified
+    /// name in the specified option configurations, and returns its store
+    /// key.
+    ///
+    /// The specified name can have leading hyphens, like `--verbos`.
+    /// An option is regarded as similar if the edit distance of the names is
+    /// at most a third of the length of the specified name, so a name shorter
+    /// than three characters is matched only exactly, and an exact name is
+    /// the most similar.
+    /// The suggestions in the error messages of `parse_with_or_exit` and
+    /// `App#run_or_exit` are made with this method, and this method is also
+    /// useful for interactive tools which resolve near-miss option names by
+    /// themselves.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::{names, store_key};
+    ///
+    /// let opt_cfgs = vec![
+    ///     OptCfg::with(&[store_key("verbose"), names(&["verbose", "v"])]),
+    ///     OptCfg::with(&[store_key("dryRun"), names(&["dry-run"])]),
+    /// ];
+    /// let mut cmd = Cmd::with_strings(["app".to_string()]);
+    /// cmd.parse_with(&opt_cfgs).unwrap();
+    ///
+    /// assert_eq!(cmd.find_opt_fuzzy("--verbos", &opt_cfgs), Some("verbose"));
+    /// assert_eq!(cmd.find_opt_fuzzy("dryrun", &opt_cfgs), Some("dryRun"));
+    /// assert_eq!(cmd.find_opt_fuzzy("-v", &opt_cfgs), Some("verbose"));
+    /// assert_eq!(cmd.find_opt_fuzzy("-x", &opt_cfgs), None);
+    /// assert_eq!(cmd.find_opt_fuzzy("quiet", &opt_cfgs), None);
+    /// ```
+    pub fn find_opt_fuzzy<'c>(&self, name: &str, opt_cfgs: &'c [OptCfg]) -> Option<&'c str> {
+        let name = name.trim_start_matches('-');
+        let (cfg, _) = similar_opts(name, opt_cfgs).into_iter().next()?;
+        Some(store_key_of(cfg))
+    }
+}
+
+/// Computes the Damerau-Levenshtein distance (optimal string alignment) of
+/// the two strings, so that a transposition of adjacent characters counts
+/// as one edit.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev2: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr: Vec<usize> = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                curr[j] = curr[j].min(prev2[j - 2] + 1);
+            }
+        }
+        prev2.clone_from(&prev);
+        prev.clone_from(&curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests_of_similar {
+    use super::*;
+
+    #[test]
+    fn should_compute_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("status", "status"), 0);
+        assert_eq!(edit_distance("stauts", "status"), 1);
+        assert_eq!(edit_distance("sync", "sink"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn should_find_similar_names() {
+        let cands = ["status", "stats", "stash", "sync"];
+        assert_eq!(similar_names("stauts", cands), vec!["status", "stats"]);
+        assert_eq!(similar_names("snyc", cands), vec!["sync"]);
+        assert_eq!(similar_names("commit", cands), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn should_find_similar_opts() {
+        use crate::OptCfgParam::{hidden_names, names};
+
+        let cfgs = vec![
+            OptCfg::with(&[names(&["verbose", "v"])]),
+            OptCfg::with(&[names(&["version", "verison"]), hidden_names(&["verison"])]),
+            OptCfg::with(&[names(&["quiet"])]),
+        ];
+        let found: Vec<&str> = similar_opts("verbos", &cfgs)
+            .iter()
+            .map(|(_, n)| *n)
+            .collect();
+        assert_eq!(found, ["verbose"]);
+        let found: Vec<&str> = similar_opts("versoin", &cfgs)
+            .iter()
+            .map(|(_, n)| *n)
+            .collect();
+        assert_eq!(found, ["version"]);
+        let found: Vec<&str> = similar_opts("v", &cfgs).iter().map(|(_, n)| *n).collect();
+        assert_eq!(found, ["v"]);
+        assert_eq!(similar_opts("x", &cfgs).len(), 0);
+        assert_eq!(similar_opts("vv", &cfgs).len(), 0);
+        assert_eq!(similar_opts("commit", &cfgs).len(), 0);
+    }
+
+    #[test]
+    fn should_find_opt_fuzzy() {
+        use crate::OptCfgParam::{names, store_key};
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[store_key("Verbose"), names(&["verbose", "v"])]),
+            OptCfg::with(&[names(&["verb"])]),
+        ];
+        let mut cmd = Cmd::with_strings(["app".to_string(), "--verb".to_string()]);
+        match cmd.parse_with(&opt_cfgs) {
+            Ok(()) => {}
+            Err(_) => panic!(),
+        }
+        assert_eq!(cmd.find_opt_fuzzy("--verbose", &opt_cfgs), Some("Verbose"));
+        assert_eq!(cmd.find_opt_fuzzy("-verb", &opt_cfgs), Some("verb"));
+        assert_eq!(cmd.find_opt_fuzzy("vebrose", &opt_cfgs), Some("Verbose"));
+        assert_eq!(cmd.find_opt_fuzzy("ver", &opt_cfgs), Some("verb"));
+        assert_eq!(cmd.find_opt_fuzzy("-v", &opt_cfgs), Some("Verbose"));
+        assert_eq!(cmd.find_opt_fuzzy("-x", &opt_cfgs), None);
+        assert_eq!(cmd.find_opt_fuzzy("quiet", &opt_cfgs), None);
+        assert_eq!(cmd.find_opt_fuzzy("verbose", &[]), None);
+    }
+
+    #[test]
+    fn should_suggest_opt_for_unconfigured_option() {
+        use crate::OptCfgParam::names;
+
+        let opt_cfgs = vec![
+            OptCfg::with(&[names(&["verbose", "v"])]),
+            OptCfg::with(&[names(&["quiet", "q"])]),
+        ];
+        let cmd = Cmd::with_strings(["app".to_string()]);
+
+        let err = InvalidOption::UnconfiguredOption {
+            option: "verbos".to_string(),
+        };
+        assert_eq!(
+            suggest_opt(&cmd, &err, &opt_cfgs),
+            Some("--verbose".to_string())
+        );
+        assert_eq!(
+            with_suggestion(&err, Some("--verbose")),
+            "The option is not specified in configurations (option: \"verbos\"), \
+             did you mean \"--verbose\"?"
+        );
+
+        let err = InvalidOption::UnconfiguredOption {
+            option: "x".to_string(),
+        };
+        assert_eq!(suggest_opt(&cmd, &err, &opt_cfgs), None);
+        assert_eq!(
+            with_suggestion(&err, None),
+            "The option is not specified in configurations (option: \"x\")"
+        );
+
+        let err = InvalidOption::OptionNeedsArg {
+            option: "verbos".to_string(),
+            store_key: "verbos".to_string(),
+        };
+        assert_eq!(suggest_opt(&cmd, &err, &opt_cfgs), None);
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use std::borrow::Borrow;
+use std::collections::{hash_map, HashMap};
+use std::hash::Hash;
+use std::mem;
+use std::ops::Index;
+use std::slice;
+
+/// The maximum number of entries stored in a vector and searched linearly.
+///
+/// Most commands have fewer options than this, and a linear search over a
+/// short vector is faster than hashing the keys.
+/// `benches/small_map.rs`, which runs with `cargo bench --features bench`,
+/// shows that lookups in a vector become as slow as in a `HashMap` at about
+/// 24 entries.
+const MAX_LINEAR_LEN: usize = 16;
+
+/// Is a map which stores entries in a vector while the number of them is
+/// small, and in a `HashMap` after it exceeds `MAX_LINEAR_LEN`.
+pub enum SmallMap<K, V> {
+    Linear(Vec<(K, V)>),
+    Hashed(HashMap<K, V>),
+}
+
+impl<K, V> Default for SmallMap<K, V> {
+    fn default() -> Self {
+        SmallMap::Linear(Vec::new())
+    }
+}
+
+impl<K: Eq + Hash, V> SmallMap<K, V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty map for the specified number of entries, of which the
+    /// storage is selected by the number.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        if capacity <= MAX_LINEAR_LEN {
+            SmallMap::Linear(Vec::with_capacity(capacity))
+        } else {
+            SmallMap::Hashed(HashMap::with_capacity(capacity))
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            SmallMap::Linear(vec) => vec.len(),
+            SmallMap::Hashed(map) => map.len(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self {
+            SmallMap::Linear(vec) => vec
+                .iter()
+                .find(|(k, _)| k.borrow() == key)
+                .map(|(k, v)| (k, v)),
+            SmallMap::Hashed(map) => map.get_key_value(key),
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    pub(crate) fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self {
+            SmallMap::Linear(vec) => vec
+                .iter_mut()
+                .find(|(k, _)| k.borrow() == key)
+                .map(|(_, v)| v),
+            SmallMap::Hashed(map) => map.get_mut(key),
+        }
+    }
+
+    pub(crate) fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.get_key_value(key).is_some()
+    }
+
+    /// Inserts the specified entry, and returns the previous value if the key
+    /// already exists.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.reserve_for(&key);
+        match self {
+            SmallMap::Linear(vec) => match vec.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, v)) => Some(mem::replace(v, value)),
+                None => {
+                    vec.push((key, value));
+                    None
+                }
+            },
+            SmallMap::Hashed(map) => map.insert(key, value),
+        }
+    }
+
+    /// Returns the mutable reference to the value of the specified key,
+    /// inserting the default value if the key does not exist.
+    pub fn get_or_insert_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        self.reserve_for(&key);
+        match self {
+            SmallMap::Linear(vec) => {
+                let i = match vec.iter().position(|(k, _)| *k == key) {
+                    Some(i) => i,
+                    None => {
+                        vec.push((key, V::default()));
+                        vec.len() - 1
+                    }
+                };
+                &mut vec[i].1
+            }
+            SmallMap::Hashed(map) => map.entry(key).or_default(),
+        }
+    }
+
+    /// Moves the entries into a `HashMap` if the vector is full and does not
+    /// contain the specified key.
+    fn reserve_for(&mut self, key: &K) {
+        if let SmallMap::Linear(vec) = self {
+            if vec.len() >= MAX_LINEAR_LEN && !vec.iter().any(|(k, _)| k == key) {
+                let map: HashMap<K, V> = mem::take(vec).into_iter().collect();
+                *self = SmallMap::Hashed(map);
+            }
+        }
+    }
+
+    pub(crate) fn retain(&mut self, mut f: impl FnMut(&K, &mut V) -> bool) {
+        match self {
+            SmallMap::Linear(vec) => vec.retain_mut(|(k, v)| f(k, v)),
+            SmallMap::Hashed(map) => map.retain(|k, v| f(k, v)),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, K, V> {
+        match self {
+            SmallMap::Linear(vec) => Iter::Linear(vec.iter()),
+            SmallMap::Hashed(map) => Iter::Hashed(map.iter()),
+        }
+    }
+
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+}
+
+impl<K, Q, V> Index<&Q> for SmallMap<K, V>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+/// Is an iterator over the entries of a `SmallMap`.
+pub(crate) enum Iter<'m, K, V> {
+    Linear(slice::Iter<'m, (K, V)>),
+    Hashed(hash_map::Iter<'m, K, V>),
+}
+
+impl<'m, K, V> Iterator for Iter<'m, K, V> {
+    type Item = (&'m K, &'m V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Iter::Linear(iter) => iter.next().map(|(k, v)| (k, v)),
+            Iter::Hashed(iter) => iter.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_of_small_map {
+    use super::*;
+
+    #[test]
+    fn should_select_storage_by_capacity() {
+        let map = SmallMap::<&str, i32>::with_capacity(MAX_LINEAR_LEN);
+        assert!(matches!(map, SmallMap::Linear(_)));
+
+        let map = SmallMap::<&str, i32>::with_capacity(MAX_LINEAR_LEN + 1);
+        assert!(matches!(map, SmallMap::Hashed(_)));
+    }
+
+    #[test]
+    fn should_insert_and_get_entries() {
+        let mut map = SmallMap::<&str, Vec<i32>>::new();
+        assert!(map.is_empty());
+
+        assert_eq!(map.insert("foo", vec![1]), None);
+        assert_eq!(map.insert("bar", vec![]), None);
+        assert_eq!(map.insert("foo", vec![2]), Some(vec![1]));
+        map.get_or_insert_default("baz").push(3);
+        map.get_or_insert_default("foo").push(4);
+        map.get_mut("bar").unwrap().push(5);
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get("foo"), Some(&vec![2, 4]));
+        assert_eq!(map["bar"], vec![5]);
+        assert_eq!(map.get_key_value("baz"), Some((&"baz", &vec![3])));
+        assert!(!map.contains_key("qux"));
+        assert_eq!(
+            map.keys().copied().collect::<Vec<&str>>(),
+            ["foo", "bar", "baz"]
+        );
+
+        map.retain(|k, _| *k != "foo");
+        assert_eq!(map.len(), 2);
+        assert!(!map.contains_key("foo"));
+    }
+
+    #[test]
+    fn should_move_entries_to_hash_map_when_exceeding_max_len() {
+        let keys: Vec<String> = (0..=MAX_LINEAR_LEN).map(|i| format!("k{i}")).collect();
+
+        let mut map = SmallMap::<&str, usize>::new();
+        for (i, key) in keys[..MAX_LINEAR_LEN].iter().enumerate() {
+            map.insert(key, i);
+        }
+        assert!(matches!(map, SmallMap::Linear(_)));
+
+        map.insert(&keys[0], 100);
+        assert!(matches!(map, SmallMap::Linear(_)));
+
+        *map.get_or_insert_default(&keys[MAX_LINEAR_LEN]) += 1;
+        assert!(matches!(map, SmallMap::Hashed(_)));
+
+        assert_eq!(map.len(), MAX_LINEAR_LEN + 1);
+        assert_eq!(map.get("k0"), Some(&100));
+        assert_eq!(map.get("k1"), Some(&1));
+        assert_eq!(map[keys[MAX_LINEAR_LEN].as_str()], 1);
+        assert_eq!(map.iter().count(), MAX_LINEAR_LEN + 1);
+    }
+}
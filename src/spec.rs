@@ -0,0 +1,658 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::errors::InvalidSpec;
+use crate::OptCfg;
+use std::fmt::Write;
+
+/// Holds a command description read from a machine-readable text.
+#[derive(Debug)]
+pub struct CmdSpec {
+    /// The command name.
+    pub name: String,
+
+    /// The option configurations of the command.
+    pub opt_cfgs: Vec<OptCfg>,
+}
+
+/// Returns a JSON text which describes the command with the specified name
+/// and option configurations.
+///
+/// The JSON text is an object which has the command name as `name` and an
+/// array of the option configurations as `options`.
+/// Each element of `options` has the fields of `OptCfg` except `validator`,
+/// in the same order as the fields are declared.
+/// Since the output is stable for the same input, it can be used for snapshot
+/// tests.
+///
+/// ```rust
+/// use cliargs::OptCfg;
+/// use cliargs::OptCfgParam::{names, has_arg, defaults};
+/// use cliargs::spec;
+///
+/// let opt_cfgs = vec![
+///     OptCfg::with(&[names(&["foo", "f"]), has_arg(true), defaults(&["1"])]),
+/// ];
+///
+/// assert_eq!(
+///     spec::to_json("app", &opt_cfgs),
+///     "{\"name\":\"app\",\"options\":[{\"store_key\":\"\",\"names\":[\"foo\",\"f\"],\
+///      \"has_arg\":true,\"is_array\":false,\"defaults\":[\"1\"],\"desc\":\"\",\
+///      \"arg_in_help\":\"\"}]}",
+/// );
+/// ```
+pub fn to_json(cmd_name: &str, opt_cfgs: &[OptCfg]) -> String {
+    let mut buf = String::new();
+
+    buf.push_str("{\"name\":");
+    write_json_str(&mut buf, cmd_name);
+    buf.push_str(",\"options\":[");
+
+    for (i, cfg) in opt_cfgs.iter().enumerate() {
+        if i > 0 {
+            buf.push(',');
+        }
+        buf.push_str("{\"store_key\":");
+        write_json_str(&mut buf, &cfg.store_key);
+        buf.push_str(",\"names\":");
+        write_json_str_array(&mut buf, &cfg.names);
+        let _ = write!(buf, ",\"has_arg\":{}", cfg.has_arg);
+        let _ = write!(buf, ",\"is_array\":{}", cfg.is_array);
+        buf.push_str(",\"defaults\":");
+        match &cfg.defaults {
+            Some(vec) => write_json_str_array(&mut buf, vec),
+            None => buf.push_str("null"),
+        }
+        buf.push_str(",\"desc\":");
+        write_json_str(&mut buf, &cfg.desc);
+        buf.push_str(",\"arg_in_help\":");
+        write_json_str(&mut buf, &cfg.arg_in_help);
+        buf.push('}');
+    }
+
+    buf.push_str("]}");
+    buf
+}
+
+/// Reads a JSON text which describes a command, and creates a `CmdSpec`
+/// instance.
+///
+/// The JSON text is of the same format as the output of `spec::to_json`.
+/// The fields which are missing in the text are set to the same values as
+/// `OptCfg::with` sets, and unknown fields are ignored.
+/// Since a function cannot be described in JSON, the `validator` field of
+/// each option configuration is set to the one which accepts any argument.
+///
+/// ```rust
+/// use cliargs::spec;
+///
+/// let spec = spec::from_json(r#"{
+///   "name": "app",
+///   "options": [
+///     { "names": ["foo", "f"], "has_arg": true, "defaults": ["1"] }
+///   ]
+/// }"#).unwrap();
+///
+/// assert_eq!(spec.name, "app");
+/// assert_eq!(spec.opt_cfgs[0].names, ["foo", "f"]);
+/// assert_eq!(spec.opt_cfgs[0].has_arg, true);
+/// assert_eq!(spec.opt_cfgs[0].defaults, Some(vec!["1".to_string()]));
+/// ```
+pub fn from_json(json: &str) -> Result<CmdSpec, InvalidSpec> {
+    let root = JsonReader::new(json).read_all()?;
+
+    let fields = match root {
+        JsonValue::Object(fields) => fields,
+        _ => return Err(type_err("", "an object")),
+    };
+
+    let mut name = String::new();
+    let mut opt_cfgs = Vec::new();
+
+    for (key, value) in fields {
+        match key.as_str() {
+            "name" => name = expect_str(value, "name")?,
+            "options" => match value {
+                JsonValue::Array(vec) => {
+                    for (i, elem) in vec.into_iter().enumerate() {
+                        opt_cfgs.push(read_opt_cfg(elem, &format!("options[{}]", i))?);
+                    }
+                }
+                _ => return Err(type_err("options", "an array")),
+            },
+            _ => {}
+        }
+    }
+
+    Ok(CmdSpec { name, opt_cfgs })
+}
+
+fn read_opt_cfg(value: JsonValue, path: &str) -> Result<OptCfg, InvalidSpec> {
+    let fields = match value {
+        JsonValue::Object(fields) => fields,
+        _ => return Err(type_err(path, "an object")),
+    };
+
+    let mut cfg = OptCfg::with(&[]);
+
+    for (key, value) in fields {
+        let field = format!("{}.{}", path, key);
+        match key.as_str() {
+            "store_key" => cfg.store_key = expect_str(value, &field)?,
+            "names" => cfg.names = expect_str_array(value, &field)?,
+            "has_arg" => cfg.has_arg = expect_bool(value, &field)?,
+            "is_array" => cfg.is_array = expect_bool(value, &field)?,
+            "defaults" => {
+                cfg.defaults = match value {
+                    JsonValue::Null => None,
+                    v => Some(expect_str_array(v, &field)?),
+                }
+            }
+            "desc" => cfg.desc = expect_str(value, &field)?,
+            "arg_in_help" => cfg.arg_in_help = expect_str(value, &field)?,
+            _ => {}
+        }
+    }
+
+    Ok(cfg)
+}
+
+fn type_err(field: &str, expected: &str) -> InvalidSpec {
+    InvalidSpec::FieldTypeIsInvalid {
+        field: field.to_string(),
+        expected: expected.to_string(),
+    }
+}
+
+fn expect_str(value: JsonValue, field: &str) -> Result<String, InvalidSpec> {
+    match value {
+        JsonValue::String(s) => Ok(s),
+        _ => Err(type_err(field, "a string")),
+    }
+}
+
+fn expect_bool(value: JsonValue, field: &str) -> Result<bool, InvalidSpec> {
+    match value {
+        JsonValue::Bool(b) => Ok(b),
+        _ => Err(type_err(field, "a boolean")),
+    }
+}
+
+fn expect_str_array(value: JsonValue, field: &str) -> Result<Vec<String>, InvalidSpec> {
+    match value {
+        JsonValue::Array(vec) => {
+            let mut strs = Vec::with_capacity(vec.len());
+            for elem in vec {
+                match elem {
+                    JsonValue::String(s) => strs.push(s),
+                    _ => return Err(type_err(field, "an array of strings")),
+                }
+            }
+            Ok(strs)
+        }
+        _ => Err(type_err(field, "an array of strings")),
+    }
+}
+
+enum JsonValue {
+    Null,
+    Bool(bool),
+    Number,
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+struct JsonReader<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonReader<'a> {
+    fn new(text: &'a str) -> Self {
+        JsonReader { text, pos: 0 }
+    }
+
+    fn read_all(mut self) -> Result<JsonValue, InvalidSpec> {
+        let value = self.read_value()?;
+        self.skip_ws();
+        if self.pos < self.text.len() {
+            return Err(self.err("unexpected trailing characters"));
+        }
+        Ok(value)
+    }
+
+    fn err(&self, details: &str) -> InvalidSpec {
+        InvalidSpec::JsonIsMalformed {
+            position: self.pos,
+            details: details.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(ch) = self.peek() {
+            if ch == ' ' || ch == '\t' || ch == '\n' || ch == '\r' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn consume(&mut self, s: &str) -> bool {
+        if self.text[self.pos..].starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn read_value(&mut self) -> Result<JsonValue, InvalidSpec> {
+        self.skip_ws();
+        match self.peek() {
+            Some('{') => self.read_object(),
+            Some('[') => self.read_array(),
+            Some('"') => Ok(JsonValue::String(self.read_string()?)),
+            Some('-') | Some('0'..='9') => self.read_number(),
+            Some(_) => {
+                if self.consume("null") {
+                    Ok(JsonValue::Null)
+                } else if self.consume("true") {
+                    Ok(JsonValue::Bool(true))
+                } else if self.consume("false") {
+                    Ok(JsonValue::Bool(false))
+                } else {
+                    Err(self.err("unexpected character"))
+                }
+            }
+            None => Err(self.err("unexpected end of text")),
+        }
+    }
+
+    fn read_object(&mut self) -> Result<JsonValue, InvalidSpec> {
+        self.pos += 1;
+        let mut fields = Vec::new();
+
+        self.skip_ws();
+        if self.consume("}") {
+            return Ok(JsonValue::Object(fields));
+        }
+
+        loop {
+            self.skip_ws();
+            if self.peek() != Some('"') {
+                return Err(self.err("expected a string as an object key"));
+            }
+            let key = self.read_string()?;
+
+            self.skip_ws();
+            if !self.consume(":") {
+                return Err(self.err("expected ':'"));
+            }
+
+            let value = self.read_value()?;
+            fields.push((key, value));
+
+            self.skip_ws();
+            if self.consume(",") {
+                continue;
+            }
+            if self.consume("}") {
+                return Ok(JsonValue::Object(fields));
+            }
+            return Err(self.err("expected ',' or '}'"));
+        }
+    }
+
+    fn read_array(&mut self) -> Result<JsonValue, InvalidSpec> {
+        self.pos += 1;
+        let mut elems = Vec::new();
+
+        self.skip_ws();
+        if self.consume("]") {
+            return Ok(JsonValue::Array(elems));
+        }
+
+        loop {
+            elems.push(self.read_value()?);
+
+            self.skip_ws();
+            if self.consume(",") {
+                continue;
+            }
+            if self.consume("]") {
+                return Ok(JsonValue::Array(elems));
+            }
+            return Err(self.err("expected ',' or ']'"));
+        }
+    }
+
+    fn read_number(&mut self) -> Result<JsonValue, InvalidSpec> {
+        let start = self.pos;
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.' | 'e' | 'E') {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        match self.text[start..self.pos].parse::<f64>() {
+            Ok(_) => Ok(JsonValue::Number),
+            Err(_) => {
+                self.pos = start;
+                Err(self.err("invalid number"))
+            }
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, InvalidSpec> {
+        self.pos += 1;
+        let mut s = String::new();
+
+        loop {
+            let ch = match self.peek() {
+                Some(ch) => ch,
+                None => return Err(self.err("unterminated string")),
+            };
+            match ch {
+                '"' => {
+                    self.pos += 1;
+                    return Ok(s);
+                }
+                '\\' => {
+                    self.pos += 1;
+                    let esc = match self.peek() {
+                        Some(esc) => esc,
+                        None => return Err(self.err("unterminated string")),
+                    };
+                    self.pos += 1;
+                    match esc {
+                        '"' => s.push('"'),
+                        '\\' => s.push('\\'),
+                        '/' => s.push('/'),
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'n' => s.push('\n'),
+                        'r' => s.push('\r'),
+                        't' => s.push('\t'),
+                        'u' => {
+                            let hi = self.read_hex4()?;
+                            let code = if (0xD800..0xDC00).contains(&hi) && self.consume("\\u") {
+                                let lo = self.read_hex4()?;
+                                0x10000 + ((hi - 0xD800) << 10) + (lo.wrapping_sub(0xDC00) & 0x3FF)
+                            } else {
+                                hi
+                            };
+                            match char::from_u32(code) {
+                                Some(c) => s.push(c),
+                                None => return Err(self.err("invalid unicode escape")),
+                            }
+                        }
+                        _ => {
+                            self.pos -= 1;
+                            return Err(self.err("invalid escape"));
+                        }
+                    }
+                }
+                c if (c as u32) < 0x20 => return Err(self.err("control character in string")),
+                c => {
+                    self.pos += c.len_utf8();
+                    s.push(c);
+                }
+            }
+        }
+    }
+
+    fn read_hex4(&mut self) -> Result<u32, InvalidSpec> {
+        let end = self.pos + 4;
+        match self
+            .text
+            .get(self.pos..end)
+            .map(|h| u32::from_str_radix(h, 16))
+        {
+            Some(Ok(n)) => {
+                self.pos = end;
+                Ok(n)
+            }
+            _ => Err(self.err("invalid unicode escape")),
+        }
+    }
+}
+
+fn write_json_str_array(buf: &mut String, vec: &[String]) {
+    buf.push('[');
+    for (i, s) in vec.iter().enumerate() {
+        if i > 0 {
+            buf.push(',');
+        }
+        write_json_str(buf, s);
+    }
+    buf.push(']');
+}
+
+fn write_json_str(buf: &mut String, s: &str) {
+    buf.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+#[cfg(test)]
+mod tests_of_spec {
+    use super::*;
+    use crate::OptCfgParam::*;
+
+    mod tests_of_to_json {
+        use super::*;
+
+        #[test]
+        fn should_output_command_without_options() {
+            assert_eq!(to_json("app", &[]), "{\"name\":\"app\",\"options\":[]}");
+        }
+
+        #[test]
+        fn should_output_command_with_options() {
+            let opt_cfgs = vec![
+                OptCfg::with(&[
+                    store_key("fooBar"),
+                    names(&["foo-bar", "f"]),
+                    desc("This is foo-bar."),
+                ]),
+                OptCfg::with(&[
+                    names(&["baz"]),
+                    has_arg(true),
+                    is_array(true),
+                    defaults(&["1", "2"]),
+                    arg_in_help("<num>"),
+                ]),
+            ];
+
+            assert_eq!(
+                to_json("app", &opt_cfgs),
+                "{\"name\":\"app\",\"options\":[\
+                 {\"store_key\":\"fooBar\",\"names\":[\"foo-bar\",\"f\"],\"has_arg\":false,\
+                 \"is_array\":false,\"defaults\":null,\"desc\":\"This is foo-bar.\",\
+                 \"arg_in_help\":\"\"},\
+                 {\"store_key\":\"\",\"names\":[\"baz\"],\"has_arg\":true,\"is_array\":true,\
+                 \"defaults\":[\"1\",\"2\"],\"desc\":\"\",\"arg_in_help\":\"<num>\"}]}"
+            );
+        }
+
+        #[test]
+        fn should_escape_strings() {
+            let opt_cfgs = vec![OptCfg::with(&[
+                names(&["foo"]),
+                desc("a \"quoted\" \\ text\n\tend\u{1}"),
+            ])];
+
+            assert_eq!(
+                to_json("my\"app", &opt_cfgs),
+                "{\"name\":\"my\\\"app\",\"options\":[\
+                 {\"store_key\":\"\",\"names\":[\"foo\"],\"has_arg\":false,\
+                 \"is_array\":false,\"defaults\":null,\
+                 \"desc\":\"a \\\"quoted\\\" \\\\ text\\n\\tend\\u0001\",\
+                 \"arg_in_help\":\"\"}]}"
+            );
+        }
+    }
+
+    mod tests_of_from_json {
+        use super::*;
+        use crate::errors::InvalidSpec;
+
+        #[test]
+        fn should_read_command_without_options() {
+            let spec = from_json("{\"name\":\"app\",\"options\":[]}").unwrap();
+            assert_eq!(spec.name, "app");
+            assert_eq!(spec.opt_cfgs.len(), 0);
+
+            let spec = from_json(" { } ").unwrap();
+            assert_eq!(spec.name, "");
+            assert_eq!(spec.opt_cfgs.len(), 0);
+        }
+
+        #[test]
+        fn should_read_command_with_options() {
+            let spec = from_json(
+                r#"{
+                  "name": "app",
+                  "version": 1.5e0,
+                  "options": [
+                    {
+                      "store_key": "fooBar",
+                      "names": ["foo-bar", "f"],
+                      "has_arg": false,
+                      "is_array": false,
+                      "defaults": null,
+                      "desc": "This is \"foo-bar\".\n\u00e9\ud83d\ude00",
+                      "arg_in_help": ""
+                    },
+                    {
+                      "names": ["baz"],
+                      "has_arg": true,
+                      "is_array": true,
+                      "defaults": ["1", "2"],
+                      "arg_in_help": "<num>",
+                      "unknown": [true, {"x": null}]
+                    }
+                  ]
+                }"#,
+            )
+            .unwrap();
+
+            assert_eq!(spec.name, "app");
+            assert_eq!(spec.opt_cfgs.len(), 2);
+
+            let cfg = &spec.opt_cfgs[0];
+            assert_eq!(cfg.store_key, "fooBar");
+            assert_eq!(cfg.names, vec!["foo-bar", "f"]);
+            assert!(!cfg.has_arg);
+            assert!(!cfg.is_array);
+            assert_eq!(cfg.defaults, None);
+            assert_eq!(cfg.desc, "This is \"foo-bar\".\n\u{e9}\u{1f600}");
+            assert_eq!(cfg.arg_in_help, "");
+            assert_eq!((cfg.validator)("a", "b", "c"), Ok(()));
+
+            let cfg = &spec.opt_cfgs[1];
+            assert_eq!(cfg.store_key, "");
+            assert_eq!(cfg.names, vec!["baz"]);
+            assert!(cfg.has_arg);
+            assert!(cfg.is_array);
+            assert_eq!(cfg.defaults, Some(vec!["1".to_string(), "2".to_string()]));
+            assert_eq!(cfg.desc, "");
+            assert_eq!(cfg.arg_in_help, "<num>");
+        }
+
+        #[test]
+        fn should_round_trip_with_to_json() {
+            let opt_cfgs = vec![
+                OptCfg::with(&[store_key("fooBar"), names(&["foo-bar", "f"]), desc("a\tb")]),
+                OptCfg::with(&[names(&["baz"]), has_arg(true), defaults(&["\"x\""])]),
+            ];
+            let json = to_json("app", &opt_cfgs);
+            let spec = from_json(&json).unwrap();
+            assert_eq!(to_json(&spec.name, &spec.opt_cfgs), json);
+        }
+
+        #[test]
+        fn should_fail_if_json_is_malformed() {
+            match from_json("{\"name\": \"app\",}") {
+                Err(InvalidSpec::JsonIsMalformed { position, details }) => {
+                    assert_eq!(position, 15);
+                    assert_eq!(details, "expected a string as an object key");
+                }
+                _ => panic!(),
+            }
+            match from_json("{\"name\": \"app\"} x") {
+                Err(InvalidSpec::JsonIsMalformed { position, details }) => {
+                    assert_eq!(position, 16);
+                    assert_eq!(details, "unexpected trailing characters");
+                }
+                _ => panic!(),
+            }
+            match from_json("[\"abc") {
+                Err(InvalidSpec::JsonIsMalformed { position, details }) => {
+                    assert_eq!(position, 5);
+                    assert_eq!(details, "unterminated string");
+                }
+                _ => panic!(),
+            }
+            match from_json("") {
+                Err(InvalidSpec::JsonIsMalformed { position, details }) => {
+                    assert_eq!(position, 0);
+                    assert_eq!(details, "unexpected end of text");
+                }
+                _ => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_fail_if_field_type_is_invalid() {
+            match from_json("[]") {
+                Err(InvalidSpec::FieldTypeIsInvalid { field, expected }) => {
+                    assert_eq!(field, "");
+                    assert_eq!(expected, "an object");
+                }
+                _ => panic!(),
+            }
+            match from_json("{\"name\": 1}") {
+                Err(InvalidSpec::FieldTypeIsInvalid { field, expected }) => {
+                    assert_eq!(field, "name");
+                    assert_eq!(expected, "a string");
+                }
+                _ => panic!(),
+            }
+            match from_json("{\"options\": [{\"names\": [\"a\", 1]}]}") {
+                Err(InvalidSpec::FieldTypeIsInvalid { field, expected }) => {
+                    assert_eq!(field, "options[0].names");
+                    assert_eq!(expected, "an array of strings");
+                }
+                _ => panic!(),
+            }
+            match from_json("{\"options\": [{}, {\"has_arg\": \"true\"}]}") {
+                Err(InvalidSpec::FieldTypeIsInvalid { field, expected }) => {
+                    assert_eq!(field, "options[1].has_arg");
+                    assert_eq!(expected, "a boolean");
+                }
+                _ => panic!(),
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::errors::InvalidOption;
+use crate::Cmd;
+use crate::OptCfg;
+
+/// Creates a `Cmd` instance with the specified arguments, which are preceded
+/// by the command name `"app"`.
+pub fn cmd_of<'a>(args: &[&str]) -> Cmd<'a> {
+    let mut strings = Vec::with_capacity(args.len() + 1);
+    strings.push("app".to_string());
+    strings.extend(args.iter().map(|s| s.to_string()));
+    Cmd::with_strings(strings)
+}
+
+/// Parses the specified arguments with the specified option configurations,
+/// and returns the parsed `Cmd` instance.
+///
+/// If `opt_cfgs` is `None`, the arguments are parsed with `Cmd#parse`.
+///
+/// # Panics
+///
+/// Panics if the parsing fails.
+pub fn parse_ok<'a>(args: &[&str], opt_cfgs: Option<&[OptCfg]>) -> Cmd<'a> {
+    let mut cmd = cmd_of(args);
+    let result = match opt_cfgs {
+        Some(cfgs) => cmd.parse_with(cfgs),
+        None => cmd.parse(),
+    };
+    if let Err(err) = result {
+        panic!("failed to parse {:?}: {:?}", args, err);
+    }
+    cmd
+}
+
+/// Parses the specified arguments with the specified option configurations,
+/// and returns the error of the parsing.
+///
+/// If `opt_cfgs` is `None`, the arguments are parsed with `Cmd#parse`.
+///
+/// # Panics
+///
+/// Panics if the parsing succeeds.
+pub fn parse_err(args: &[&str], opt_cfgs: Option<&[OptCfg]>) -> InvalidOption {
+    let mut cmd = cmd_of(args);
+    let result = match opt_cfgs {
+        Some(cfgs) => cmd.parse_with(cfgs),
+        None => cmd.parse(),
+    };
+    match result {
+        Ok(_) => panic!("succeeded to parse {:?}: {:?}", args, cmd),
+        Err(err) => err,
+    }
+}
+
+/// Parses the specified arguments, optionally with option configurations,
+/// and returns the parsed `Cmd` instance, or panics if the parsing fails.
+///
+/// ```
+/// use cliargs::{parse_ok, OptCfg};
+/// use cliargs::OptCfgParam::{names, has_arg};
+///
+/// let cfgs = vec![OptCfg::with(&[names(&["foo"]), has_arg(true)])];
+/// let cmd = parse_ok!(["--foo", "1", "bar"], cfgs);
+/// assert_eq!(cmd.opt_arg("foo"), Some("1"));
+/// assert_eq!(cmd.args(), &["bar"]);
+///
+/// let cmd = parse_ok!(["-a", "b"]);
+/// assert_eq!(cmd.has_opt("a"), true);
+/// ```
+#[macro_export]
+macro_rules! parse_ok {
+    ([$($arg:expr),* $(,)?]) => {
+        $crate::testing::parse_ok(&[$($arg),*], None)
+    };
+    ([$($arg:expr),* $(,)?], $cfgs:expr) => {
+        $crate::testing::parse_ok(&[$($arg),*], Some(&$cfgs))
+    };
+}
+
+/// Parses the specified arguments, optionally with option configurations,
+/// and returns the error of the parsing, or panics if the parsing succeeds.
+///
+/// ```
+/// use cliargs::{parse_err, OptCfg};
+/// use cliargs::OptCfgParam::names;
+/// use cliargs::errors::InvalidOption;
+///
+/// let cfgs = vec![OptCfg::with(&[names(&["foo"])])];
+/// let err = parse_err!(["--bar"], cfgs);
+/// assert_eq!(err, InvalidOption::UnconfiguredOption { option: "bar".to_string() });
+/// ```
+#[macro_export]
+macro_rules! parse_err {
+    ([$($arg:expr),* $(,)?]) => {
+        $crate::testing::parse_err(&[$($arg),*], None)
+    };
+    ([$($arg:expr),* $(,)?], $cfgs:expr) => {
+        $crate::testing::parse_err(&[$($arg),*], Some(&$cfgs))
+    };
+}
+
+/// Asserts that the option arguments of the specified store key in a `Cmd`
+/// instance are equal to the expected values.
+///
+/// An empty array means that the option is specified without any argument.
+///
+/// ```
+/// use cliargs::{assert_opt, parse_ok};
+///
+/// let cmd = parse_ok!(["--foo=1", "--foo=2", "-b"]);
+/// assert_opt!(cmd, "foo", ["1", "2"]);
+/// assert_opt!(cmd, "b", []);
+/// ```
+#[macro_export]
+macro_rules! assert_opt {
+    ($cmd:expr, $key:expr, [$($arg:expr),* $(,)?]) => {
+        let expected: &[&str] = &[$($arg),*];
+        assert!($cmd.has_opt($key), "option {:?} is not found in {:?}", $key, $cmd);
+        assert_eq!(
+            $cmd.opt_args($key).unwrap_or(&[]),
+            expected,
+            "option arguments of {:?} are different",
+            $key
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests_of_testing {
+    use super::*;
+    use crate::OptCfgParam::{defaults, has_arg, is_array, names};
+
+    #[test]
+    fn should_parse_ok_without_cfgs() {
+        let cmd = parse_ok!(["--foo", "-b=2", "baz"]);
+        assert_eq!(cmd.name(), "app");
+        assert_opt!(cmd, "foo", []);
+        assert_opt!(cmd, "b", ["2"]);
+        assert_eq!(cmd.args(), &["baz"]);
+    }
+
+    #[test]
+    fn should_parse_ok_with_cfgs() {
+        let cfgs = vec![
+            OptCfg::with(&[names(&["foo", "f"]), has_arg(true), is_array(true)]),
+            OptCfg::with(&[names(&["bar"]), has_arg(true), defaults(&["9"])]),
+        ];
+        let cmd = parse_ok!(["-f", "1", "--foo=2", "qux"], cfgs);
+        assert_opt!(cmd, "foo", ["1", "2"]);
+        assert_opt!(cmd, "bar", ["9"]);
+        assert_eq!(cmd.args(), &["qux"]);
+    }
+
+    #[test]
+    fn should_return_error_with_parse_err() {
+        let cfgs = vec![OptCfg::with(&[names(&["foo"]), has_arg(true)])];
+        match parse_err!(["--foo"], cfgs) {
+            InvalidOption::OptionNeedsArg { option, store_key } => {
+                assert_eq!(option, "foo");
+                assert_eq!(store_key, "foo");
+            }
+            _ => panic!(),
+        }
+
+        match parse_err!(["-@"]) {
+            InvalidOption::OptionContainsInvalidChar { option } => assert_eq!(option, "@"),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_if_parse_ok_fails() {
+        let cfgs = vec![OptCfg::with(&[names(&["foo"])])];
+        parse_ok!(["--bar"], cfgs);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_if_parse_err_succeeds() {
+        parse_err!(["--bar"]);
+    }
+}
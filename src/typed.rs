@@ -0,0 +1,342 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::errors::{InvalidOptArgs, InvalidOption};
+use crate::values::parse_number;
+use crate::Cmd;
+use std::fmt;
+use std::str;
+
+impl<'a> Cmd<'a> {
+    /// Converts the first option argument of the specified store key to the
+    /// type `T`, and stores the converted value for retrieving with `get`.
+    ///
+    /// This method should be called after parsing.
+    /// An integer option argument can also be written with a radix prefix,
+    /// like `0xFF`, `0o755`, or `0b1010`.
+    /// If the option is not specified and has no default value, this method
+    /// stores nothing and `get` returns `None`.
+    /// If the conversion fails, this method returns
+    /// `InvalidOption::OptionArgIsInvalid`.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::{names, has_arg};
+    ///
+    /// let mut cmd = Cmd::with_strings(["app".to_string(), "--port=8080".to_string()]);
+    /// cmd.parse_with(&[OptCfg::with(&[names(&["port"]), has_arg(true)])]).unwrap();
+    ///
+    /// cmd.register_type::<u16>("port").unwrap();
+    /// assert_eq!(cmd.get::<u16>("port"), Some(&8080));
+    /// ```
+    pub fn register_type<T>(&mut self, store_key: &str) -> Result<(), InvalidOption>
+    where
+        T: str::FromStr + Send + Sync + 'static,
+        <T as str::FromStr>::Err: fmt::Display,
+    {
+        let Some(opt_arg) = self.opts.get(store_key).and_then(|v| v.first()) else {
+            self.typed_values.remove(store_key);
+            return Ok(());
+        };
+        match parse_number::<T>(opt_arg) {
+            Ok(value) => {
+                self.typed_values
+                    .insert(store_key.to_string(), Box::new(value));
+                Ok(())
+            }
+            Err(err) => Err(InvalidOption::OptionArgIsInvalid {
+                store_key: store_key.to_string(),
+                option: store_key.to_string(),
+                opt_arg: opt_arg.to_string(),
+                details: format!("{}", err),
+            }),
+        }
+    }
+
+    /// Converts all option arguments of the specified store key to the type
+    /// `T`.
+    ///
+    /// Like `register_type`, integer option arguments can be written with
+    /// radix prefixes.
+    /// If some option arguments cannot be converted, this method returns
+    /// `errors::InvalidOptArgs` which has all the failures with the indices of
+    /// the invalid arguments, instead of stopping at the first one.
+    /// If the option is not specified, this method returns an empty vector.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::{names, has_arg, is_array};
+    ///
+    /// let mut cmd = Cmd::with_strings(
+    ///     ["app", "--port=80", "--port=x", "--port=443", "--port=-1"].map(String::from));
+    /// cmd.parse_with(&[OptCfg::with(&[names(&["port"]), has_arg(true), is_array(true)])])
+    ///     .unwrap();
+    ///
+    /// let err = cmd.opt_args_as::<u16>("port").unwrap_err();
+    /// assert_eq!(err.errors.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [1, 3]);
+    /// ```
+    pub fn opt_args_as<T>(&self, store_key: &str) -> Result<Vec<T>, InvalidOptArgs>
+    where
+        T: str::FromStr,
+        <T as str::FromStr>::Err: fmt::Display,
+    {
+        let Some(opt_args) = self.opts.get(store_key) else {
+            return Ok(Vec::new());
+        };
+
+        let mut values = Vec::with_capacity(opt_args.len());
+        let mut errors = Vec::new();
+        for (i, opt_arg) in opt_args.iter().enumerate() {
+            match parse_number::<T>(opt_arg) {
+                Ok(value) => values.push(value),
+                Err(err) => errors.push((
+                    i,
+                    InvalidOption::OptionArgIsInvalid {
+                        store_key: store_key.to_string(),
+                        option: store_key.to_string(),
+                        opt_arg: opt_arg.to_string(),
+                        details: format!("{}", err),
+                    },
+                )),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(InvalidOptArgs { errors })
+        }
+    }
+
+    /// Converts the option arguments of the specified store key to an array
+    /// of the type `T` of which the length is fixed to `N`, like a pair of
+    /// `--size 640 --size 480`.
+    ///
+    /// If the option is not specified, this method returns `None`.
+    /// If the number of the option arguments is not `N`, this method returns
+    /// `errors::InvalidOptArgs` which has an error at the index of the first
+    /// missing or extra argument.
+    /// If some option arguments cannot be converted, this method returns
+    /// `errors::InvalidOptArgs` like `opt_args_as`, of which indices are the
+    /// positions of the invalid elements.
+    ///
+    /// ```
+    /// use cliargs::{Cmd, OptCfg};
+    /// use cliargs::OptCfgParam::{names, has_arg, is_array};
+    ///
+    /// let mut cmd = Cmd::with_strings(["app", "--size=640", "--size=480"].map(String::from));
+    /// cmd.parse_with(&[OptCfg::with(&[names(&["size"]), has_arg(true), is_array(true)])])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(cmd.opt_args_as_array::<u32, 2>("size").unwrap(), Some([640, 480]));
+    /// assert_eq!(cmd.opt_args_as_array::<u32, 3>("size").unwrap_err().errors[0].0, 2);
+    /// ```
+    pub fn opt_args_as_array<T, const N: usize>(
+        &self,
+        store_key: &str,
+    ) -> Result<Option<[T; N]>, InvalidOptArgs>
+    where
+        T: str::FromStr,
+        <T as str::FromStr>::Err: fmt::Display,
+    {
+        let Some(opt_args) = self.opts.get(store_key) else {
+            return Ok(None);
+        };
+
+        if opt_args.len() != N {
+            let index = opt_args.len().min(N);
+            return Err(InvalidOptArgs {
+                errors: vec![(
+                    index,
+                    InvalidOption::OptionArgIsInvalid {
+                        store_key: store_key.to_string(),
+                        option: store_key.to_string(),
+                        opt_arg: opt_args.get(index).unwrap_or(&"").to_string(),
+                        details: format!(
+                            "{} arguments are needed but {} are given",
+                            N,
+                            opt_args.len()
+                        ),
+                    },
+                )],
+            });
+        }
+
+        let values = self.opt_args_as::<T>(store_key)?;
+        Ok(values.try_into().ok())
+    }
+
+    /// Returns the value of the specified store key which is converted by
+    /// `register_type`.
+    ///
+    /// If the value is not registered or is registered as another type, this
+    /// method returns `None`.
+    pub fn get<T: 'static>(&self, store_key: &str) -> Option<&T> {
+        self.typed_values
+            .get(store_key)
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+}
+
+#[cfg(test)]
+mod tests_of_typed {
+    use crate::errors::InvalidOption;
+    use crate::OptCfg;
+    use crate::OptCfgParam::{defaults, has_arg, is_array, names};
+
+    fn cfgs() -> Vec<OptCfg> {
+        vec![
+            OptCfg::with(&[names(&["port"]), has_arg(true)]),
+            OptCfg::with(&[names(&["host"]), has_arg(true), defaults(&["localhost"])]),
+            OptCfg::with(&[names(&["retries"]), has_arg(true)]),
+        ]
+    }
+
+    #[test]
+    fn should_convert_and_get_values() {
+        let cfgs = cfgs();
+        let mut cmd = crate::testing::parse_ok(&["--port", "8080"], Some(&cfgs));
+
+        cmd.register_type::<u16>("port").unwrap();
+        cmd.register_type::<String>("host").unwrap();
+        cmd.register_type::<u8>("retries").unwrap();
+
+        assert_eq!(cmd.get::<u16>("port"), Some(&8080));
+        assert_eq!(cmd.get::<String>("host"), Some(&"localhost".to_string()));
+        assert_eq!(cmd.get::<u8>("retries"), None);
+    }
+
+    #[test]
+    fn should_return_none_if_type_is_different() {
+        let cfgs = cfgs();
+        let mut cmd = crate::testing::parse_ok(&["--port", "8080"], Some(&cfgs));
+
+        assert_eq!(cmd.get::<u16>("port"), None);
+        cmd.register_type::<u16>("port").unwrap();
+        assert_eq!(cmd.get::<u32>("port"), None);
+    }
+
+    #[test]
+    fn should_fail_if_conversion_fails() {
+        let cfgs = cfgs();
+        let mut cmd = crate::testing::parse_ok(&["--port", "99999"], Some(&cfgs));
+
+        match cmd.register_type::<u16>("port") {
+            Err(InvalidOption::OptionArgIsInvalid {
+                store_key,
+                option,
+                opt_arg,
+                details,
+            }) => {
+                assert_eq!(store_key, "port");
+                assert_eq!(option, "port");
+                assert_eq!(opt_arg, "99999");
+                assert_eq!(details, "number too large to fit in target type");
+            }
+            _ => panic!(),
+        }
+        assert_eq!(cmd.get::<u16>("port"), None);
+    }
+
+    #[test]
+    fn should_convert_all_opt_args() {
+        let cfgs = vec![OptCfg::with(&[
+            names(&["n"]),
+            has_arg(true),
+            is_array(true),
+        ])];
+
+        let cmd = crate::testing::parse_ok(&["-n", "1", "-n=2", "-n", "3"], Some(&cfgs));
+        assert_eq!(cmd.opt_args_as::<i32>("n"), Ok(vec![1, 2, 3]));
+        assert_eq!(cmd.opt_args_as::<i32>("m"), Ok(vec![]));
+
+        let cmd = crate::testing::parse_ok(&["-n", "1", "-n=x", "-n", "3", "-n=-"], Some(&cfgs));
+        match cmd.opt_args_as::<i32>("n") {
+            Ok(_) => panic!(),
+            Err(err) => {
+                assert_eq!(err.errors.len(), 2);
+                assert_eq!(err.errors[0].0, 1);
+                assert_eq!(err.errors[0].1.option(), "n");
+                assert_eq!(err.errors[1].0, 3);
+                match &err.errors[1].1 {
+                    InvalidOption::OptionArgIsInvalid {
+                        opt_arg, details, ..
+                    } => {
+                        assert_eq!(opt_arg, "-");
+                        assert_eq!(details, "invalid digit found in string");
+                    }
+                    _ => panic!(),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_convert_integers_with_radix_prefixes() {
+        let cfgs = cfgs();
+        let mut cmd =
+            crate::testing::parse_ok(&["--port", "0x1F90", "--retries=0b11"], Some(&cfgs));
+
+        cmd.register_type::<u16>("port").unwrap();
+        assert_eq!(cmd.get::<u16>("port"), Some(&8080));
+        assert_eq!(cmd.opt_args_as::<u8>("retries"), Ok(vec![3]));
+        assert_eq!(
+            cmd.opt_args_as::<String>("retries"),
+            Ok(vec!["0b11".to_string()])
+        );
+    }
+
+    #[test]
+    fn should_convert_opt_args_to_fixed_length_array() {
+        let cfgs = vec![OptCfg::with(&[
+            names(&["n"]),
+            has_arg(true),
+            is_array(true),
+        ])];
+
+        let cmd = crate::testing::parse_ok(&["-n", "1", "-n=2"], Some(&cfgs));
+        assert_eq!(cmd.opt_args_as_array::<u8, 2>("n"), Ok(Some([1, 2])));
+        assert_eq!(cmd.opt_args_as_array::<u8, 2>("m"), Ok(None));
+
+        match cmd.opt_args_as_array::<u8, 3>("n") {
+            Err(err) => {
+                assert_eq!(err.errors.len(), 1);
+                assert_eq!(err.errors[0].0, 2);
+                match &err.errors[0].1 {
+                    InvalidOption::OptionArgIsInvalid {
+                        opt_arg, details, ..
+                    } => {
+                        assert_eq!(opt_arg, "");
+                        assert_eq!(details, "3 arguments are needed but 2 are given");
+                    }
+                    _ => panic!(),
+                }
+            }
+            _ => panic!(),
+        }
+
+        match cmd.opt_args_as_array::<u8, 1>("n") {
+            Err(err) => {
+                assert_eq!(err.errors[0].0, 1);
+                match &err.errors[0].1 {
+                    InvalidOption::OptionArgIsInvalid { opt_arg, .. } => {
+                        assert_eq!(opt_arg, "2");
+                    }
+                    _ => panic!(),
+                }
+            }
+            _ => panic!(),
+        }
+
+        let cmd = crate::testing::parse_ok(&["-n", "x", "-n=2", "-n=300"], Some(&cfgs));
+        match cmd.opt_args_as_array::<u8, 3>("n") {
+            Err(err) => {
+                assert_eq!(err.errors.len(), 2);
+                assert_eq!(err.errors[0].0, 0);
+                assert_eq!(err.errors[1].0, 2);
+            }
+            _ => panic!(),
+        }
+    }
+}
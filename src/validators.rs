@@ -3,6 +3,9 @@
 // See the file LICENSE in this distribution for more details.
 
 use crate::errors::InvalidOption;
+use crate::values::{
+    parse_number, ByteSize, HexColor, HumanDuration, LenientNumber, OptValue, Ratio, Timestamp,
+};
 use std::fmt;
 use std::ops;
 use std::str;
@@ -10,6 +13,8 @@ use std::str;
 /// Validates an option argument string whether it is valid as a number value of the specified
 /// type.
 ///
+/// An integer can also be written with a radix prefix, like `0xFF`, `0o755`, or `0b1010`.
+///
 /// If the option argument is invalid, this funciton returns a `InvalidOption::OptionArgIsInvalid`
 /// instance.
 pub fn validate_number<T>(store_key: &str, option: &str, opt_arg: &str) -> Result<(), InvalidOption>
@@ -24,6 +29,170 @@ where
         + PartialEq
         + PartialOrd,
     <T as str::FromStr>::Err: fmt::Display,
+{
+    match parse_number::<T>(opt_arg) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(InvalidOption::OptionArgIsInvalid {
+            store_key: store_key.to_string(),
+            option: option.to_string(),
+            opt_arg: opt_arg.to_string(),
+            details: format!("{}", err),
+        }),
+    }
+}
+
+/// Validates an option argument string whether it is valid as a human-readable
+/// duration like `250ms`, `1.5h`, or `2d`.
+///
+/// The option argument can be converted to `values::HumanDuration`.
+/// If the option argument is invalid, this function returns a
+/// `InvalidOption::OptionArgIsInvalid` instance of which the details show the
+/// accepted units.
+pub fn validate_duration(
+    store_key: &str,
+    option: &str,
+    opt_arg: &str,
+) -> Result<(), InvalidOption> {
+    validate_value::<HumanDuration>(store_key, option, opt_arg)
+}
+
+/// Validates an option argument string whether it is valid as a byte size
+/// with an optional suffix like `10K` or `2MiB`.
+///
+/// The option argument can be converted to `values::ByteSize`.
+/// If the option argument is invalid, this function returns a
+/// `InvalidOption::OptionArgIsInvalid` instance.
+pub fn validate_byte_size(
+    store_key: &str,
+    option: &str,
+    opt_arg: &str,
+) -> Result<(), InvalidOption> {
+    validate_value::<ByteSize>(store_key, option, opt_arg)
+}
+
+/// Validates an option argument string whether it is valid as a ratio in the
+/// range [0, 1], written as a percentage like `75%` or a fraction like `0.75`.
+///
+/// The option argument can be converted to `values::Ratio`.
+/// If the option argument is invalid or out of the range, this function
+/// returns a `InvalidOption::OptionArgIsInvalid` instance.
+pub fn validate_ratio(store_key: &str, option: &str, opt_arg: &str) -> Result<(), InvalidOption> {
+    validate_value::<Ratio>(store_key, option, opt_arg)
+}
+
+/// Validates an option argument string whether it is valid as a hex color
+/// like `#RRGGBB` or `#RGB`.
+///
+/// The option argument can be converted to `values::HexColor`.
+/// If the option argument is invalid, this function returns a
+/// `InvalidOption::OptionArgIsInvalid` instance.
+pub fn validate_hex_color(
+    store_key: &str,
+    option: &str,
+    opt_arg: &str,
+) -> Result<(), InvalidOption> {
+    validate_value::<HexColor>(store_key, option, opt_arg)
+}
+
+/// Validates an option argument string whether it is valid as an absolute URL
+/// which has a host, like `https://example.com/api`.
+///
+/// If the option argument is invalid, this function returns a
+/// `InvalidOption::OptionArgIsInvalid` instance of which the details is the
+/// message of the underlying parse error of the [url](https://docs.rs/url)
+/// crate.
+///
+/// This function is available when the `url` feature is enabled.
+#[cfg(feature = "url")]
+pub fn validate_url(store_key: &str, option: &str, opt_arg: &str) -> Result<(), InvalidOption> {
+    let details = match url::Url::parse(opt_arg) {
+        Ok(u) if u.has_host() => return Ok(()),
+        Ok(_) => url::ParseError::EmptyHost.to_string(),
+        Err(err) => err.to_string(),
+    };
+    Err(InvalidOption::OptionArgIsInvalid {
+        store_key: store_key.to_string(),
+        option: option.to_string(),
+        opt_arg: opt_arg.to_string(),
+        details,
+    })
+}
+
+/// Validates an option argument string whether it is valid as a semantic
+/// version like `1.2.3`.
+///
+/// Since `semver::Version` implements `FromStr`, the option argument can be
+/// converted with `Cmd#register_type::<semver::Version>` or
+/// `Cmd#opt_args_as::<semver::Version>` and compared in the application.
+/// If the option argument is invalid, this function returns a
+/// `InvalidOption::OptionArgIsInvalid` instance of which the details is the
+/// message of the underlying error of the [semver](https://docs.rs/semver)
+/// crate.
+///
+/// This function is available when the `semver` feature is enabled.
+#[cfg(feature = "semver")]
+pub fn validate_semver(store_key: &str, option: &str, opt_arg: &str) -> Result<(), InvalidOption> {
+    validate_value::<semver::Version>(store_key, option, opt_arg)
+}
+
+/// Validates an option argument string whether it is valid as a timestamp
+/// in RFC 3339 format like `2024-05-01T12:34:56Z`.
+///
+/// The option argument can be converted to `values::Timestamp`, and relative
+/// forms like `yesterday` or `-2h` are also accepted when the
+/// `relative-time` feature is enabled.
+/// If the option argument is invalid, this function returns a
+/// `InvalidOption::OptionArgIsInvalid` instance.
+pub fn validate_timestamp(
+    store_key: &str,
+    option: &str,
+    opt_arg: &str,
+) -> Result<(), InvalidOption> {
+    validate_value::<Timestamp>(store_key, option, opt_arg)
+}
+
+/// Validates an option argument string whether it is valid as a number value
+/// of the specified type after normalizing digit separators and decimal
+/// marks, like `1_000_000`, `1,000,000`, or `0,75`.
+///
+/// The option argument can be converted to `values::LenientNumber<T>`.
+/// See `values::LenientNumber` about how the option argument is normalized.
+/// If the option argument is invalid, this function returns a
+/// `InvalidOption::OptionArgIsInvalid` instance.
+pub fn validate_lenient_number<T>(
+    store_key: &str,
+    option: &str,
+    opt_arg: &str,
+) -> Result<(), InvalidOption>
+where
+    T: str::FromStr,
+    <T as str::FromStr>::Err: fmt::Display,
+{
+    validate_value::<LenientNumber<T>>(store_key, option, opt_arg)
+}
+
+/// Validates an option argument string whether it is one of the names of
+/// the variants of the specified enum, which implements `values::OptValue`.
+///
+/// If the option argument is invalid, this function returns a
+/// `InvalidOption::OptionArgIsInvalid` instance of which the details show the
+/// possible names.
+pub fn validate_opt_value<T>(
+    store_key: &str,
+    option: &str,
+    opt_arg: &str,
+) -> Result<(), InvalidOption>
+where
+    T: OptValue + str::FromStr,
+    <T as str::FromStr>::Err: fmt::Display,
+{
+    validate_value::<T>(store_key, option, opt_arg)
+}
+
+fn validate_value<T>(store_key: &str, option: &str, opt_arg: &str) -> Result<(), InvalidOption>
+where
+    T: str::FromStr,
+    <T as str::FromStr>::Err: fmt::Display,
 {
     match opt_arg.parse::<T>() {
         Ok(_) => Ok(()),
@@ -50,7 +219,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<i8>("FooBar", "foo-bar", "-123"), Ok(()));
 
             match validate_number::<i8>("FooBar", "foo-bar", "128") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -62,10 +231,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "128");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i8>("FooBar", "foo-bar", "-129") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -77,10 +246,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-129");
                     assert_eq!(details, "number too small to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i8>("FooBar", "foo-bar", "1e1") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -92,25 +261,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "1e1");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<i8>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<i8>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<i8>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -122,7 +277,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -133,7 +288,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<i16>("FooBar", "foo-bar", "-123"), Ok(()));
 
             match validate_number::<i16>("FooBar", "foo-bar", "32768") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -145,10 +300,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "32768");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i16>("FooBar", "foo-bar", "-32769") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -160,10 +315,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-32769");
                     assert_eq!(details, "number too small to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i16>("FooBar", "foo-bar", "1e3") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -175,25 +330,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "1e3");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<i16>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<i16>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<i16>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -205,7 +346,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -216,7 +357,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<i32>("FooBar", "foo-bar", "-123"), Ok(()));
 
             match validate_number::<i32>("FooBar", "foo-bar", "2147483648") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -228,10 +369,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "2147483648");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i32>("FooBar", "foo-bar", "-2147483649") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -243,10 +384,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-2147483649");
                     assert_eq!(details, "number too small to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i32>("FooBar", "foo-bar", "1e+3") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -258,25 +399,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "1e+3");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<i32>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<i32>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<i32>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -288,7 +415,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -299,7 +426,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<i64>("FooBar", "foo-bar", "-123"), Ok(()));
 
             match validate_number::<i64>("FooBar", "foo-bar", "9223372036854775808") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -311,10 +438,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "9223372036854775808");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i64>("FooBar", "foo-bar", "-9223372036854775809") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -326,10 +453,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-9223372036854775809");
                     assert_eq!(details, "number too small to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i64>("FooBar", "foo-bar", "100e-1") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -341,25 +468,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "100e-1");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<i64>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<i64>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<i64>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -371,7 +484,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -386,7 +499,7 @@ mod tests_of_validators {
                 "foo-bar",
                 "170141183460469231731687303715884105728",
             ) {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -398,14 +511,14 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "170141183460469231731687303715884105728");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i128>(
                 "FooBar",
                 "foo-bar",
                 "-170141183460469231731687303715884105729",
             ) {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -417,10 +530,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-170141183460469231731687303715884105729");
                     assert_eq!(details, "number too small to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<i128>("FooBar", "foo-bar", "1E+3") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -432,25 +545,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "1E+3");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<i128>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<i128>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<i128>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -462,7 +561,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -476,7 +575,7 @@ mod tests_of_validators {
             );
 
             match validate_number::<i64>("FooBar", "foo-bar", "9223372036854775808") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -488,10 +587,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "9223372036854775808");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<isize>("FooBar", "foo-bar", "-9223372036854775809") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -503,25 +602,14 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-9223372036854775809");
                     assert_eq!(details, "number too small to fit in target type");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<isize>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(
+                validate_number::<isize>("FooBar", "foo-bar", "0x0a"),
+                Ok(())
+            );
             match validate_number::<isize>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -533,7 +621,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -543,7 +631,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<u8>("FooBar", "foo-bar", "123"), Ok(()));
 
             match validate_number::<u8>("FooBar", "foo-bar", "256") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -555,10 +643,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "256");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<u8>("FooBar", "foo-bar", "-1") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -570,25 +658,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-1");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<u8>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<u8>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<u8>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -600,7 +674,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -610,7 +684,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<u16>("FooBar", "foo-bar", "123"), Ok(()));
 
             match validate_number::<u16>("FooBar", "foo-bar", "65536") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -622,10 +696,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "65536");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<u16>("FooBar", "foo-bar", "-1") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -637,25 +711,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-1");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<u16>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<u16>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<u16>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -667,7 +727,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -677,7 +737,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<u32>("FooBar", "foo-bar", "123"), Ok(()));
 
             match validate_number::<u32>("FooBar", "foo-bar", "4294967296") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -689,10 +749,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "4294967296");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<u32>("FooBar", "foo-bar", "-1") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -704,25 +764,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-1");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<u32>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<u32>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<u32>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -734,7 +780,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -744,7 +790,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<u64>("FooBar", "foo-bar", "123"), Ok(()));
 
             match validate_number::<u64>("FooBar", "foo-bar", "18446744073709551616") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -756,10 +802,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "18446744073709551616");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<u64>("FooBar", "foo-bar", "-1") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -771,25 +817,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-1");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<u64>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<u64>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<u64>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -801,7 +833,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -815,7 +847,7 @@ mod tests_of_validators {
                 "foo-bar",
                 "340282366920938463463374607431768211456",
             ) {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -827,10 +859,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "340282366920938463463374607431768211456");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<u128>("FooBar", "foo-bar", "-1") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -842,25 +874,11 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-1");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
-            }
-            match validate_number::<u128>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
-                Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
-                }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
-                    assert_eq!(details, "invalid digit found in string");
-                }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
+            assert_eq!(validate_number::<u128>("FooBar", "foo-bar", "0x0a"), Ok(()));
             match validate_number::<u128>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -872,7 +890,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -882,7 +900,7 @@ mod tests_of_validators {
             assert_eq!(validate_number::<usize>("FooBar", "foo-bar", "123"), Ok(()));
 
             match validate_number::<usize>("FooBar", "foo-bar", "18446744073709551616") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -894,10 +912,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "18446744073709551616");
                     assert_eq!(details, "number too large to fit in target type");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<usize>("FooBar", "foo-bar", "-1") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -909,10 +927,14 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "-1");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
-            match validate_number::<usize>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
+            assert_eq!(
+                validate_number::<usize>("FooBar", "foo-bar", "0x0a"),
+                Ok(())
+            );
+            match validate_number::<usize>("FooBar", "foo-bar", "abc") {
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -921,25 +943,28 @@ mod tests_of_validators {
                 }) => {
                     assert_eq!(store_key, "FooBar");
                     assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "0x0a");
+                    assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
-            match validate_number::<usize>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+        }
+
+        #[test]
+        fn should_validate_integers_with_radix_prefixes() {
+            assert_eq!(validate_number::<u16>("Mode", "mode", "0o755"), Ok(()));
+            assert_eq!(validate_number::<u8>("Mask", "mask", "0xFF"), Ok(()));
+            assert_eq!(validate_number::<i32>("Bits", "bits", "-0b1010"), Ok(()));
+
+            match validate_number::<u8>("Mask", "mask", "0x1FF") {
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
-                    store_key,
-                    option,
-                    opt_arg,
-                    details,
+                    opt_arg, details, ..
                 }) => {
-                    assert_eq!(store_key, "FooBar");
-                    assert_eq!(option, "foo-bar");
-                    assert_eq!(opt_arg, "abc");
+                    assert_eq!(opt_arg, "0x1FF");
                     assert_eq!(details, "invalid digit found in string");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -951,7 +976,7 @@ mod tests_of_validators {
             // floating point number literal exceeding f32::MAX is valid as f32::INFINITY.
 
             match validate_number::<f32>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -963,10 +988,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "0x0a");
                     assert_eq!(details, "invalid float literal");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<f32>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -978,7 +1003,7 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid float literal");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
         }
 
@@ -990,7 +1015,7 @@ mod tests_of_validators {
             // floating point number literal exceeding f64::MAX is valid as f64::INFINITY.
 
             match validate_number::<f64>("FooBar", "foo-bar", "0x0a") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -1002,10 +1027,10 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "0x0a");
                     assert_eq!(details, "invalid float literal");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
             }
             match validate_number::<f64>("FooBar", "foo-bar", "abc") {
-                Ok(_) => assert!(false),
+                Ok(_) => panic!(),
                 Err(InvalidOption::OptionArgIsInvalid {
                     store_key,
                     option,
@@ -1017,7 +1042,335 @@ mod tests_of_validators {
                     assert_eq!(opt_arg, "abc");
                     assert_eq!(details, "invalid float literal");
                 }
-                Err(_) => assert!(false),
+                Err(_) => panic!(),
+            }
+        }
+    }
+
+    mod test_of_validate_duration {
+        use super::*;
+
+        #[test]
+        fn should_validate_durations() {
+            assert_eq!(validate_duration("timeout", "timeout", "250ms"), Ok(()));
+            assert_eq!(validate_duration("timeout", "timeout", "1.5h"), Ok(()));
+
+            match validate_duration("timeout", "t", "5 min") {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsInvalid {
+                    store_key,
+                    option,
+                    opt_arg,
+                    details,
+                }) => {
+                    assert_eq!(store_key, "timeout");
+                    assert_eq!(option, "t");
+                    assert_eq!(opt_arg, "5 min");
+                    assert_eq!(
+                        details,
+                        "invalid duration: \"5 min\" (accepted units: ns, us, ms, s, m, h, d)"
+                    );
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_convert_with_typed_accessors() {
+            let mut cmd = crate::Cmd::with_strings(["app", "--timeout=2d"].map(String::from));
+            cmd.parse().unwrap();
+            cmd.register_type::<HumanDuration>("timeout").unwrap();
+            assert_eq!(
+                cmd.get::<HumanDuration>("timeout").map(|d| d.0.as_secs()),
+                Some(172800)
+            );
+        }
+    }
+
+    mod test_of_validate_byte_size {
+        use super::*;
+
+        #[test]
+        fn should_validate_byte_sizes() {
+            assert_eq!(validate_byte_size("bufSize", "buf-size", "64Ki"), Ok(()));
+            assert_eq!(validate_byte_size("bufSize", "buf-size", "1024"), Ok(()));
+
+            match validate_byte_size("bufSize", "buf-size", "1Q") {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsInvalid {
+                    store_key,
+                    option,
+                    opt_arg,
+                    ..
+                }) => {
+                    assert_eq!(store_key, "bufSize");
+                    assert_eq!(option, "buf-size");
+                    assert_eq!(opt_arg, "1Q");
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_convert_with_typed_accessors() {
+            let mut cmd =
+                crate::Cmd::with_strings(["app", "--limit=1K", "--limit=2Mi"].map(String::from));
+            cmd.parse().unwrap();
+            assert_eq!(
+                cmd.opt_args_as::<ByteSize>("limit"),
+                Ok(vec![ByteSize(1000), ByteSize(2 << 20)])
+            );
+        }
+    }
+
+    mod test_of_validate_ratio {
+        use super::*;
+
+        #[test]
+        fn should_validate_ratios() {
+            assert_eq!(validate_ratio("sampleRate", "sample-rate", "5%"), Ok(()));
+            assert_eq!(validate_ratio("sampleRate", "sample-rate", "0.05"), Ok(()));
+
+            match validate_ratio("sampleRate", "sample-rate", "150%") {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsInvalid {
+                    store_key,
+                    option,
+                    opt_arg,
+                    details,
+                }) => {
+                    assert_eq!(store_key, "sampleRate");
+                    assert_eq!(option, "sample-rate");
+                    assert_eq!(opt_arg, "150%");
+                    assert_eq!(
+                        details,
+                        "invalid ratio: \"150%\" (accepted: 0% to 100%, or 0 to 1)"
+                    );
+                }
+                Err(_) => panic!(),
+            }
+        }
+    }
+
+    mod test_of_validate_hex_color {
+        use super::*;
+
+        #[test]
+        fn should_validate_hex_colors() {
+            assert_eq!(validate_hex_color("fg", "fg", "#abcdef"), Ok(()));
+            assert_eq!(validate_hex_color("fg", "fg", "#abc"), Ok(()));
+
+            match validate_hex_color("fg", "fg", "red") {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsInvalid {
+                    store_key,
+                    option,
+                    opt_arg,
+                    details,
+                }) => {
+                    assert_eq!(store_key, "fg");
+                    assert_eq!(option, "fg");
+                    assert_eq!(opt_arg, "red");
+                    assert_eq!(
+                        details,
+                        "invalid hex color: \"red\" (accepted: #RRGGBB or #RGB)"
+                    );
+                }
+                Err(_) => panic!(),
+            }
+        }
+    }
+
+    #[cfg(feature = "url")]
+    mod test_of_validate_url {
+        use super::*;
+
+        #[test]
+        fn should_validate_urls() {
+            assert_eq!(
+                validate_url("endpoint", "endpoint", "https://example.com/api"),
+                Ok(())
+            );
+            assert_eq!(
+                validate_url("endpoint", "endpoint", "http://127.0.0.1:8080"),
+                Ok(())
+            );
+
+            for (arg, cause) in [
+                ("example.com", "relative URL without a base"),
+                ("https://", "empty host"),
+                ("mailto:someone@example.com", "empty host"),
+                ("http://exa mple.com", "invalid international domain name"),
+            ] {
+                match validate_url("endpoint", "e", arg) {
+                    Ok(_) => panic!(),
+                    Err(InvalidOption::OptionArgIsInvalid {
+                        store_key,
+                        option,
+                        opt_arg,
+                        details,
+                    }) => {
+                        assert_eq!(store_key, "endpoint");
+                        assert_eq!(option, "e");
+                        assert_eq!(opt_arg, arg);
+                        assert_eq!(details, cause);
+                    }
+                    Err(_) => panic!(),
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "semver")]
+    mod test_of_validate_semver {
+        use super::*;
+        use crate::Cmd;
+        use crate::OptCfg;
+        use crate::OptCfgParam::{has_arg, names, validator};
+
+        #[test]
+        fn should_validate_semantic_versions() {
+            assert_eq!(
+                validate_semver("minVersion", "min-version", "1.2.3"),
+                Ok(())
+            );
+            assert_eq!(
+                validate_semver("minVersion", "min-version", "1.0.0-rc.1+build.5"),
+                Ok(())
+            );
+
+            match validate_semver("minVersion", "min-version", "1.2") {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsInvalid {
+                    store_key,
+                    option,
+                    opt_arg,
+                    details,
+                }) => {
+                    assert_eq!(store_key, "minVersion");
+                    assert_eq!(option, "min-version");
+                    assert_eq!(opt_arg, "1.2");
+                    assert_eq!(
+                        details,
+                        "unexpected end of input while parsing minor version number"
+                    );
+                }
+                Err(_) => panic!(),
+            }
+        }
+
+        #[test]
+        fn should_convert_and_compare_versions() {
+            let mut cmd = Cmd::with_strings(["app", "--min-version", "1.10.0"].map(String::from));
+            cmd.parse_with(&[OptCfg::with(&[
+                names(&["min-version"]),
+                has_arg(true),
+                validator(validate_semver),
+            ])])
+            .unwrap();
+            cmd.register_type::<semver::Version>("min-version").unwrap();
+            let min = cmd.get::<semver::Version>("min-version").unwrap();
+            assert!(*min > semver::Version::new(1, 9, 0));
+        }
+    }
+
+    mod test_of_validate_timestamp {
+        use super::*;
+
+        #[test]
+        fn should_validate_timestamps() {
+            assert_eq!(
+                validate_timestamp("since", "since", "2024-05-01T12:34:56Z"),
+                Ok(())
+            );
+
+            match validate_timestamp("since", "since", "2024-05-01") {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsInvalid {
+                    store_key,
+                    option,
+                    opt_arg,
+                    details,
+                }) => {
+                    assert_eq!(store_key, "since");
+                    assert_eq!(option, "since");
+                    assert_eq!(opt_arg, "2024-05-01");
+                    assert!(details.starts_with("invalid timestamp: \"2024-05-01\""));
+                }
+                Err(_) => panic!(),
+            }
+        }
+    }
+
+    mod test_of_validate_lenient_number {
+        use super::*;
+
+        #[test]
+        fn should_validate_lenient_numbers() {
+            assert_eq!(
+                validate_lenient_number::<u32>("count", "count", "1,000,000"),
+                Ok(())
+            );
+            assert_eq!(
+                validate_lenient_number::<f64>("ratio", "ratio", "0,5"),
+                Ok(())
+            );
+
+            match validate_lenient_number::<u8>("count", "c", "1_000") {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsInvalid {
+                    store_key,
+                    option,
+                    opt_arg,
+                    details,
+                }) => {
+                    assert_eq!(store_key, "count");
+                    assert_eq!(option, "c");
+                    assert_eq!(opt_arg, "1_000");
+                    assert_eq!(
+                        details,
+                        "number too large to fit in target type (normalized: \"1000\")"
+                    );
+                }
+                Err(_) => panic!(),
+            }
+        }
+    }
+
+    mod test_of_validate_opt_value {
+        use super::*;
+
+        crate::opt_value! {
+            enum Color {
+                Auto = "auto",
+                Never = "never",
+            }
+        }
+
+        #[test]
+        fn should_validate_names_of_variants() {
+            assert_eq!(
+                validate_opt_value::<Color>("color", "color", "auto"),
+                Ok(())
+            );
+
+            match validate_opt_value::<Color>("color", "c", "always") {
+                Ok(_) => panic!(),
+                Err(InvalidOption::OptionArgIsInvalid {
+                    store_key,
+                    option,
+                    opt_arg,
+                    details,
+                }) => {
+                    assert_eq!(store_key, "color");
+                    assert_eq!(option, "c");
+                    assert_eq!(opt_arg, "always");
+                    assert_eq!(
+                        details,
+                        "invalid value: \"always\" (possible values: auto, never)"
+                    );
+                }
+                Err(_) => panic!(),
             }
         }
     }
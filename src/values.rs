@@ -0,0 +1,1092 @@
+// Copyright (C) 2024 Takayuki Sato. All Rights Reserved.
+// This program is free software under MIT License.
+// See the file LICENSE in this distribution for more details.
+
+use crate::completion::ValueHint;
+use std::fmt;
+use std::str;
+use std::time;
+
+/// A duration value converted from a human-readable string like `250ms`,
+/// `1.5h`, or `1h30m`.
+///
+/// A string is a sequence of pairs of a non-negative number and a unit.
+/// The accepted units are `ns`, `us`, `ms`, `s`, `m`, `h`, and `d`.
+///
+/// ```
+/// use cliargs::values::HumanDuration;
+/// use std::time::Duration;
+///
+/// let d: HumanDuration = "1m30s".parse().unwrap();
+/// assert_eq!(d.0, Duration::from_secs(90));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HumanDuration(pub time::Duration);
+
+const DURATION_UNITS: [(&str, f64); 7] = [
+    ("ns", 1e-9),
+    ("us", 1e-6),
+    ("ms", 1e-3),
+    ("s", 1.0),
+    ("m", 60.0),
+    ("h", 3600.0),
+    ("d", 86400.0),
+];
+
+impl str::FromStr for HumanDuration {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || {
+            format!(
+                "invalid duration: {:?} (accepted units: ns, us, ms, s, m, h, d)",
+                s
+            )
+        };
+
+        if s.is_empty() {
+            return Err(err());
+        }
+
+        let mut secs = 0.0_f64;
+        let mut rest = s;
+        while !rest.is_empty() {
+            let num_len = rest
+                .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+                .ok_or_else(err)?;
+            let num: f64 = rest[..num_len].parse().map_err(|_| err())?;
+            rest = &rest[num_len..];
+
+            let unit_len = rest
+                .find(|ch: char| ch.is_ascii_digit() || ch == '.')
+                .unwrap_or(rest.len());
+            let unit = &rest[..unit_len];
+            rest = &rest[unit_len..];
+
+            let (_, scale) = DURATION_UNITS
+                .iter()
+                .find(|(u, _)| *u == unit)
+                .ok_or_else(err)?;
+            secs += num * scale;
+        }
+
+        time::Duration::try_from_secs_f64(secs)
+            .map(HumanDuration)
+            .map_err(|_| err())
+    }
+}
+
+/// A number of bytes converted from a string with a size suffix like `10K`,
+/// `2MiB`, or `512`.
+///
+/// The accepted suffixes are `K`, `M`, `G`, `T` (powers of 1000) and `Ki`,
+/// `Mi`, `Gi`, `Ti` (powers of 1024), optionally followed by `B`, and are
+/// case-insensitive.
+/// A number without a suffix, or with only `B`, is a number of bytes.
+/// The number can have a fractional part if the result is a whole number of
+/// bytes, like `1.5K`.
+///
+/// ```
+/// use cliargs::values::ByteSize;
+///
+/// let size: ByteSize = "2MiB".parse().unwrap();
+/// assert_eq!(size.0, 2 * 1024 * 1024);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+impl str::FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || {
+            format!(
+                "invalid byte size: {:?} (accepted suffixes: K, M, G, T, Ki, Mi, Gi, Ti, optionally followed by B)",
+                s
+            )
+        };
+
+        let num_len = s
+            .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+            .unwrap_or(s.len());
+        let (num, suffix) = s.split_at(num_len);
+        if num.is_empty() {
+            return Err(err());
+        }
+
+        let lower = suffix.to_ascii_lowercase();
+        let unit = lower.strip_suffix('b').unwrap_or(&lower);
+        let scale: u64 = match unit {
+            "" => 1,
+            "k" => 1_000,
+            "m" => 1_000_000,
+            "g" => 1_000_000_000,
+            "t" => 1_000_000_000_000,
+            "ki" => 1 << 10,
+            "mi" => 1 << 20,
+            "gi" => 1 << 30,
+            "ti" => 1 << 40,
+            _ => return Err(err()),
+        };
+
+        // The decimal point is shifted with integer math, like
+        // `expand_si_suffix`, so that exact inputs like `4.1M` have no
+        // rounding error.
+        let (int_part, frac_part) = num.split_once('.').unwrap_or((num, ""));
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(err());
+        }
+        let frac_part = frac_part.trim_end_matches('0');
+        let digits = format!("{}{}", int_part, frac_part);
+        let digits: u128 = if digits.is_empty() {
+            0
+        } else {
+            digits.parse().map_err(|_| err())?
+        };
+        let divisor = 10u128.checked_pow(frac_part.len() as u32).ok_or_else(err)?;
+        let bytes = digits.checked_mul(scale as u128).ok_or_else(err)?;
+        if bytes % divisor != 0 {
+            return Err(err());
+        }
+        u64::try_from(bytes / divisor)
+            .map(ByteSize)
+            .map_err(|_| err())
+    }
+}
+
+/// A ratio in the range [0, 1] converted from a percentage like `75%` or a
+/// fraction like `0.75`.
+///
+/// ```
+/// use cliargs::values::Ratio;
+///
+/// let r: Ratio = "75%".parse().unwrap();
+/// assert_eq!(r.0, 0.75);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Ratio(pub f64);
+
+impl str::FromStr for Ratio {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("invalid ratio: {:?} (accepted: 0% to 100%, or 0 to 1)", s);
+
+        let value = match s.strip_suffix('%') {
+            Some(pct) => pct.parse::<f64>().map_err(|_| err())? / 100.0,
+            None => s.parse::<f64>().map_err(|_| err())?,
+        };
+        if !(0.0..=1.0).contains(&value) {
+            return Err(err());
+        }
+        Ok(Ratio(value))
+    }
+}
+
+/// An RGB color converted from a hex color string like `#RRGGBB` or `#RGB`.
+///
+/// This type is also an example of a custom value type: implementing
+/// `FromStr` makes a type usable with `Cmd#register_type` and
+/// `Cmd#opt_args_as`, and a validator for it can be written in the same way
+/// as `validators::validate_hex_color`.
+///
+/// ```
+/// use cliargs::{Cmd, OptCfg};
+/// use cliargs::OptCfgParam::{names, has_arg, validator};
+/// use cliargs::validators::validate_hex_color;
+/// use cliargs::values::HexColor;
+///
+/// let mut cmd = Cmd::with_strings(["app", "--fg=#f80"].map(String::from));
+/// cmd.parse_with(&[
+///     OptCfg::with(&[names(&["fg"]), has_arg(true), validator(validate_hex_color)]),
+/// ]).unwrap();
+///
+/// cmd.register_type::<HexColor>("fg").unwrap();
+/// assert_eq!(cmd.get::<HexColor>("fg").map(|c| c.rgb()), Some((0xff, 0x88, 0x00)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexColor(pub u8, pub u8, pub u8);
+
+impl HexColor {
+    /// Returns the red, green, and blue components as a tuple.
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        (self.0, self.1, self.2)
+    }
+}
+
+impl str::FromStr for HexColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("invalid hex color: {:?} (accepted: #RRGGBB or #RGB)", s);
+
+        let hex = s.strip_prefix('#').ok_or_else(err)?;
+        if !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(err());
+        }
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).unwrap();
+        let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+
+        match hex.len() {
+            3 => Ok(HexColor(digit(0) * 17, digit(1) * 17, digit(2) * 17)),
+            6 => Ok(HexColor(pair(0), pair(2), pair(4))),
+            _ => Err(err()),
+        }
+    }
+}
+
+/// A point in time converted from an RFC 3339 timestamp like
+/// `2024-05-01T12:34:56Z` or `2024-05-01T21:34:56.789+09:00`.
+///
+/// When the `relative-time` feature is enabled, relative forms are also
+/// accepted: `now`, `today`, `yesterday`, `tomorrow` (`today` is the start
+/// of the current day in UTC), and a duration of `HumanDuration` preceded by
+/// `-` or `+`, like `-2h`, which is relative to the current time.
+///
+/// ```
+/// use cliargs::values::Timestamp;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let t: Timestamp = "1970-01-02T00:00:00+01:00".parse().unwrap();
+/// assert_eq!(t.0, UNIX_EPOCH + Duration::from_secs(82800));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub time::SystemTime);
+
+impl str::FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[cfg(feature = "relative-time")]
+        if let Some(t) = parse_relative_time(s) {
+            return Ok(Timestamp(t));
+        }
+
+        parse_rfc3339(s).map(Timestamp).ok_or_else(|| {
+            if cfg!(feature = "relative-time") {
+                format!(
+                    "invalid timestamp: {:?} (accepted: RFC 3339 like 2024-05-01T12:34:56Z, now, today, yesterday, tomorrow, or -/+ duration)",
+                    s
+                )
+            } else {
+                format!(
+                    "invalid timestamp: {:?} (accepted: RFC 3339 like 2024-05-01T12:34:56Z)",
+                    s
+                )
+            }
+        })
+    }
+}
+
+fn parse_rfc3339(s: &str) -> Option<time::SystemTime> {
+    let b = s.as_bytes();
+    if b.len() < 20 {
+        return None;
+    }
+    let num = |r: std::ops::Range<usize>| -> Option<i64> {
+        let t = s.get(r)?;
+        if !t.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        t.parse().ok()
+    };
+
+    let year = num(0..4)?;
+    let month = num(5..7)?;
+    let day = num(8..10)?;
+    let hour = num(11..13)?;
+    let min = num(14..16)?;
+    let sec = num(17..19)?;
+    if b[4] != b'-'
+        || b[7] != b'-'
+        || !matches!(b[10], b'T' | b't' | b' ')
+        || b[13] != b':'
+        || b[16] != b':'
+    {
+        return None;
+    }
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    if hour > 23 || min > 59 || sec > 60 {
+        return None;
+    }
+
+    let mut rest = &s[19..];
+    let mut nanos: u32 = 0;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let len = frac
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(frac.len());
+        if len == 0 {
+            return None;
+        }
+        let digits = &frac[..len.min(9)];
+        nanos = digits.parse::<u32>().ok()? * 10_u32.pow(9 - digits.len() as u32);
+        rest = &frac[len..];
+    }
+
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let ob = rest.as_bytes();
+            if ob.len() != 6 || ob[3] != b':' {
+                return None;
+            }
+            let sign = match ob[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let oh = num(s.len() - 5..s.len() - 3)?;
+            let om = num(s.len() - 2..s.len())?;
+            if oh > 23 || om > 59 {
+                return None;
+            }
+            sign * (oh * 3600 + om * 60)
+        }
+    };
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86400 + hour * 3600 + min * 60 + sec - offset;
+    if secs >= 0 {
+        time::UNIX_EPOCH.checked_add(time::Duration::new(secs as u64, nanos))
+    } else {
+        time::UNIX_EPOCH
+            .checked_sub(time::Duration::from_secs(secs.unsigned_abs()))?
+            .checked_add(time::Duration::new(0, nanos))
+    }
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+#[cfg(feature = "relative-time")]
+fn parse_relative_time(s: &str) -> Option<time::SystemTime> {
+    let now = time::SystemTime::now();
+    let day = time::Duration::from_secs(86400);
+    let today = || {
+        let secs = now.duration_since(time::UNIX_EPOCH).ok()?.as_secs();
+        Some(time::UNIX_EPOCH + time::Duration::from_secs(secs - secs % 86400))
+    };
+    match s {
+        "now" => Some(now),
+        "today" => today(),
+        "yesterday" => today()?.checked_sub(day),
+        "tomorrow" => today()?.checked_add(day),
+        _ => {
+            if let Some(d) = s.strip_prefix('-') {
+                now.checked_sub(d.parse::<HumanDuration>().ok()?.0)
+            } else if let Some(d) = s.strip_prefix('+') {
+                now.checked_add(d.parse::<HumanDuration>().ok()?.0)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// A number converted after normalizing digit separators and decimal marks,
+/// like `1_000_000`, `1,000,000`, `1.234,5`, or `0,75`.
+///
+/// Underscores are removed, and then `,` and `.` are interpreted as follows
+/// before converting with `FromStr` of `T`:
+///
+/// - If both `,` and `.` are present, the last one is the decimal mark and
+///   the other is a thousands separator.
+/// - If either occurs more than once, it is a thousands separator.
+/// - A single `,` followed by exactly three digits is a thousands separator,
+///   and otherwise it is a decimal mark.
+/// - A single `.` is a decimal mark.
+///
+/// ```
+/// use cliargs::values::LenientNumber;
+///
+/// assert_eq!("1,000,000".parse::<LenientNumber<u32>>().unwrap().0, 1_000_000);
+/// assert_eq!("1.234,5".parse::<LenientNumber<f64>>().unwrap().0, 1234.5);
+/// assert_eq!("0,75".parse::<LenientNumber<f64>>().unwrap().0, 0.75);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct LenientNumber<T>(pub T);
+
+impl<T> str::FromStr for LenientNumber<T>
+where
+    T: str::FromStr,
+    <T as str::FromStr>::Err: fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = normalize_number(s);
+        normalized
+            .parse::<T>()
+            .map(LenientNumber)
+            .map_err(|e| format!("{} (normalized: {:?})", e, normalized))
+    }
+}
+
+fn normalize_number(s: &str) -> String {
+    let s: String = s.chars().filter(|ch| *ch != '_').collect();
+
+    let commas = s.matches(',').count();
+    let dots = s.matches('.').count();
+
+    let (group, decimal) = if commas > 0 && dots > 0 {
+        if s.rfind(',') > s.rfind('.') {
+            (Some('.'), Some(','))
+        } else {
+            (Some(','), Some('.'))
+        }
+    } else if commas > 1 {
+        (Some(','), None)
+    } else if dots > 1 {
+        (Some('.'), None)
+    } else if commas == 1 {
+        let after = &s[s.find(',').unwrap() + 1..];
+        if after.len() == 3 && after.chars().all(|ch| ch.is_ascii_digit()) {
+            (Some(','), None)
+        } else {
+            (None, Some(','))
+        }
+    } else {
+        (None, None)
+    };
+
+    s.chars()
+        .filter(|ch| Some(*ch) != group)
+        .map(|ch| if Some(ch) == decimal { '.' } else { ch })
+        .collect()
+}
+
+/// The trait for fieldless enums of which the variants are given as option
+/// arguments by their names, like `json` of `--format json`.
+///
+/// This trait is implemented with the `opt_value!` macro, which also
+/// implements `FromStr` and `Display` with the names, so that the enum can be
+/// used with `Cmd#register_type` and `Cmd#opt_args_as`, and validated with
+/// `validators::validate_opt_value`.
+///
+/// ```
+/// use cliargs::{opt_value, Cmd, OptCfg};
+/// use cliargs::OptCfgParam::{names, has_arg, validator, complete, arg_in_help};
+/// use cliargs::validators::validate_opt_value;
+/// use cliargs::values::OptValue;
+///
+/// opt_value! {
+///     #[derive(Debug, Clone, Copy, PartialEq)]
+///     enum Format {
+///         Json = "json",
+///         Yaml = "yaml",
+///     }
+/// }
+///
+/// let opt_cfgs = vec![OptCfg::with(&[
+///     names(&["format"]),
+///     has_arg(true),
+///     validator(validate_opt_value::<Format>),
+///     complete(Format::choices()),
+///     arg_in_help(&Format::arg_in_help()),
+/// ])];
+///
+/// let mut cmd = Cmd::with_strings(["app", "--format=yaml"].map(String::from));
+/// cmd.parse_with(&opt_cfgs).unwrap();
+/// assert_eq!(cmd.opt_args_as::<Format>("format").unwrap(), [Format::Yaml]);
+///
+/// let mut cmd = Cmd::with_strings(["app", "--format=xml"].map(String::from));
+/// assert!(cmd.parse_with(&opt_cfgs).is_err());
+/// ```
+pub trait OptValue: Sized + 'static {
+    /// The names of the variants in the declared order.
+    const NAMES: &'static [&'static str];
+
+    /// Returns the variant of the specified name, or `None` if there is no
+    /// such variant.
+    fn from_name(name: &str) -> Option<Self>;
+
+    /// Returns the name of this variant.
+    fn name(&self) -> &'static str;
+
+    /// Returns the names of the variants as a hint for completion.
+    fn choices() -> ValueHint {
+        ValueHint::Choices(Self::NAMES.iter().map(|s| s.to_string()).collect())
+    }
+
+    /// Returns the display of the option argument in a help text, like
+    /// `<json|yaml>`.
+    fn arg_in_help() -> String {
+        format!("<{}>", Self::NAMES.join("|"))
+    }
+}
+
+/// Declares a fieldless enum of which the variants are given as option
+/// arguments by the specified names, and implements `values::OptValue`,
+/// `FromStr`, and `Display` for it.
+///
+/// Each variant is written with its name, like `Json = "json"`.
+/// The conversion from a string fails with the message which shows the
+/// possible names.
+///
+/// ```
+/// use cliargs::opt_value;
+///
+/// opt_value! {
+///     #[derive(Debug, PartialEq)]
+///     pub enum Level {
+///         Low = "low",
+///         High = "high",
+///     }
+/// }
+///
+/// assert_eq!("high".parse::<Level>(), Ok(Level::High));
+/// assert_eq!(Level::Low.to_string(), "low");
+/// assert_eq!(
+///     "mid".parse::<Level>(),
+///     Err("invalid value: \"mid\" (possible values: low, high)".to_string())
+/// );
+/// ```
+#[macro_export]
+macro_rules! opt_value {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident = $vname:literal),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$vmeta])* $variant),*
+        }
+
+        impl $crate::values::OptValue for $name {
+            const NAMES: &'static [&'static str] = &[$($vname),*];
+
+            fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($vname => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $vname,)*
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                <Self as $crate::values::OptValue>::from_name(s).ok_or_else(|| {
+                    format!(
+                        "invalid value: {:?} (possible values: {})",
+                        s,
+                        <Self as $crate::values::OptValue>::NAMES.join(", ")
+                    )
+                })
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str($crate::values::OptValue::name(self))
+            }
+        }
+    };
+}
+
+/// Converts the specified string to the type `T` with `FromStr`, and, if it
+/// fails, retries with the decimal form of an integer literal with a radix
+/// prefix, like `0xFF`, `0o755`, or `0b1010`.
+///
+/// The retry is done only if `T` is an integer type, which is judged by that
+/// `T` cannot be converted from a decimal fraction.
+/// If the retry also fails, this function returns the error of the first
+/// conversion.
+pub(crate) fn parse_number<T: str::FromStr>(s: &str) -> Result<T, T::Err> {
+    match s.parse::<T>() {
+        Ok(value) => Ok(value),
+        Err(err) if "0.5".parse::<T>().is_ok() => Err(err),
+        Err(err) => match radix_to_decimal(s) {
+            Some(decimal) => decimal.parse::<T>().or(Err(err)),
+            None => Err(err),
+        },
+    }
+}
+
+fn radix_to_decimal(s: &str) -> Option<String> {
+    let (sign, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", s.strip_prefix('+').unwrap_or(s)),
+    };
+    let radix = match unsigned.get(0..2)? {
+        "0x" | "0X" => 16,
+        "0o" | "0O" => 8,
+        "0b" | "0B" => 2,
+        _ => return None,
+    };
+    let digits = &unsigned[2..];
+    if digits.starts_with(['+', '-']) {
+        return None;
+    }
+    let n = u128::from_str_radix(digits, radix).ok()?;
+    Some(format!("{}{}", sign, n))
+}
+
+/// Converts a decimal number with an SI prefix as a suffix, like `1.5k` or
+/// `2M`, to the number in the base unit, like `1500` or `2000000`.
+///
+/// The conversion is done by shifting the decimal point, so it has no
+/// rounding error.
+/// If the specified string does not end with an SI prefix or the rest is not
+/// a decimal number, this function returns `None`.
+pub(crate) fn expand_si_suffix(s: &str) -> Option<String> {
+    let (i, suffix) = s.char_indices().last()?;
+    let exp: i32 = match suffix {
+        'p' => -12,
+        'n' => -9,
+        'u' | 'µ' => -6,
+        'm' => -3,
+        'k' | 'K' => 3,
+        'M' => 6,
+        'G' => 9,
+        'T' => 12,
+        'P' => 15,
+        'E' => 18,
+        _ => return None,
+    };
+
+    let num = &s[..i];
+    let (sign, num) = match num.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", num.strip_prefix('+').unwrap_or(num)),
+    };
+    let (int_part, frac_part) = num.split_once('.').unwrap_or((num, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+    if !int_part
+        .chars()
+        .chain(frac_part.chars())
+        .all(|ch| ch.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let digits = format!("{}{}", int_part, frac_part);
+    let point = int_part.len() as i32 + exp;
+    let (int_part, frac_part) = if point <= 0 {
+        (
+            "0".to_string(),
+            format!("{}{}", "0".repeat(point.unsigned_abs() as usize), digits),
+        )
+    } else if point as usize >= digits.len() {
+        (
+            format!("{}{}", digits, "0".repeat(point as usize - digits.len())),
+            String::new(),
+        )
+    } else {
+        let (a, b) = digits.split_at(point as usize);
+        (a.to_string(), b.to_string())
+    };
+
+    let int_part = int_part.trim_start_matches('0');
+    let int_part = if int_part.is_empty() { "0" } else { int_part };
+    let frac_part = frac_part.trim_end_matches('0');
+    if frac_part.is_empty() {
+        Some(format!("{}{}", sign, int_part))
+    } else {
+        Some(format!("{}{}.{}", sign, int_part, frac_part))
+    }
+}
+
+#[cfg(test)]
+mod tests_of_values {
+    use super::*;
+
+    mod tests_of_parse_number {
+        use super::*;
+
+        #[test]
+        fn should_parse_integers_with_radix_prefixes() {
+            assert_eq!(parse_number::<u8>("0xFF"), Ok(255));
+            assert_eq!(parse_number::<u32>("0o755"), Ok(0o755));
+            assert_eq!(parse_number::<i8>("0b1010"), Ok(10));
+            assert_eq!(parse_number::<i16>("-0X10"), Ok(-16));
+            assert_eq!(parse_number::<i16>("+0B11"), Ok(3));
+            assert_eq!(parse_number::<i8>("-0x80"), Ok(-128));
+            assert_eq!(parse_number::<u64>("123"), Ok(123));
+        }
+
+        #[test]
+        fn should_return_error_of_first_conversion() {
+            match parse_number::<u8>("0x100") {
+                Err(err) => assert_eq!(err.to_string(), "invalid digit found in string"),
+                Ok(_) => panic!(),
+            }
+            assert!(parse_number::<u8>("0xFG").is_err());
+            assert!(parse_number::<u8>("0x").is_err());
+            assert!(parse_number::<u8>("0x-1").is_err());
+            assert!(parse_number::<u8>("-0x1").is_err());
+            assert_eq!(parse_number::<String>("0xFF"), Ok("0xFF".to_string()));
+            assert!(parse_number::<f64>("0x10").is_err());
+        }
+    }
+
+    mod tests_of_expand_si_suffix {
+        use super::*;
+
+        #[test]
+        fn should_convert_to_base_unit() {
+            assert_eq!(expand_si_suffix("1.5k"), Some("1500".to_string()));
+            assert_eq!(expand_si_suffix("2M"), Some("2000000".to_string()));
+            assert_eq!(expand_si_suffix("-3K"), Some("-3000".to_string()));
+            assert_eq!(expand_si_suffix("+1.25G"), Some("1250000000".to_string()));
+            assert_eq!(
+                expand_si_suffix("1234.5678k"),
+                Some("1234567.8".to_string())
+            );
+            assert_eq!(expand_si_suffix("1.5m"), Some("0.0015".to_string()));
+            assert_eq!(expand_si_suffix("250u"), Some("0.00025".to_string()));
+            assert_eq!(expand_si_suffix("3µ"), Some("0.000003".to_string()));
+            assert_eq!(expand_si_suffix(".5k"), Some("500".to_string()));
+            assert_eq!(expand_si_suffix("0010.00k"), Some("10000".to_string()));
+            assert_eq!(
+                expand_si_suffix("1E"),
+                Some("1000000000000000000".to_string())
+            );
+        }
+
+        #[test]
+        fn should_return_none_if_not_number_with_suffix() {
+            assert_eq!(expand_si_suffix("123"), None);
+            assert_eq!(expand_si_suffix("k"), None);
+            assert_eq!(expand_si_suffix(".k"), None);
+            assert_eq!(expand_si_suffix("1e3k"), None);
+            assert_eq!(expand_si_suffix("1.2.3k"), None);
+            assert_eq!(expand_si_suffix("1x"), None);
+            assert_eq!(expand_si_suffix(""), None);
+        }
+    }
+
+    mod tests_of_human_duration {
+        use super::*;
+        use std::time::Duration;
+
+        #[test]
+        fn should_parse_durations() {
+            let d = |s: &str| s.parse::<HumanDuration>().map(|d| d.0);
+            assert_eq!(d("250ms"), Ok(Duration::from_millis(250)));
+            assert_eq!(d("1.5h"), Ok(Duration::from_secs(5400)));
+            assert_eq!(d("2d"), Ok(Duration::from_secs(172800)));
+            assert_eq!(d("1h30m10s"), Ok(Duration::from_secs(5410)));
+            assert_eq!(d("10us"), Ok(Duration::from_micros(10)));
+            assert_eq!(d("7ns"), Ok(Duration::from_nanos(7)));
+            assert_eq!(d("0s"), Ok(Duration::ZERO));
+        }
+
+        #[test]
+        fn should_fail_to_parse_invalid_durations() {
+            for s in ["", "10", "ms", "1x", "-1s", "1..5s", "1 s", "1e3s"] {
+                match s.parse::<HumanDuration>() {
+                    Ok(_) => panic!(),
+                    Err(e) => assert_eq!(
+                        e,
+                        format!(
+                            "invalid duration: {:?} (accepted units: ns, us, ms, s, m, h, d)",
+                            s
+                        )
+                    ),
+                }
+            }
+        }
+    }
+
+    mod tests_of_byte_size {
+        use super::*;
+
+        #[test]
+        fn should_parse_byte_sizes() {
+            let b = |s: &str| s.parse::<ByteSize>().map(|b| b.0);
+            assert_eq!(b("512"), Ok(512));
+            assert_eq!(b("512B"), Ok(512));
+            assert_eq!(b("10K"), Ok(10_000));
+            assert_eq!(b("10kb"), Ok(10_000));
+            assert_eq!(b("2MiB"), Ok(2 * 1024 * 1024));
+            assert_eq!(b("2mi"), Ok(2 * 1024 * 1024));
+            assert_eq!(b("3G"), Ok(3_000_000_000));
+            assert_eq!(b("1Gi"), Ok(1 << 30));
+            assert_eq!(b("1Ti"), Ok(1 << 40));
+            assert_eq!(b("1.5K"), Ok(1500));
+            assert_eq!(b("0.5KiB"), Ok(512));
+            assert_eq!(b("4.1M"), Ok(4_100_000));
+            assert_eq!(b("0.001K"), Ok(1));
+            assert_eq!(b("1.50K"), Ok(1500));
+            assert_eq!(b("18446744073709551615"), Ok(u64::MAX));
+        }
+
+        #[test]
+        fn should_fail_to_parse_invalid_byte_sizes() {
+            for s in [
+                "",
+                "K",
+                "10X",
+                "10 K",
+                "-1",
+                "0.3B",
+                "1.5.1K",
+                "99999999999T",
+                "18446744073709551616",
+                "0.0001K",
+                ".",
+            ] {
+                match s.parse::<ByteSize>() {
+                    Ok(_) => panic!(),
+                    Err(e) => assert_eq!(
+                        e,
+                        format!(
+                            "invalid byte size: {:?} (accepted suffixes: K, M, G, T, Ki, Mi, Gi, Ti, optionally followed by B)",
+                            s
+                        )
+                    ),
+                }
+            }
+        }
+    }
+
+    mod tests_of_ratio {
+        use super::*;
+
+        #[test]
+        fn should_parse_ratios() {
+            let r = |s: &str| s.parse::<Ratio>().map(|r| r.0);
+            assert_eq!(r("75%"), Ok(0.75));
+            assert_eq!(r("0.75"), Ok(0.75));
+            assert_eq!(r("0%"), Ok(0.0));
+            assert_eq!(r("100%"), Ok(1.0));
+            assert_eq!(r("1"), Ok(1.0));
+            assert_eq!(r("12.5%"), Ok(0.125));
+        }
+
+        #[test]
+        fn should_fail_to_parse_invalid_ratios() {
+            for s in ["", "%", "101%", "1.5", "-0.1", "abc", "50 %", "NaN"] {
+                match s.parse::<Ratio>() {
+                    Ok(_) => panic!(),
+                    Err(e) => assert_eq!(
+                        e,
+                        format!("invalid ratio: {:?} (accepted: 0% to 100%, or 0 to 1)", s)
+                    ),
+                }
+            }
+        }
+    }
+
+    mod tests_of_hex_color {
+        use super::*;
+
+        #[test]
+        fn should_parse_hex_colors() {
+            let c = |s: &str| s.parse::<HexColor>().map(|c| c.rgb());
+            assert_eq!(c("#000000"), Ok((0, 0, 0)));
+            assert_eq!(c("#FF8000"), Ok((255, 128, 0)));
+            assert_eq!(c("#1a2B3c"), Ok((0x1a, 0x2b, 0x3c)));
+            assert_eq!(c("#fff"), Ok((255, 255, 255)));
+            assert_eq!(c("#f80"), Ok((255, 136, 0)));
+        }
+
+        #[test]
+        fn should_fail_to_parse_invalid_hex_colors() {
+            for s in [
+                "", "#", "fff", "#ff", "#ffff", "#gggggg", "#+fffff", "#fffffff",
+            ] {
+                match s.parse::<HexColor>() {
+                    Ok(_) => panic!(),
+                    Err(e) => assert_eq!(
+                        e,
+                        format!("invalid hex color: {:?} (accepted: #RRGGBB or #RGB)", s)
+                    ),
+                }
+            }
+        }
+    }
+
+    mod tests_of_timestamp {
+        use super::*;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        #[test]
+        fn should_parse_rfc3339_timestamps() {
+            let t = |s: &str| s.parse::<Timestamp>().map(|t| t.0);
+            assert_eq!(t("1970-01-01T00:00:00Z"), Ok(UNIX_EPOCH));
+            assert_eq!(
+                t("2024-02-29T12:34:56Z"),
+                Ok(UNIX_EPOCH + Duration::from_secs(1709210096))
+            );
+            assert_eq!(
+                t("2024-02-29t21:34:56+09:00"),
+                Ok(UNIX_EPOCH + Duration::from_secs(1709210096))
+            );
+            assert_eq!(
+                t("2024-02-29 07:04:56.25-05:30"),
+                Ok(UNIX_EPOCH + Duration::new(1709210096, 250_000_000))
+            );
+            assert_eq!(
+                t("1969-12-31T23:59:59.5Z"),
+                Ok(UNIX_EPOCH - Duration::from_millis(500))
+            );
+        }
+
+        #[test]
+        fn should_fail_to_parse_invalid_timestamps() {
+            for s in [
+                "",
+                "2024-02-29",
+                "2023-02-29T00:00:00Z",
+                "2024-13-01T00:00:00Z",
+                "2024-01-01T24:00:00Z",
+                "2024-01-01T00:00:00",
+                "2024-01-01T00:00:00+0900",
+                "2024-01-01T00:00:00.Z",
+                "2024/01/01T00:00:00Z",
+                "+2024-01-01T00:00:00Z",
+            ] {
+                match s.parse::<Timestamp>() {
+                    Ok(_) => panic!(),
+                    Err(e) => {
+                        assert!(e.starts_with(&format!("invalid timestamp: {:?}", s)))
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature = "relative-time")]
+        #[test]
+        fn should_parse_relative_times() {
+            let before = time::SystemTime::now();
+            let t = "-2h".parse::<Timestamp>().unwrap().0;
+            assert!(t <= before - Duration::from_secs(7200) + Duration::from_secs(5));
+            assert!(t >= before - Duration::from_secs(7200));
+
+            let today = "today".parse::<Timestamp>().unwrap().0;
+            let yesterday = "yesterday".parse::<Timestamp>().unwrap().0;
+            let tomorrow = "tomorrow".parse::<Timestamp>().unwrap().0;
+            assert_eq!(
+                today.duration_since(UNIX_EPOCH).unwrap().as_secs() % 86400,
+                0
+            );
+            assert_eq!(
+                today.duration_since(yesterday).ok(),
+                Some(Duration::from_secs(86400))
+            );
+            assert_eq!(
+                tomorrow.duration_since(today).ok(),
+                Some(Duration::from_secs(86400))
+            );
+            assert!("now".parse::<Timestamp>().is_ok());
+            assert!("-2x".parse::<Timestamp>().is_err());
+        }
+    }
+
+    mod tests_of_lenient_number {
+        use super::*;
+
+        #[test]
+        fn should_normalize_numbers() {
+            assert_eq!(normalize_number("1_000_000"), "1000000");
+            assert_eq!(normalize_number("1,000,000"), "1000000");
+            assert_eq!(normalize_number("1.000.000"), "1000000");
+            assert_eq!(normalize_number("1,000"), "1000");
+            assert_eq!(normalize_number("1,5"), "1.5");
+            assert_eq!(normalize_number("1.5"), "1.5");
+            assert_eq!(normalize_number("1,234.5"), "1234.5");
+            assert_eq!(normalize_number("1.234,5"), "1234.5");
+            assert_eq!(normalize_number("-0,25"), "-0.25");
+            assert_eq!(normalize_number("abc"), "abc");
+        }
+
+        #[test]
+        fn should_parse_lenient_numbers() {
+            assert_eq!(
+                "1_000_000".parse::<LenientNumber<i64>>(),
+                Ok(LenientNumber(1_000_000))
+            );
+            assert_eq!(
+                "1,000".parse::<LenientNumber<u16>>(),
+                Ok(LenientNumber(1000))
+            );
+            assert_eq!("2,5".parse::<LenientNumber<f32>>(), Ok(LenientNumber(2.5)));
+            assert_eq!("12".parse::<LenientNumber<u8>>(), Ok(LenientNumber(12)));
+        }
+
+        #[test]
+        fn should_fail_to_parse_invalid_numbers() {
+            assert_eq!(
+                "1,5".parse::<LenientNumber<u32>>(),
+                Err("invalid digit found in string (normalized: \"1.5\")".to_string())
+            );
+            assert_eq!(
+                "x".parse::<LenientNumber<f64>>(),
+                Err("invalid float literal (normalized: \"x\")".to_string())
+            );
+        }
+    }
+
+    mod tests_of_opt_value {
+        use super::*;
+
+        crate::opt_value! {
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            enum Format {
+                Json = "json",
+                Yaml = "yaml",
+                TomlV1 = "toml-v1",
+            }
+        }
+
+        #[test]
+        fn should_convert_from_and_to_names() {
+            assert_eq!(Format::NAMES, ["json", "yaml", "toml-v1"]);
+            assert_eq!(Format::from_name("toml-v1"), Some(Format::TomlV1));
+            assert_eq!(Format::from_name("Json"), None);
+            assert_eq!(Format::Yaml.name(), "yaml");
+
+            assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+            assert_eq!(
+                "xml".parse::<Format>(),
+                Err("invalid value: \"xml\" (possible values: json, yaml, toml-v1)".to_string())
+            );
+            assert_eq!(format!("{}", Format::TomlV1), "toml-v1");
+        }
+
+        #[test]
+        fn should_create_choices_and_arg_in_help() {
+            assert_eq!(
+                Format::choices(),
+                ValueHint::Choices(vec![
+                    "json".to_string(),
+                    "yaml".to_string(),
+                    "toml-v1".to_string()
+                ])
+            );
+            assert_eq!(Format::arg_in_help(), "<json|yaml|toml-v1>");
+        }
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(test)]
 mod tests_of_parse {
+    // Kept to check that the crate can be imported by its name.
+    #[allow(clippy::single_component_path_imports)]
     use cliargs;
     use std::ffi;
 
@@ -8,7 +10,7 @@ mod tests_of_parse {
         let mut cmd = cliargs::Cmd::new().unwrap();
         match cmd.parse() {
             Ok(_) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
         println!("cmd = {cmd:?}");
         assert!(cmd.name().starts_with("parse_test-"));
@@ -25,15 +27,15 @@ mod tests_of_parse {
         ]);
         match cmd.parse() {
             Ok(_) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
         println!("cmd = {cmd:?}");
         assert_eq!(cmd.name(), "app");
         assert_eq!(cmd.args(), ["bar", "qux"]);
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), Some("123"));
         assert_eq!(cmd.opt_args("foo-bar"), Some(&["123"] as &[&str]));
-        assert_eq!(cmd.has_opt("baz"), true);
+        assert!(cmd.has_opt("baz"));
         assert_eq!(cmd.opt_arg("baz"), None);
         assert_eq!(cmd.opt_args("baz"), Some(&[] as &[&str]));
     }
@@ -50,15 +52,15 @@ mod tests_of_parse {
         .unwrap();
         match cmd.parse() {
             Ok(_) => {}
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
         println!("cmd = {cmd:?}");
         assert_eq!(cmd.name(), "app");
         assert_eq!(cmd.args(), ["bar", "qux"]);
-        assert_eq!(cmd.has_opt("foo-bar"), true);
+        assert!(cmd.has_opt("foo-bar"));
         assert_eq!(cmd.opt_arg("foo-bar"), Some("123"));
         assert_eq!(cmd.opt_args("foo-bar"), Some(&["123"] as &[&str]));
-        assert_eq!(cmd.has_opt("baz"), true);
+        assert!(cmd.has_opt("baz"));
         assert_eq!(cmd.opt_arg("baz"), None);
         assert_eq!(cmd.opt_args("baz"), Some(&[] as &[&str]));
     }
@@ -66,6 +68,8 @@ mod tests_of_parse {
 
 #[cfg(test)]
 mod tests_of_errors {
+    // Kept to check that the crate can be imported by its name.
+    #[allow(clippy::single_component_path_imports)]
     use cliargs;
     use std::ffi;
 
@@ -83,7 +87,7 @@ mod tests_of_errors {
             ffi::OsString::from("--baz"),
             ffi::OsString::from("qux"),
         ]) {
-            Ok(_) => assert!(false),
+            Ok(_) => panic!(),
             Err(cliargs::errors::InvalidOsArg::OsArgsContainInvalidUnicode { index, os_arg }) => {
                 assert_eq!(index, 2);
                 assert_eq!(os_arg, bad_os_string);
@@ -100,11 +104,11 @@ mod tests_of_errors {
             "qux".to_string(),
         ]);
         match cmd.parse() {
-            Ok(_) => assert!(false),
+            Ok(_) => panic!(),
             Err(cliargs::errors::InvalidOption::OptionContainsInvalidChar { option }) => {
                 assert_eq!(option, "b@z");
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
     }
 }
@@ -29,17 +29,17 @@ mod tests_of_parse_with {
 
         if let Err(err) = cmd.parse_with(&opt_cfgs) {
             println!("{:?}", err);
-            assert!(false);
+            panic!();
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("fooBar"), true);
+        assert!(cmd.has_opt("fooBar"));
         assert_eq!(cmd.opt_arg("fooBar"), None);
         assert_eq!(cmd.opt_args("fooBar"), Some(&[] as &[&str]));
-        assert_eq!(cmd.has_opt("baz"), true);
+        assert!(cmd.has_opt("baz"));
         assert_eq!(cmd.opt_arg("baz"), Some("ABC"));
         assert_eq!(cmd.opt_args("baz"), Some(&["ABC"] as &[&str]));
-        assert_eq!(cmd.has_opt("qux"), true);
+        assert!(cmd.has_opt("qux"));
         assert_eq!(cmd.opt_arg("qux"), Some("123"));
         assert_eq!(cmd.opt_args("qux"), Some(&["123", "456"] as &[&str]));
     }
@@ -83,17 +83,17 @@ mod tests_of_errors {
             assert_eq!(sk, "fooBar");
             assert_eq!(option, "f");
         } else {
-            assert!(false);
+            panic!();
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("fooBar"), false);
+        assert!(!cmd.has_opt("fooBar"));
         assert_eq!(cmd.opt_arg("fooBar"), None);
         assert_eq!(cmd.opt_args("fooBar"), None);
-        assert_eq!(cmd.has_opt("baz"), true);
+        assert!(cmd.has_opt("baz"));
         assert_eq!(cmd.opt_arg("baz"), Some("ABC"));
         assert_eq!(cmd.opt_args("baz"), Some(&["ABC"] as &[&str]));
-        assert_eq!(cmd.has_opt("qux"), true);
+        assert!(cmd.has_opt("qux"));
         assert_eq!(cmd.opt_arg("qux"), Some("123"));
         assert_eq!(cmd.opt_args("qux"), Some(&["123", "456"] as &[&str]));
     }
@@ -133,17 +133,17 @@ mod tests_of_errors {
             assert_eq!(opt_arg, "xxx");
             assert_eq!(details, "invalid digit found in string");
         } else {
-            assert!(false);
+            panic!();
         }
 
         assert_eq!(cmd.name(), "app");
-        assert_eq!(cmd.has_opt("fooBar"), true);
+        assert!(cmd.has_opt("fooBar"));
         assert_eq!(cmd.opt_arg("fooBar"), None);
         assert_eq!(cmd.opt_args("fooBar"), Some(&[] as &[&str]));
-        assert_eq!(cmd.has_opt("baz"), true);
+        assert!(cmd.has_opt("baz"));
         assert_eq!(cmd.opt_arg("baz"), Some("ABC"));
         assert_eq!(cmd.opt_args("baz"), Some(&["ABC"] as &[&str]));
-        assert_eq!(cmd.has_opt("qux"), true);
+        assert!(cmd.has_opt("qux"));
         assert_eq!(cmd.opt_arg("qux"), Some("123"));
         assert_eq!(cmd.opt_args("qux"), Some(&["123"] as &[&str]));
     }